@@ -28,7 +28,7 @@ export class CircuitRelayer {
         return new Promise((res: any, rej: any) => {
             // console.log(args.sideEffects.toHuman())
             return this.circuit.tx.circuit
-                .onExtrinsicTrigger(args.sideEffects.toHuman(), args.fee, args.sequential)
+                .onExtrinsicTrigger(args.sideEffects.toHuman(), args.fee, args.sequential, args.steps, args.delayStepsAt, args.timeout)
                 .signAndSend(this.signer, async result => {
                     // @ts-ignore
                     if (result && result.toHuman().dispatchError !== undefined) { // The pallet doesn't return a proper error
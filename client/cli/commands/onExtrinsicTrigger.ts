@@ -8,5 +8,8 @@ export const onExtrinsicTrigger = (circuitApi: ApiPromise, sideEffects: any[], s
         ),
         fee: circuitApi.createType("Currency::Balance", 0),
         sequential: circuitApi.createType("bool", sequential),
+        steps: circuitApi.createType("Option<Vec<u32>>", null),
+        delayStepsAt: circuitApi.createType("Option<Vec<BlockNumber>>", null),
+        timeout: circuitApi.createType("Option<BlockNumber>", null),
     }
 }
\ No newline at end of file
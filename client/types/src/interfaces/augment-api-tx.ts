@@ -405,9 +405,29 @@ declare module "@polkadot/api-base/types/submittable" {
                 | Uint8Array
               )[],
           fee: u128 | AnyNumber | Uint8Array,
-          sequential: bool | boolean | Uint8Array
+          sequential: bool | boolean | Uint8Array,
+          steps:
+            | Option<Vec<u32>>
+            | null
+            | Uint8Array
+            | Vec<u32>
+            | (u32 | AnyNumber | Uint8Array)[],
+          delayStepsAt:
+            | Option<Vec<u32>>
+            | null
+            | Uint8Array
+            | Vec<u32>
+            | (u32 | AnyNumber | Uint8Array)[],
+          timeout: Option<u32> | null | Uint8Array | u32 | AnyNumber
         ) => SubmittableExtrinsic<ApiType>,
-        [Vec<T3rnTypesSideEffect>, u128, bool]
+        [
+          Vec<T3rnTypesSideEffect>,
+          u128,
+          bool,
+          Option<Vec<u32>>,
+          Option<Vec<u32>>,
+          Option<u32>
+        ]
       >;
       /** Used by other pallets that want to create the exec order */
       onLocalTrigger: AugmentedSubmittable<
@@ -1094,6 +1094,9 @@ export default {
         sideEffects: "Vec<T3rnTypesSideEffect>",
         fee: "u128",
         sequential: "bool",
+        steps: "Option<Vec<u32>>",
+        delayStepsAt: "Option<Vec<u32>>",
+        timeout: "Option<u32>",
       },
       bond_insurance_deposit: {
         xtxId: "H256",
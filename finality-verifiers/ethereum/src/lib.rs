@@ -0,0 +1,1311 @@
+//! Ethereum Finality Verifier Pallet
+//!
+//! This pallet is an on-chain Ethereum light client, built on top of the Snowfork primitives.
+//!
+//! PoW headers are imported together with the Ethash proofs of their seal, and tracked per gateway
+//! with their total difficulty. The header with the highest total difficulty, then the highest
+//! number, is the best one and its ancestor `descendants_until_final` blocks deep is considered
+//! finalized. Headers not descending from the finalized header are rejected, so forks are only
+//! followed above it.
+//!
+//! Gateways registered with a terminal total difficulty follow the chain through the merge. The
+//! PoS headers descending from the terminal PoW block are sealed by the beacon chain, whose sync
+//! committee signatures aren't verified on-chain, so they're only accepted from the gateway owner.
+//! Verifying the beacon chain finality is out of scope of this pallet, so these headers are only
+//! tracked - no SideEffect can be confirmed against them.
+//!
+//! Receipts included in finalized PoW headers can be proven against their receipts root. This
+//! allows the Circuit to confirm SideEffects executed on Ethereum, by the logs they emitted. Native
+//! ETH transfers don't emit any, so only ERC-20 transfers can be confirmed, as `mult` transfers of
+//! the token contract.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use ethereum_types::{H256, H64, U256};
+use frame_support::{ensure, pallet_prelude::*};
+use frame_system::{ensure_signed, pallet_prelude::*, RawOrigin};
+use snowbridge_ethereum::{
+    difficulty::calc_difficulty,
+    ethashproof::{DoubleNodeWithMerkleProof, EthashProver},
+    Header as EthereumHeader, HeaderId, Log,
+};
+use sp_runtime::traits::BadOrigin;
+use sp_std::{convert::TryFrom, vec, vec::Vec};
+
+#[cfg(test)]
+mod mock;
+mod side_effects;
+pub mod types;
+
+use side_effects::decode_log;
+use types::{
+    ChainId, DeinitializationCursor, EthereumGatewaySettings, EthereumHeaderData,
+    EthereumInclusionData, EthereumRegistrationData, StoredHeader,
+};
+
+// Re-export in crate namespace for `construct_runtime!`
+pub use pallet::*;
+
+/// Maximal number of block heights pruned while importing a single header.
+const HEIGHTS_TO_PRUNE_PER_IMPORT: u64 = 8;
+
+/// Maximal number of headers and heights removed by a single deinitialization call.
+pub const MAX_KEYS_REMOVED_PER_DEINITIALIZATION: u32 = 512;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// Number of block heights below the finalized header to keep in the storage.
+        ///
+        /// Older headers are pruned, so receipts included in them can't be proven anymore.
+        #[pallet::constant]
+        type HeadersToKeep: Get<u32>;
+
+        /// Whether imported headers must carry a valid Ethash seal and difficulty.
+        ///
+        /// Should only be disabled for testing.
+        #[pallet::constant]
+        type VerifyPoW: Get<bool>;
+    }
+
+    #[pallet::pallet]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(PhantomData<T>);
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+    /// Headers which have been imported into the pallet, with their total difficulty.
+    #[pallet::storage]
+    #[pallet::getter(fn get_imported_headers)]
+    pub type Headers<T: Config> =
+        StorageDoubleMap<_, Blake2_256, ChainId, Identity, H256, StoredHeader>;
+
+    /// Hashes of the imported headers of each height.
+    #[pallet::storage]
+    pub(super) type HeadersByNumber<T: Config> =
+        StorageDoubleMap<_, Blake2_256, ChainId, Identity, u64, Vec<H256>, ValueQuery>;
+
+    /// Map of the header with the highest total difficulty.
+    #[pallet::storage]
+    pub type BestBlockMap<T: Config> = StorageMap<_, Blake2_256, ChainId, (HeaderId, U256)>;
+
+    /// Map of the best finalized header.
+    #[pallet::storage]
+    #[pallet::getter(fn get_finalized_block)]
+    pub type FinalizedBlockMap<T: Config> = StorageMap<_, Blake2_256, ChainId, HeaderId>;
+
+    /// Lowest height of the headers which haven't been pruned yet.
+    #[pallet::storage]
+    pub(super) type OldestHeaderNumberMap<T: Config> = StorageMap<_, Blake2_256, ChainId, u64>;
+
+    /// Finality and difficulty settings of the gateway.
+    #[pallet::storage]
+    pub(super) type GatewaySettingsMap<T: Config> =
+        StorageMap<_, Blake2_256, ChainId, EthereumGatewaySettings>;
+
+    /// Optional pallet owner.
+    ///
+    /// Pallet owner has a right to halt all pallet operations and then resume them. If it is
+    /// `None`, then there are no direct ways to halt/resume pallet operations, but other
+    /// runtime methods may still be used to do that (i.e. democracy::referendum to update halt
+    /// flag directly or call the `halt_operations`).
+    #[pallet::storage]
+    pub(super) type PalletOwnerMap<T: Config> = StorageMap<_, Blake2_256, ChainId, T::AccountId>;
+
+    /// If true, all header submissions of the gateway are failed immediately.
+    #[pallet::storage]
+    pub(super) type IsHaltedMap<T: Config> = StorageMap<_, Blake2_256, ChainId, bool>;
+
+    /// Progress of the headers removal of the gateways being deinitialized.
+    #[pallet::storage]
+    pub(super) type DeinitializationCursorMap<T: Config> =
+        StorageMap<_, Blake2_256, ChainId, DeinitializationCursor>;
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// The gateway hasn't been initialized
+        NotInitialized,
+        /// The submitted range is empty
+        EmptyRangeSubmitted,
+        /// The given bytes couldn't be decoded as header data
+        HeaderDataDecodingError,
+        /// The header has already been imported
+        DuplicateHeader,
+        /// The parent of the header hasn't been imported
+        MissingParentHeader,
+        /// The header doesn't descend from the finalized header
+        HeaderOnStaleFork,
+        /// The header's seal or difficulty is not valid
+        InvalidHeader,
+        /// The total difficulty of the header overflows
+        TotalDifficultyOverflow,
+        /// The inclusion data couldn't be decoded
+        InclusionDataDecodeError,
+        /// The header couldn't be found in storage
+        UnknownHeader,
+        /// The header hasn't been finalized yet
+        HeaderNotFinalized,
+        /// The receipt proof doesn't match the receipts root of the header
+        InvalidReceiptProof,
+        /// The proven receipt couldn't be decoded
+        ReceiptDecodingFailed,
+        /// The log was not found in the proven receipt
+        EventNotIncluded,
+        /// The logs paramaters couldn't be decoded
+        EventDecodingFailed,
+        /// The side effect is not known for this vendor
+        UnkownSideEffect,
+        /// The gateway is currently halted
+        Halted,
+        /// The block height couldn't be converted
+        BlockHeightConversionError,
+        /// PoS headers are only accepted from the owner of the gateway
+        UntrustedSubmitter,
+        /// SideEffects can't be confirmed against PoS headers, whose finality isn't verified
+        PostMergeConfirmationUnsupported,
+    }
+
+    /// Checks the header's linkage, seal and difficulty, and that it descends from the finalized
+    /// header. The Ethash verification is skipped if `VerifyPoW` is disabled.
+    ///
+    /// Headers descending from the terminal PoW block must carry an empty PoS seal, and can only be
+    /// submitted by trusted submitters.
+    pub(crate) fn validate_header_to_import<T: Config>(
+        gateway_id: ChainId,
+        header: &EthereumHeader,
+        proof: &[DoubleNodeWithMerkleProof],
+        trusted_submitter: bool,
+    ) -> DispatchResult {
+        ensure!(
+            !<Headers<T>>::contains_key(gateway_id, header.compute_hash()),
+            Error::<T>::DuplicateHeader
+        );
+        let stored_parent = <Headers<T>>::get(gateway_id, header.parent_hash)
+            .ok_or(Error::<T>::MissingParentHeader)?;
+        let parent = stored_parent.header;
+        ensure!(
+            header.number == parent.number.saturating_add(1),
+            Error::<T>::InvalidHeader
+        );
+
+        let settings =
+            <GatewaySettingsMap<T>>::get(gateway_id).ok_or(Error::<T>::NotInitialized)?;
+        let is_pos = is_past_terminal_difficulty(&settings, &stored_parent);
+
+        if is_pos {
+            ensure!(trusted_submitter, Error::<T>::UntrustedSubmitter);
+            ensure!(
+                header.difficulty.is_zero()
+                    && !header.has_ommers()
+                    && header.nonce() == Some(H64::zero()),
+                Error::<T>::InvalidHeader
+            );
+        } else if T::VerifyPoW::get() {
+            let difficulty =
+                calc_difficulty(&settings.difficulty_config, header.timestamp, &parent)
+                    .map_err(|_| Error::<T>::InvalidHeader)?;
+            ensure!(header.difficulty == difficulty, Error::<T>::InvalidHeader);
+
+            let header_mix_hash = header.mix_hash().ok_or(Error::<T>::InvalidHeader)?;
+            let header_nonce = header.nonce().ok_or(Error::<T>::InvalidHeader)?;
+            let (mix_hash, result) = EthashProver::new()
+                .hashimoto_merkle(
+                    header.compute_partial_hash(),
+                    header_nonce,
+                    header.number,
+                    proof,
+                )
+                .map_err(|_| Error::<T>::InvalidHeader)?;
+            ensure!(
+                mix_hash == header_mix_hash
+                    && U256::from(result.0) < ethash::cross_boundary(header.difficulty),
+                Error::<T>::InvalidHeader
+            );
+        }
+
+        // walk down to the finalized height, which must be reached on the finalized header
+        let finalized =
+            <FinalizedBlockMap<T>>::get(gateway_id).ok_or(Error::<T>::NotInitialized)?;
+        ensure!(
+            header.number > finalized.number,
+            Error::<T>::HeaderOnStaleFork
+        );
+        let (mut ancestor, mut ancestor_hash) = (parent, header.parent_hash);
+        while ancestor.number > finalized.number {
+            ancestor_hash = ancestor.parent_hash;
+            ancestor = <Headers<T>>::get(gateway_id, ancestor_hash)
+                .ok_or(Error::<T>::HeaderOnStaleFork)?
+                .header;
+        }
+        ensure!(
+            ancestor_hash == finalized.hash,
+            Error::<T>::HeaderOnStaleFork
+        );
+
+        Ok(())
+    }
+
+    /// Whether the total difficulty of the header reached the terminal total difficulty of the
+    /// gateway, so its descendants are PoS headers.
+    pub(crate) fn is_past_terminal_difficulty(
+        settings: &EthereumGatewaySettings,
+        stored: &StoredHeader,
+    ) -> bool {
+        settings
+            .terminal_total_difficulty
+            .map_or(false, |ttd| stored.total_difficulty >= ttd)
+    }
+
+    /// Stores the header and updates the best and finalized headers of the gateway.
+    pub(crate) fn import_validated_header<T: Config>(
+        gateway_id: ChainId,
+        header: EthereumHeader,
+    ) -> DispatchResult {
+        let header_id = HeaderId {
+            number: header.number,
+            hash: header.compute_hash(),
+        };
+        let parent = <Headers<T>>::get(gateway_id, header.parent_hash)
+            .ok_or(Error::<T>::MissingParentHeader)?;
+        let total_difficulty = parent
+            .total_difficulty
+            .checked_add(header.difficulty)
+            .ok_or(Error::<T>::TotalDifficultyOverflow)?;
+
+        <Headers<T>>::insert(
+            gateway_id,
+            header_id.hash,
+            StoredHeader {
+                header,
+                total_difficulty,
+                finalized: false,
+            },
+        );
+        <HeadersByNumber<T>>::mutate(gateway_id, header_id.number, |hashes| {
+            hashes.push(header_id.hash)
+        });
+
+        let (best, best_difficulty) =
+            <BestBlockMap<T>>::get(gateway_id).ok_or(Error::<T>::NotInitialized)?;
+        // total difficulty doesn't grow after the merge, so the highest PoS header is the best
+        if (total_difficulty, header_id.number) > (best_difficulty, best.number) {
+            <BestBlockMap<T>>::insert(gateway_id, (header_id, total_difficulty));
+            try_finalize::<T>(gateway_id, header_id)?;
+        }
+
+        Ok(())
+    }
+
+    /// Finalizes the ancestor of the best header, which is `descendants_until_final` blocks deep.
+    pub(crate) fn try_finalize<T: Config>(gateway_id: ChainId, best: HeaderId) -> DispatchResult {
+        let settings =
+            <GatewaySettingsMap<T>>::get(gateway_id).ok_or(Error::<T>::NotInitialized)?;
+        let finalized =
+            <FinalizedBlockMap<T>>::get(gateway_id).ok_or(Error::<T>::NotInitialized)?;
+        let depth = u64::from(settings.descendants_until_final);
+        if best.number <= finalized.number.saturating_add(depth) {
+            return Ok(())
+        }
+
+        let mut candidate_hash = best.hash;
+        for _ in 0..depth {
+            candidate_hash = <Headers<T>>::get(gateway_id, candidate_hash)
+                .ok_or(Error::<T>::UnknownHeader)?
+                .header
+                .parent_hash;
+        }
+        let candidate = HeaderId {
+            number: best.number - depth,
+            hash: candidate_hash,
+        };
+        <FinalizedBlockMap<T>>::insert(gateway_id, candidate);
+
+        // the ancestors up to the previously finalized header are final now too
+        let mut next_hash = Some(candidate.hash);
+        while let Some(hash) = next_hash.take() {
+            <Headers<T>>::mutate(gateway_id, hash, |maybe_stored| {
+                if let Some(stored) = maybe_stored {
+                    if !stored.finalized {
+                        stored.finalized = true;
+                        next_hash = Some(stored.header.parent_hash);
+                    }
+                }
+            });
+        }
+
+        prune_headers::<T>(gateway_id, candidate.number);
+
+        Ok(())
+    }
+
+    /// Removes the headers more than `HeadersToKeep` heights below the finalized one, including
+    /// the ones on abandoned forks. Bounded to a few heights per call.
+    pub(crate) fn prune_headers<T: Config>(gateway_id: ChainId, finalized_number: u64) {
+        let keep_from = finalized_number.saturating_sub(T::HeadersToKeep::get().into());
+        let oldest = <OldestHeaderNumberMap<T>>::get(gateway_id).unwrap_or_default();
+        let prune_until = keep_from.min(oldest.saturating_add(HEIGHTS_TO_PRUNE_PER_IMPORT));
+
+        for number in oldest..prune_until {
+            for hash in <HeadersByNumber<T>>::take(gateway_id, number) {
+                <Headers<T>>::remove(gateway_id, hash);
+            }
+        }
+        if prune_until > oldest {
+            <OldestHeaderNumberMap<T>>::insert(gateway_id, prune_until);
+        }
+    }
+
+    /// Verifies that the log is included in a receipt of the given finalized PoW block, which
+    /// was produced after the SideEffect was submitted.
+    pub(crate) fn verify_log_inclusion<T: Config>(
+        gateway_id: ChainId,
+        block_hash: H256,
+        receipt_proof: &[Vec<u8>],
+        encoded_log: Vec<u8>,
+        submission_target_height: Vec<u8>,
+    ) -> Result<Log, DispatchError> {
+        let stored = <Headers<T>>::get(gateway_id, block_hash).ok_or(Error::<T>::UnknownHeader)?;
+        ensure!(stored.finalized, Error::<T>::HeaderNotFinalized);
+
+        // PoS headers are only trusted from the owner, not proven final by the beacon chain
+        let settings =
+            <GatewaySettingsMap<T>>::get(gateway_id).ok_or(Error::<T>::NotInitialized)?;
+        ensure!(
+            !stored.header.difficulty.is_zero() || !is_past_terminal_difficulty(&settings, &stored),
+            Error::<T>::PostMergeConfirmationUnsupported
+        );
+
+        // ensures old equal side_effects can't be replayed
+        included_after_creation(&stored.header, submission_target_height)?;
+
+        let receipt = stored
+            .header
+            .check_receipt_proof(receipt_proof)
+            .ok_or(Error::<T>::InvalidReceiptProof)?
+            .map_err(|_| Error::<T>::ReceiptDecodingFailed)?;
+        let log: Log =
+            Decode::decode(&mut &*encoded_log).map_err(|_| Error::<T>::InclusionDataDecodeError)?;
+        ensure!(receipt.contains_log(&log), Error::<T>::EventNotIncluded);
+
+        Ok(log)
+    }
+
+    pub fn ensure_operational<T: Config>(gateway_id: ChainId) -> Result<(), Error<T>> {
+        match <IsHaltedMap<T>>::get(gateway_id) {
+            Some(false) => Ok(()),
+            Some(true) => Err(<Error<T>>::Halted),
+            None => Err(<Error<T>>::NotInitialized),
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Initializes the gateway with trusted headers, the first one being considered finalized.
+    ///
+    /// May only be called either by root, or by `PalletOwner`.
+    pub fn initialize(
+        origin: T::Origin,
+        gateway_id: ChainId,
+        encoded_registration_data: Vec<u8>,
+    ) -> Result<(), &'static str> {
+        ensure_owner_or_root::<T>(origin, gateway_id)?;
+        ensure!(
+            !<FinalizedBlockMap<T>>::contains_key(gateway_id),
+            "Already initialized"
+        );
+        ensure!(
+            !<DeinitializationCursorMap<T>>::contains_key(gateway_id),
+            "Deinitialization in progress"
+        );
+        let registration_data: EthereumRegistrationData<T::AccountId> =
+            Decode::decode(&mut &*encoded_registration_data)
+                .map_err(|_| "Registration data decoding error!")?;
+
+        let mut headers = registration_data.initial_headers.into_iter();
+        let initial_header = headers.next().ok_or("Initial header missing")?;
+        let initial_id = HeaderId {
+            number: initial_header.number,
+            hash: initial_header.compute_hash(),
+        };
+
+        <GatewaySettingsMap<T>>::insert(
+            gateway_id,
+            EthereumGatewaySettings {
+                descendants_until_final: registration_data.descendants_until_final,
+                difficulty_config: registration_data.difficulty_config,
+                terminal_total_difficulty: registration_data.terminal_total_difficulty,
+            },
+        );
+        <Headers<T>>::insert(
+            gateway_id,
+            initial_id.hash,
+            StoredHeader {
+                header: initial_header,
+                total_difficulty: registration_data.initial_difficulty,
+                finalized: true,
+            },
+        );
+        <HeadersByNumber<T>>::insert(gateway_id, initial_id.number, vec![initial_id.hash]);
+        <BestBlockMap<T>>::insert(
+            gateway_id,
+            (initial_id, registration_data.initial_difficulty),
+        );
+        <FinalizedBlockMap<T>>::insert(gateway_id, initial_id);
+        <OldestHeaderNumberMap<T>>::insert(gateway_id, initial_id.number);
+        <IsHaltedMap<T>>::insert(gateway_id, false);
+        <PalletOwnerMap<T>>::insert(gateway_id, registration_data.owner);
+
+        // the following headers are trusted like the first one, so their seals aren't verified
+        for header in headers {
+            import_validated_header::<T>(gateway_id, header)
+                .map_err(|_| "Initial headers import failed")?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes all the state of the gateway, so it can be initialized again.
+    ///
+    /// At most `MAX_KEYS_REMOVED_PER_DEINITIALIZATION` headers and heights are removed per call,
+    /// so it has to be repeated until it returns `true`. The gateway stops importing headers
+    /// with the first call.
+    ///
+    /// May only be called either by root, or by `PalletOwner`.
+    pub fn deinitialize(origin: T::Origin, gateway_id: ChainId) -> Result<bool, &'static str> {
+        ensure_owner_or_root::<T>(origin, gateway_id)?;
+        let mut cursor = match <DeinitializationCursorMap<T>>::get(gateway_id) {
+            Some(cursor) => cursor,
+            None => {
+                ensure!(
+                    <FinalizedBlockMap<T>>::contains_key(gateway_id),
+                    "Not initialized"
+                );
+                <BestBlockMap<T>>::remove(gateway_id);
+                <FinalizedBlockMap<T>>::remove(gateway_id);
+                <OldestHeaderNumberMap<T>>::remove(gateway_id);
+                <GatewaySettingsMap<T>>::remove(gateway_id);
+                <IsHaltedMap<T>>::remove(gateway_id);
+                DeinitializationCursor::Headers(None)
+            },
+        };
+
+        let mut limit = MAX_KEYS_REMOVED_PER_DEINITIALIZATION;
+        if let DeinitializationCursor::Headers(previous) = &cursor {
+            let removal = <Headers<T>>::clear_prefix(gateway_id, limit, previous.as_deref());
+            limit = limit.saturating_sub(removal.loops);
+            cursor = match removal.maybe_cursor {
+                Some(next) => DeinitializationCursor::Headers(Some(next)),
+                None => DeinitializationCursor::HeadersByNumber(None),
+            };
+        }
+        if let DeinitializationCursor::HeadersByNumber(previous) = &cursor {
+            if limit > 0 {
+                let removal =
+                    <HeadersByNumber<T>>::clear_prefix(gateway_id, limit, previous.as_deref());
+                if removal.maybe_cursor.is_none() {
+                    <DeinitializationCursorMap<T>>::remove(gateway_id);
+                    <PalletOwnerMap<T>>::remove(gateway_id);
+                    return Ok(true)
+                }
+                cursor = DeinitializationCursor::HeadersByNumber(removal.maybe_cursor);
+            }
+        }
+
+        <DeinitializationCursorMap<T>>::insert(gateway_id, cursor);
+        Ok(false)
+    }
+
+    /// Change `PalletOwner`.
+    ///
+    /// May only be called either by root, or by `PalletOwner`.
+    pub fn set_owner(
+        origin: T::Origin,
+        gateway_id: ChainId,
+        encoded_new_owner: Vec<u8>,
+    ) -> Result<(), &'static str> {
+        ensure_owner_or_root::<T>(origin, gateway_id)?;
+        let new_owner: Option<T::AccountId> =
+            Decode::decode(&mut &*encoded_new_owner).map_err(|_| "New Owner decoding error")?;
+
+        match new_owner {
+            Some(new_owner) => {
+                PalletOwnerMap::<T>::insert(gateway_id, &new_owner);
+                log::info!("Setting pallet Owner to: {:?}", new_owner);
+            },
+            None => {
+                PalletOwnerMap::<T>::remove(gateway_id);
+                log::info!("Removed Owner of pallet.");
+            },
+        }
+
+        Ok(())
+    }
+
+    /// Halt or resume the header submissions of the gateway.
+    ///
+    /// May only be called either by root, or by `PalletOwner`.
+    pub fn set_operational(
+        origin: T::Origin,
+        operational: bool,
+        gateway_id: ChainId,
+    ) -> Result<(), &'static str> {
+        ensure_owner_or_root::<T>(origin, gateway_id)?;
+        <IsHaltedMap<T>>::insert(gateway_id, !operational);
+
+        if operational {
+            log::info!("Resuming pallet operations.");
+        } else {
+            log::info!("Stopping pallet operations.");
+        }
+
+        Ok(())
+    }
+
+    /// Imports a range of headers, each one together with the Ethash proof of its seal.
+    ///
+    /// PoS headers may only be submitted either by root, or by `PalletOwner`.
+    ///
+    /// Returns the height of the last imported header.
+    pub fn submit_headers(
+        origin: OriginFor<T>,
+        gateway_id: ChainId,
+        encoded_header_data: Vec<u8>,
+    ) -> Result<Vec<u8>, DispatchError> {
+        ensure_operational::<T>(gateway_id)?;
+        let trusted_submitter = ensure_owner_or_root::<T>(origin.clone(), gateway_id).is_ok();
+        if !trusted_submitter {
+            ensure_signed(origin)?;
+        }
+        let range: Vec<EthereumHeaderData> = Decode::decode(&mut &*encoded_header_data)
+            .map_err(|_| Error::<T>::HeaderDataDecodingError)?;
+        let last_number = range
+            .last()
+            .map(|data| data.header.number)
+            .ok_or(Error::<T>::EmptyRangeSubmitted)?;
+
+        for EthereumHeaderData { header, proof } in range {
+            validate_header_to_import::<T>(gateway_id, &header, &proof, trusted_submitter)?;
+            import_validated_header::<T>(gateway_id, header)?;
+        }
+
+        match u32::try_from(last_number) {
+            Ok(number) => Ok(number.to_be_bytes().to_vec()),
+            _ => Err(Error::<T>::BlockHeightConversionError.into()),
+        }
+    }
+
+    pub fn confirm_and_decode_payload_params(
+        gateway_id: ChainId,
+        encoded_inclusion_data: Vec<u8>,
+        submission_target_height: Vec<u8>,
+        value_abi_unsigned_type: &[u8],
+        side_effect_id: [u8; 4],
+    ) -> Result<(Vec<Vec<u8>>, Vec<u8>), DispatchError> {
+        let inclusion_data: EthereumInclusionData =
+            Decode::decode(&mut &*encoded_inclusion_data)
+                .map_err(|_| Error::<T>::InclusionDataDecodeError)?;
+
+        let log = verify_log_inclusion::<T>(
+            gateway_id,
+            inclusion_data.block_hash,
+            &inclusion_data.receipt_proof,
+            inclusion_data.encoded_log,
+            submission_target_height,
+        )?;
+
+        decode_log::<T>(&side_effect_id, log, value_abi_unsigned_type)
+    }
+
+    /// Confirms that the encoded log was emitted in the block with the given hash.
+    ///
+    /// The inclusion proof is the receipt proof of the transaction, checked against the receipts
+    /// root of the finalized block.
+    pub fn confirm_event_inclusion(
+        gateway_id: ChainId,
+        encoded_event: Vec<u8>,
+        submission_target_height: Vec<u8>,
+        inclusion_proof: Vec<Vec<u8>>,
+        encoded_block_hash: Vec<u8>,
+        value_abi_unsigned_type: &[u8],
+        side_effect_id: [u8; 4],
+    ) -> Result<(Vec<Vec<u8>>, Vec<u8>), DispatchError> {
+        let block_hash: H256 = Decode::decode(&mut &*encoded_block_hash)
+            .map_err(|_| Error::<T>::InclusionDataDecodeError)?;
+
+        let log = verify_log_inclusion::<T>(
+            gateway_id,
+            block_hash,
+            &inclusion_proof,
+            encoded_event,
+            submission_target_height,
+        )?;
+
+        decode_log::<T>(&side_effect_id, log, value_abi_unsigned_type)
+    }
+
+    pub fn get_latest_finalized_header(gateway_id: ChainId) -> Option<Vec<u8>> {
+        <FinalizedBlockMap<T>>::get(gateway_id).map(|header_id| header_id.hash.encode())
+    }
+
+    pub fn get_latest_finalized_height(gateway_id: ChainId) -> Option<Vec<u8>> {
+        <FinalizedBlockMap<T>>::get(gateway_id).map(|header_id| header_id.number.encode())
+    }
+
+    /// Whether any state is held for the gateway, including headers still being deinitialized.
+    pub fn has_state(gateway_id: ChainId) -> bool {
+        <FinalizedBlockMap<T>>::contains_key(gateway_id)
+            || <DeinitializationCursorMap<T>>::contains_key(gateway_id)
+    }
+}
+
+/// Ensure that the origin is either root, or `PalletOwner`.
+fn ensure_owner_or_root<T: Config>(
+    origin: T::Origin,
+    gateway_id: ChainId,
+) -> Result<(), &'static str> {
+    match origin.into() {
+        Ok(RawOrigin::Root) => Ok(()),
+        Ok(RawOrigin::Signed(ref signer))
+            if Some(signer) == <PalletOwnerMap<T>>::get(gateway_id).as_ref() =>
+            Ok(()),
+        _ => Err(BadOrigin.into()),
+    }
+}
+
+/// Ensure that the block including the SideEffect's log was produced after the SideEffect was created.
+fn included_after_creation(
+    header: &EthereumHeader,
+    submission_target_height: Vec<u8>,
+) -> Result<(), &'static str> {
+    let submission_target: u64 = Decode::decode(&mut &*submission_target_height)
+        .map_err(|_| "Submission target height decoding error")?;
+    ensure!(
+        submission_target < header.number,
+        "Transaction executed before SideEffect creation"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mock::{run_test, Origin, TestRuntime, VerifyPoW},
+        side_effects::TRANSFER_EVENT_SIGNATURE,
+    };
+    use ethereum_types::H160;
+    use frame_support::{assert_err, assert_noop, assert_ok};
+    use hex_literal::hex;
+    use snowbridge_ethereum::{difficulty::DifficultyConfig, header::EMPTY_OMMERS_HASH};
+    use snowbridge_testutils::BlockWithProofs;
+
+    const GATEWAY: ChainId = *b"eth2";
+
+    // Receipt proof of a USDT transfer, whose receipts root is stored in `header_with_receipt`.
+    fn usdt_transfer_receipt_proof() -> Vec<Vec<u8>> {
+        vec![
+            hex!("f90131a00d3cb8d3f57ac1c0e12918a2ebe0cafed8c273577b9dd73e7ed1079b403ef494a0678b9835b834f8a287c0dd33a8fca9146e456ca688555ed4ec1361a2180b778da0fe42da181a46677a043b3d9d4b8bb05a6a17b7b5c010c17e7c1d31cfb7c4f911a0c89f0e2c53241cdb578e1f2b4caf6ba36e00500bdc57fecd66b84a6a58394c19a086c3c1fae5a0575940b5d38e111c469d07883106c26856f3ef608469a2081f13a06c5992ff00aab6226a70a032fd2f571ba22f797321f45e2daa73020d638d21b0a050861e9503ef68728f6c90a44f7fe1bceb2a9bdab6957bbe7136166bd849561ea006aa6eaca8a07e57176e9aa41e6a09edfb7678d1a112404e0ec779d7e567e82ea0bb0b430d303ba21b0af11c487b8a218bd75db54c98940b3f11bad8ff47cad3ef8080808080808080").to_vec(),
+            hex!("f871a0246de222036ee6a03329b0105da0a6b3f916fc95a9ed5a403a581a0c4d74242ca0ac108a49a88b57a05ac34a108b39f1e45f6f167f2b9fbc8d52fb58e2e5a6af1ea0fcfe07ac2ccd3c28b6eab68d1bce112f6f6dbd9023e4ec3c05b96615aa803d798080808080808080808080808080").to_vec(),
+            hex!("e4820001a04fff54398cad4d05ea6abfd8b0f3b4fe14c04d7ff5f5211c5b927d9cf72ac1d8").to_vec(),
+            hex!("f851a096d010643ca2d47412ca66898286b5f2412963b9ec051b33e570d575914c9c5ca028cd24c652989542fe89479ec6388eac4592432242af5ba97563b3ac7c71c019808080808080808080808080808080").to_vec(),
+            hex!("f90211a0bb35a84c5b1dcb78ec9d32614912c696e62df77bebf9ab326ee55b5d3acdde46a01084b30dac8df0accfcd0fd6330b7f6fc72a4651246d0694be9162151686a620a03eed50afdce7909d784c6157c445a444c806b5f23d31f3b63786f600c84a95b2a0af5232f1df6c6d41879804d081abe867002abe26ba3e5f8e0254a83a54769831a0607915fb13dd5da594256389a45007a67a7f7a86e95d38d8462792b6c98a722ea00e1260fda1730f2738c650ce2bfba83857bc10f8fb119ebc4fb39acba24e6fbaa0d11de17e417327457812675ca3b84ae8e1b64827abfe01420953697c8313d5b1a05fcaf2f7a88f76336a0c32ffc78acb87ae2005454bd25d658035331be3173b46a03f94f4952ab9e650f83cfd0e7f367b1bcc493aacf39a06f16c4a2e1b5605da48a0bdb4ec79785ca8ae22d60f1bbd42d707b4d7ec4aff231a3ebab755e315b35053a043a67c3f2bcef37c8f47a673adcb7061007a553696d1092408601c11b2e6846aa0c519d5af48cae87c7f4538845417c9735813bee892a6fe2dda79f5c414e8576aa0f7058256e09589501d7c231d739e61c84a850e139690989d24fda6058b432e98a081a52faab520978cb19ce14400dba0cd5bcdc4e5a3c0740678aa8f97ee0e5c56a0bcecc61cadeae52518e3b68a48af4b11603dfd9d99d99d7985efa6d2de44f904a02cba4accfc6f39bc5adb6d4440eb6358b4a5103ef93298e4e694f1f940f8b48280").to_vec(),
+            hex!("f901ae20b901aaf901a70183bb444eb9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000010000000000000001000000000000000000000000000100000000000008000000000000000000000000000000000000000000000000000000000000000000000000000000000200000000000010000000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000080000000000000000000000000000000000000000000000002000000000000000000081000000000000000000000000000000000000000400000000000000000000000000000000000000000000000000000000000f89df89b94dac17f958d2ee523a2206206994597c13d831ec7f863a0ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3efa00000000000000000000000002e514404ff6823f1b46a8318a709251db414e5e1a000000000000000000000000055021c55847c00d764357a352e5803237d328954a0000000000000000000000000000000000000000000000000000000000201c370").to_vec(),
+        ]
+    }
+
+    fn usdt_transfer_log() -> Log {
+        Log {
+            address: hex!("dac17f958d2ee523a2206206994597c13d831ec7").into(),
+            topics: vec![
+                TRANSFER_EVENT_SIGNATURE,
+                hex!("0000000000000000000000002e514404ff6823f1b46a8318a709251db414e5e1").into(),
+                hex!("00000000000000000000000055021c55847c00d764357a352e5803237d328954").into(),
+            ],
+            data: hex!("000000000000000000000000000000000000000000000000000000000201c370").to_vec(),
+        }
+    }
+
+    fn child_of(parent: &EthereumHeader) -> EthereumHeader {
+        EthereumHeader {
+            parent_hash: parent.compute_hash(),
+            number: parent.number + 1,
+            difficulty: 1.into(),
+            ..Default::default()
+        }
+    }
+
+    fn initialize_gateway(descendants_until_final: u8) -> EthereumHeader {
+        initialize_gateway_with_ttd(descendants_until_final, None)
+    }
+
+    fn initialize_gateway_with_ttd(
+        descendants_until_final: u8,
+        terminal_total_difficulty: Option<U256>,
+    ) -> EthereumHeader {
+        let genesis = EthereumHeader {
+            number: 10,
+            difficulty: 1.into(),
+            ..Default::default()
+        };
+        let registration_data = EthereumRegistrationData::<u64> {
+            initial_headers: vec![genesis.clone()],
+            initial_difficulty: 1.into(),
+            descendants_until_final,
+            difficulty_config: DifficultyConfig::mainnet(),
+            terminal_total_difficulty,
+            owner: 1,
+        };
+        assert_ok!(Pallet::<TestRuntime>::initialize(
+            Origin::root(),
+            GATEWAY,
+            registration_data.encode()
+        ));
+        genesis
+    }
+
+    // PoS header, with the empty seal the beacon chain leaves in the execution payload
+    fn pos_child_of(parent: &EthereumHeader) -> EthereumHeader {
+        EthereumHeader {
+            parent_hash: parent.compute_hash(),
+            number: parent.number + 1,
+            ommers_hash: EMPTY_OMMERS_HASH.into(),
+            seal: vec![
+                rlp::encode(&[7u8; 32].to_vec()).to_vec(),
+                rlp::encode(&[0u8; 8].to_vec()).to_vec(),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn submit(headers: &[EthereumHeader]) -> Result<Vec<u8>, DispatchError> {
+        submit_as(Origin::signed(1), headers)
+    }
+
+    fn submit_as(origin: Origin, headers: &[EthereumHeader]) -> Result<Vec<u8>, DispatchError> {
+        let range: Vec<EthereumHeaderData> = headers
+            .iter()
+            .map(|header| EthereumHeaderData {
+                header: header.clone(),
+                proof: vec![],
+            })
+            .collect();
+        Pallet::<TestRuntime>::submit_headers(origin, GATEWAY, range.encode())
+    }
+
+    // https://etherscan.io/block/11090290, with the Ethash proof of its seal
+    fn pow_header_with_proof() -> EthereumHeaderData {
+        let nonce = hex!("6935bbe7b63c4f8e").to_vec();
+        let mix_hash =
+            hex!("be3adfb0087be62b28b716e2cdf3c79329df5caa04c9eee035d35b5d52102815").to_vec();
+        let header = EthereumHeader {
+            parent_hash: hex!("bede0bddd6f32c895fc505ffe0c39d9bde58e9a5272f31a3dee448b796edcbe3")
+                .into(),
+            timestamp: 1603160977,
+            number: 11090290,
+            author: hex!("ea674fdde714fd979de3edf0f56aa9716b898ec8").into(),
+            transactions_root: hex!(
+                "56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"
+            )
+            .into(),
+            ommers_hash: EMPTY_OMMERS_HASH.into(),
+            extra_data: hex!("65746865726d696e652d61736961312d33").to_vec(),
+            state_root: hex!("7dcb8aca872b712bad81df34a89d4efedc293566ffc3eeeb5cbcafcc703e42c9")
+                .into(),
+            receipts_root: hex!("56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421")
+                .into(),
+            gas_limit: 0xbe8c19.into(),
+            difficulty: 0xbc140caa61087u64.into(),
+            seal: vec![
+                rlp::encode(&mix_hash).to_vec(),
+                rlp::encode(&nonce).to_vec(),
+            ],
+            ..Default::default()
+        };
+        let fixture: std::path::PathBuf = [
+            env!("CARGO_MANIFEST_DIR"),
+            "../../primitives/src/bridges/snowfork/ethereum/tests/fixtures/11090290.json",
+        ]
+        .iter()
+        .collect();
+        let proof = BlockWithProofs::from_file(&fixture)
+            .to_double_node_with_merkle_proof_vec(DoubleNodeWithMerkleProof::from_values);
+
+        EthereumHeaderData { header, proof }
+    }
+
+    // Trusts the parent of `pow_header_with_proof` as finalized. Only the fields the difficulty
+    // of its child is calculated from are set, so it's stored under the real parent hash.
+    fn initialize_gateway_at_pow_parent(parent_hash: H256) {
+        let parent = EthereumHeader {
+            number: 11090289,
+            // 9 seconds before the child, so its difficulty only grows by the bomb
+            timestamp: 1603160968,
+            ommers_hash: EMPTY_OMMERS_HASH.into(),
+            difficulty: 0xbc140caa21087u64.into(),
+            ..Default::default()
+        };
+        let parent_id = HeaderId {
+            number: parent.number,
+            hash: parent_hash,
+        };
+
+        <GatewaySettingsMap<TestRuntime>>::insert(
+            GATEWAY,
+            EthereumGatewaySettings {
+                descendants_until_final: 0,
+                difficulty_config: DifficultyConfig::mainnet(),
+                terminal_total_difficulty: None,
+            },
+        );
+        <Headers<TestRuntime>>::insert(
+            GATEWAY,
+            parent_hash,
+            StoredHeader {
+                total_difficulty: parent.difficulty,
+                header: parent.clone(),
+                finalized: true,
+            },
+        );
+        <HeadersByNumber<TestRuntime>>::insert(GATEWAY, parent_id.number, vec![parent_hash]);
+        <BestBlockMap<TestRuntime>>::insert(GATEWAY, (parent_id, parent.difficulty));
+        <FinalizedBlockMap<TestRuntime>>::insert(GATEWAY, parent_id);
+        <OldestHeaderNumberMap<TestRuntime>>::insert(GATEWAY, parent_id.number);
+        <IsHaltedMap<TestRuntime>>::insert(GATEWAY, false);
+        <PalletOwnerMap<TestRuntime>>::insert(GATEWAY, 1);
+    }
+
+    // Imports a header including the USDT transfer receipt, and finalizes it.
+    fn header_with_receipt(genesis: &EthereumHeader) -> EthereumHeader {
+        let mut header = child_of(genesis);
+        header.receipts_root =
+            hex!("d128e3a57142d2bf15bc0cbcac7ad54f40750d571b5c3097e425882c10c9ba66").into();
+        let descendant = child_of(&header);
+        assert_ok!(submit(&[header.clone(), descendant]));
+        header
+    }
+
+    #[test]
+    fn init_stores_first_header_as_finalized() {
+        run_test(|| {
+            let genesis = initialize_gateway(1);
+
+            assert_eq!(
+                Pallet::<TestRuntime>::get_latest_finalized_header(GATEWAY),
+                Some(genesis.compute_hash().encode())
+            );
+            assert_eq!(
+                Pallet::<TestRuntime>::get_latest_finalized_height(GATEWAY),
+                Some(10u64.encode())
+            );
+        })
+    }
+
+    #[test]
+    fn init_can_only_be_done_once() {
+        run_test(|| {
+            let genesis = initialize_gateway(1);
+            let registration_data = EthereumRegistrationData::<u64> {
+                initial_headers: vec![genesis],
+                initial_difficulty: 1.into(),
+                descendants_until_final: 1,
+                difficulty_config: DifficultyConfig::mainnet(),
+                terminal_total_difficulty: None,
+                owner: 1,
+            };
+
+            assert_noop!(
+                Pallet::<TestRuntime>::initialize(
+                    Origin::root(),
+                    GATEWAY,
+                    registration_data.encode()
+                ),
+                "Already initialized"
+            );
+        })
+    }
+
+    #[test]
+    fn deinitialize_removes_gateway_state() {
+        run_test(|| {
+            let genesis = initialize_gateway(1);
+            let first = child_of(&genesis);
+            assert_ok!(submit(&[first.clone(), child_of(&first)]));
+
+            assert_eq!(
+                Pallet::<TestRuntime>::deinitialize(Origin::root(), GATEWAY),
+                Ok(true)
+            );
+
+            assert_eq!(
+                Pallet::<TestRuntime>::get_latest_finalized_header(GATEWAY),
+                None
+            );
+            assert!(!<Headers<TestRuntime>>::contains_key(
+                GATEWAY,
+                first.compute_hash()
+            ));
+            assert_err!(
+                submit(&[child_of(&genesis)]),
+                Error::<TestRuntime>::NotInitialized
+            );
+            initialize_gateway(1);
+        })
+    }
+
+    #[test]
+    fn submitted_headers_are_finalized_after_enough_descendants() {
+        run_test(|| {
+            let genesis = initialize_gateway(2);
+            let first = child_of(&genesis);
+            let second = child_of(&first);
+            let third = child_of(&second);
+
+            assert_ok!(submit(&[first.clone(), second.clone()]));
+            assert_eq!(
+                Pallet::<TestRuntime>::get_latest_finalized_header(GATEWAY),
+                Some(genesis.compute_hash().encode())
+            );
+
+            assert_eq!(submit(&[third]), Ok(13u32.to_be_bytes().to_vec()));
+            assert_eq!(
+                Pallet::<TestRuntime>::get_latest_finalized_header(GATEWAY),
+                Some(first.compute_hash().encode())
+            );
+            assert!(
+                <Headers<TestRuntime>>::get(GATEWAY, first.compute_hash())
+                    .unwrap()
+                    .finalized
+            );
+            assert!(
+                !<Headers<TestRuntime>>::get(GATEWAY, second.compute_hash())
+                    .unwrap()
+                    .finalized
+            );
+        })
+    }
+
+    #[test]
+    fn submit_headers_rejects_unknown_parent() {
+        run_test(|| {
+            let genesis = initialize_gateway(1);
+            let orphan = child_of(&child_of(&genesis));
+
+            assert_err!(submit(&[orphan]), Error::<TestRuntime>::MissingParentHeader);
+        })
+    }
+
+    #[test]
+    fn submit_headers_rejects_fork_below_finalized_header() {
+        run_test(|| {
+            let genesis = initialize_gateway(1);
+            let first = child_of(&genesis);
+            let second = child_of(&first);
+            assert_ok!(submit(&[first.clone(), second]));
+
+            // a sibling of the finalized header
+            let mut fork = child_of(&genesis);
+            fork.timestamp = 1;
+
+            assert_err!(submit(&[fork]), Error::<TestRuntime>::HeaderOnStaleFork);
+        })
+    }
+
+    #[test]
+    fn submit_headers_fails_when_halted() {
+        run_test(|| {
+            let genesis = initialize_gateway(1);
+            assert_ok!(Pallet::<TestRuntime>::set_operational(
+                Origin::root(),
+                false,
+                GATEWAY
+            ));
+
+            assert_err!(submit(&[child_of(&genesis)]), Error::<TestRuntime>::Halted);
+        })
+    }
+
+    #[test]
+    fn confirms_erc20_transfer_included_in_finalized_block() {
+        run_test(|| {
+            let genesis = initialize_gateway(1);
+            let header = header_with_receipt(&genesis);
+
+            let params = Pallet::<TestRuntime>::confirm_event_inclusion(
+                GATEWAY,
+                usdt_transfer_log().encode(),
+                10u64.encode(),
+                usdt_transfer_receipt_proof(),
+                header.compute_hash().encode(),
+                b"uint128",
+                *b"mult",
+            );
+
+            assert_eq!(
+                params,
+                Ok((
+                    vec![
+                        hex!("dac17f958d2ee523a2206206994597c13d831ec7").to_vec(),
+                        hex!("2e514404ff6823f1b46a8318a709251db414e5e1").to_vec(),
+                        hex!("55021c55847c00d764357a352e5803237d328954").to_vec(),
+                        0x201c370u128.encode(),
+                    ],
+                    hex!("dac17f958d2ee523a2206206994597c13d831ec7").to_vec(),
+                ))
+            );
+        })
+    }
+
+    #[test]
+    fn confirm_rejects_native_transfers() {
+        run_test(|| {
+            let genesis = initialize_gateway(1);
+            let header = header_with_receipt(&genesis);
+
+            assert_err!(
+                Pallet::<TestRuntime>::confirm_event_inclusion(
+                    GATEWAY,
+                    usdt_transfer_log().encode(),
+                    10u64.encode(),
+                    usdt_transfer_receipt_proof(),
+                    header.compute_hash().encode(),
+                    b"uint128",
+                    *b"tran",
+                ),
+                Error::<TestRuntime>::UnkownSideEffect
+            );
+        })
+    }
+
+    #[test]
+    fn confirm_rejects_receipt_of_pos_block() {
+        run_test(|| {
+            let terminal = initialize_gateway_with_ttd(1, Some(1.into()));
+            let mut header = pos_child_of(&terminal);
+            header.receipts_root =
+                hex!("d128e3a57142d2bf15bc0cbcac7ad54f40750d571b5c3097e425882c10c9ba66").into();
+            assert_ok!(submit(&[header.clone(), pos_child_of(&header)]));
+
+            assert_err!(
+                Pallet::<TestRuntime>::confirm_event_inclusion(
+                    GATEWAY,
+                    usdt_transfer_log().encode(),
+                    10u64.encode(),
+                    usdt_transfer_receipt_proof(),
+                    header.compute_hash().encode(),
+                    b"uint128",
+                    *b"mult",
+                ),
+                Error::<TestRuntime>::PostMergeConfirmationUnsupported
+            );
+        })
+    }
+
+    #[test]
+    fn confirms_evm_call_log_from_inclusion_data() {
+        run_test(|| {
+            let genesis = initialize_gateway(1);
+            let header = header_with_receipt(&genesis);
+            let log = usdt_transfer_log();
+            let inclusion_data = EthereumInclusionData {
+                encoded_log: log.encode(),
+                receipt_proof: usdt_transfer_receipt_proof(),
+                block_hash: header.compute_hash(),
+            };
+
+            let (params, source) = Pallet::<TestRuntime>::confirm_and_decode_payload_params(
+                GATEWAY,
+                inclusion_data.encode(),
+                10u64.encode(),
+                b"uint128",
+                *b"cevm",
+            )
+            .unwrap();
+
+            assert_eq!(source, log.address.as_bytes().to_vec());
+            assert_eq!(params.len(), 5);
+            assert_eq!(params[4], log.data);
+        })
+    }
+
+    #[test]
+    fn confirm_rejects_log_not_in_receipt() {
+        run_test(|| {
+            let genesis = initialize_gateway(1);
+            let header = header_with_receipt(&genesis);
+            let mut log = usdt_transfer_log();
+            log.address = H160::repeat_byte(1);
+
+            assert_err!(
+                Pallet::<TestRuntime>::confirm_event_inclusion(
+                    GATEWAY,
+                    log.encode(),
+                    10u64.encode(),
+                    usdt_transfer_receipt_proof(),
+                    header.compute_hash().encode(),
+                    b"uint128",
+                    *b"mult",
+                ),
+                Error::<TestRuntime>::EventNotIncluded
+            );
+        })
+    }
+
+    #[test]
+    fn confirm_rejects_receipt_of_unfinalized_block() {
+        run_test(|| {
+            let genesis = initialize_gateway(1);
+            let mut header = child_of(&genesis);
+            header.receipts_root =
+                hex!("d128e3a57142d2bf15bc0cbcac7ad54f40750d571b5c3097e425882c10c9ba66").into();
+            assert_ok!(submit(&[header.clone()]));
+
+            assert_err!(
+                Pallet::<TestRuntime>::confirm_event_inclusion(
+                    GATEWAY,
+                    usdt_transfer_log().encode(),
+                    10u64.encode(),
+                    usdt_transfer_receipt_proof(),
+                    header.compute_hash().encode(),
+                    b"uint128",
+                    *b"mult",
+                ),
+                Error::<TestRuntime>::HeaderNotFinalized
+            );
+        })
+    }
+
+    #[test]
+    fn confirm_rejects_block_before_submission_height() {
+        run_test(|| {
+            let genesis = initialize_gateway(1);
+            let header = header_with_receipt(&genesis);
+
+            assert_err!(
+                Pallet::<TestRuntime>::confirm_event_inclusion(
+                    GATEWAY,
+                    usdt_transfer_log().encode(),
+                    11u64.encode(),
+                    usdt_transfer_receipt_proof(),
+                    header.compute_hash().encode(),
+                    b"uint128",
+                    *b"mult",
+                ),
+                "Transaction executed before SideEffect creation"
+            );
+        })
+    }
+
+    #[test]
+    fn old_headers_are_pruned() {
+        run_test(|| {
+            let genesis = initialize_gateway(0);
+            let mut headers = vec![child_of(&genesis)];
+            for _ in 0..4 {
+                headers.push(child_of(headers.last().unwrap()));
+            }
+            assert_ok!(submit(&headers));
+
+            // HeadersToKeep is 2 heights below the finalized header at 15
+            assert!(!<Headers<TestRuntime>>::contains_key(
+                GATEWAY,
+                genesis.compute_hash()
+            ));
+            assert!(!<Headers<TestRuntime>>::contains_key(
+                GATEWAY,
+                headers[1].compute_hash()
+            ));
+            assert!(<Headers<TestRuntime>>::contains_key(
+                GATEWAY,
+                headers[2].compute_hash()
+            ));
+        })
+    }
+
+    #[test]
+    fn pow_header_with_valid_seal_is_imported() {
+        run_test(|| {
+            VerifyPoW::set(&true);
+            let header_data = pow_header_with_proof();
+            initialize_gateway_at_pow_parent(header_data.header.parent_hash);
+
+            assert_ok!(Pallet::<TestRuntime>::submit_headers(
+                Origin::signed(2),
+                GATEWAY,
+                vec![header_data.clone()].encode()
+            ));
+
+            assert_eq!(
+                Pallet::<TestRuntime>::get_finalized_block(GATEWAY),
+                Some(HeaderId {
+                    number: 11090290,
+                    hash: hex!("0f9bdc91c2e0140acb873330742bda8c8181fa3add91fe7ae046251679cedef7")
+                        .into(),
+                })
+            );
+        })
+    }
+
+    #[test]
+    fn pow_header_with_invalid_seal_is_rejected() {
+        run_test(|| {
+            VerifyPoW::set(&true);
+            let mut header_data = pow_header_with_proof();
+            initialize_gateway_at_pow_parent(header_data.header.parent_hash);
+            header_data.header.seal[1] = rlp::encode(&hex!("6935bbe7b63c4f8f").to_vec()).to_vec();
+
+            assert_noop!(
+                Pallet::<TestRuntime>::submit_headers(
+                    Origin::signed(2),
+                    GATEWAY,
+                    vec![header_data.clone()].encode()
+                ),
+                Error::<TestRuntime>::InvalidHeader
+            );
+
+            // the difficulty has to follow the parent's one too
+            header_data = pow_header_with_proof();
+            header_data.header.difficulty += U256::one();
+            assert_noop!(
+                Pallet::<TestRuntime>::submit_headers(
+                    Origin::signed(2),
+                    GATEWAY,
+                    vec![header_data].encode()
+                ),
+                Error::<TestRuntime>::InvalidHeader
+            );
+        })
+    }
+
+    #[test]
+    fn pos_headers_after_terminal_difficulty_are_finalized_by_height() {
+        run_test(|| {
+            let terminal = initialize_gateway_with_ttd(1, Some(1.into()));
+            let first = pos_child_of(&terminal);
+            let second = pos_child_of(&first);
+
+            assert_noop!(
+                submit_as(Origin::signed(2), &[first.clone()]),
+                Error::<TestRuntime>::UntrustedSubmitter
+            );
+            assert_noop!(
+                submit(&[child_of(&terminal)]),
+                Error::<TestRuntime>::InvalidHeader
+            );
+
+            assert_ok!(submit(&[first.clone(), second]));
+
+            assert_eq!(
+                Pallet::<TestRuntime>::get_finalized_block(GATEWAY),
+                Some(HeaderId {
+                    number: first.number,
+                    hash: first.compute_hash(),
+                })
+            );
+        })
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{Config, Error};
+use codec::Encode;
+use ethereum_types::{H160, H256, U256};
+use hex_literal::hex;
+use snowbridge_ethereum::Log;
+use sp_runtime::DispatchError;
+use sp_std::{convert::TryFrom, vec, vec::Vec};
+
+/// keccak256("Transfer(address,address,uint256)"), emitted by ERC-20 tokens
+pub const TRANSFER_EVENT_SIGNATURE: H256 = H256(hex!(
+    "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+));
+
+/// Decodes the SideEffect params from a log, returning them together with the address of the
+/// contract that emitted it.
+///
+/// Native ETH transfers emit no logs, so `tran` can't be confirmed on Ethereum. Transfers of
+/// ERC-20 tokens are confirmed as `mult`, with the token contract as the asset.
+pub(crate) fn decode_log<T: Config>(
+    id: &[u8; 4],
+    log: Log,
+    value_abi_unsigned_type: &[u8],
+) -> Result<(Vec<Vec<u8>>, Vec<u8>), DispatchError> {
+    let source = log.address.as_bytes().to_vec();
+    match &id {
+        &b"mult" => {
+            if log.topics.len() != 3
+                || log.topics[0] != TRANSFER_EVENT_SIGNATURE
+                || log.data.len() != 32
+            {
+                return Err(Error::<T>::EventDecodingFailed.into())
+            }
+            let from = H160::from(log.topics[1]);
+            let to = H160::from(log.topics[2]);
+            let amount =
+                encode_amount::<T>(U256::from_big_endian(&log.data), value_abi_unsigned_type)?;
+
+            Ok((
+                vec![
+                    source.clone(),
+                    from.as_bytes().to_vec(),
+                    to.as_bytes().to_vec(),
+                    amount,
+                ],
+                source,
+            ))
+        },
+        &b"cevm" => {
+            // the called contract, followed by the topics and the data of the log it emitted
+            let mut params = vec![source.clone()];
+            params.extend(log.topics.iter().map(|topic| topic.as_bytes().to_vec()));
+            params.push(log.data);
+
+            Ok((params, source))
+        },
+        &_ => Err(Error::<T>::UnkownSideEffect.into()),
+    }
+}
+
+/// Encodes the amount like the value type of the gateway, which is how Substrate events carry it.
+fn encode_amount<T: Config>(
+    amount: U256,
+    value_abi_unsigned_type: &[u8],
+) -> Result<Vec<u8>, DispatchError> {
+    let encoded_amount = match value_abi_unsigned_type {
+        b"uint32" => u32::try_from(amount).ok().map(|amount| amount.encode()),
+        b"uint64" => u64::try_from(amount).ok().map(|amount| amount.encode()),
+        b"uint128" => u128::try_from(amount).ok().map(|amount| amount.encode()),
+        &_ => None,
+    };
+    encoded_amount.ok_or_else(|| Error::<T>::EventDecodingFailed.into())
+}
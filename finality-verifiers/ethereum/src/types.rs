@@ -0,0 +1,63 @@
+use codec::{Decode, Encode};
+use ethereum_types::{H256, U256};
+use scale_info::TypeInfo;
+use snowbridge_ethereum::{
+    difficulty::DifficultyConfig, ethashproof::DoubleNodeWithMerkleProof, Header,
+};
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+pub type ChainId = [u8; 4];
+
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct EthereumRegistrationData<T> {
+    // first header is trusted as finalized, the following ones are imported as its descendants
+    pub initial_headers: Vec<Header>,
+    // total difficulty of the first header
+    pub initial_difficulty: U256,
+    // number of descendants a header needs to be considered final
+    pub descendants_until_final: u8,
+    // hard forks of the bridged network affecting the difficulty calculation
+    pub difficulty_config: DifficultyConfig,
+    // total difficulty of the last PoW block, its descendants are sealed by the beacon chain
+    pub terminal_total_difficulty: Option<U256>,
+    pub owner: T,
+}
+
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct EthereumGatewaySettings {
+    pub descendants_until_final: u8,
+    pub difficulty_config: DifficultyConfig,
+    pub terminal_total_difficulty: Option<U256>,
+}
+
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct EthereumHeaderData {
+    pub header: Header,
+    // Ethash DAG nodes with their merkle proofs, required for the PoW verification
+    pub proof: Vec<DoubleNodeWithMerkleProof>,
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum DeinitializationCursor {
+    // headers are removed first, then the hashes of each height
+    Headers(Option<Vec<u8>>),
+    HeadersByNumber(Option<Vec<u8>>),
+}
+
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct StoredHeader {
+    pub header: Header,
+    pub total_difficulty: U256,
+    pub finalized: bool,
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct EthereumInclusionData {
+    // SCALE encoded log emitted by the transaction
+    pub encoded_log: Vec<u8>,
+    // merkle patricia trie proof of the receipt, starting with the root node
+    pub receipt_proof: Vec<Vec<u8>>,
+    // hash of the block containing the transaction receipt
+    pub block_hash: H256,
+}
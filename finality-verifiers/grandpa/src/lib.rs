@@ -24,7 +24,10 @@
 //!
 //! The pallet is responsible for tracking GRANDPA validator set hand-offs. We only import headers
 //! with justifications signed by the current validator set we know of. The header is inspected for
-//! a `ScheduledChanges` digest item, which is then used to update to next validator set.
+//! `ScheduledChange` and `ForcedChange` digest items, which are kept as a pending change until the
+//! header at their enactment height is imported, and then used to update to next validator set.
+//! Forced changes recover a stalled validator set, so the range reaching their enactment height is
+//! already expected to be signed by the next validator set.
 //!
 //! Since this pallet only tracks finalized headers it does not deal with forks. Forks can only
 //! occur if the GRANDPA validator set on the bridged chain is either colluding or there is a severe
@@ -44,12 +47,18 @@ use bp_runtime::{BlockNumberOf, Chain, ChainId, HashOf, HasherOf, HeaderOf};
 use sp_std::convert::TryInto;
 
 use finality_grandpa::voter_set::VoterSet;
-use frame_support::{ensure, pallet_prelude::*, StorageHasher};
+use frame_support::{
+    ensure,
+    pallet_prelude::*,
+    storage::migration::take_storage_value,
+    traits::{PalletInfoAccess, StorageVersion},
+    StorageHasher,
+};
 use frame_system::{ensure_signed, RawOrigin};
 use num_traits::cast::AsPrimitive;
 use sp_core::crypto::ByteArray;
 use sp_finality_grandpa::{ConsensusLog, GRANDPA_ENGINE_ID};
-use sp_runtime::traits::{BadOrigin, Header as HeaderT, Zero};
+use sp_runtime::traits::{BadOrigin, Header as HeaderT, Saturating, Zero};
 use sp_std::{vec, vec::Vec};
 
 mod types;
@@ -86,7 +95,9 @@ pub type BridgedHeader<T, I> = HeaderOf<<T as Config<I>>::BridgedChain>;
 
 use crate::{
     side_effects::decode_event,
-    types::{InclusionData, Parachain, ParachainHeaderData, RelaychainHeaderData},
+    types::{
+        InclusionData, Parachain, ParachainHeaderData, PendingAuthorityChange, RelaychainHeaderData,
+    },
 };
 use frame_system::pallet_prelude::*;
 
@@ -112,12 +123,20 @@ pub mod pallet {
         type WeightInfo: WeightInfo;
     }
 
+    /// The in-code storage version.
+    pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
     #[pallet::without_storage_info]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
     #[pallet::hooks]
-    impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {}
+    impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+        fn on_runtime_upgrade() -> Weight {
+            migrate_authority_set_to_map::<T, I>()
+        }
+    }
 
     /// Hash of the header used to bootstrap the pallet.
     #[pallet::storage]
@@ -167,14 +186,15 @@ pub mod pallet {
         (BridgedBlockHash<T, I>, BridgedBlockHash<T, I>),
     >;
 
+    /// Map of the current GRANDPA Authority set, together with its set id, of each relaychain.
     #[pallet::storage]
-    pub(super) type RelayChainId<T: Config<I>, I: 'static = ()> =
-        StorageValue<_, ChainId, OptionQuery>;
+    pub(super) type CurrentAuthoritySetMap<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Blake2_256, ChainId, bp_header_chain::AuthoritySet>;
 
-    /// The current GRANDPA Authority set.
+    /// Map of the GRANDPA Authority set change, scheduled or forced, which awaits its enactment height.
     #[pallet::storage]
-    pub(super) type CurrentAuthoritySet<T: Config<I>, I: 'static = ()> =
-        StorageValue<_, bp_header_chain::AuthoritySet, OptionQuery>;
+    pub(super) type PendingAuthorityChangeMap<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Blake2_256, ChainId, PendingAuthorityChange<BridgedBlockNumber<T, I>>>;
 
     /// The current GRANDPA Authority set.
     #[pallet::storage]
@@ -243,8 +263,6 @@ pub mod pallet {
         EventDecodingFailed,
         /// The side effect is not known for this vendor
         UnkownSideEffect,
-        /// A forced change was detected, which is not supported
-        UnsupportedScheduledChange,
         /// The pallet is currently halted
         Halted,
         /// The block height couldn't be converted
@@ -291,24 +309,31 @@ pub mod pallet {
 
         // For efficiency we check the the justification first. If it's invalid, we can skip the rest
         let (signed_hash, signed_number) = (signed_header.hash(), signed_header.number());
-        let authority_set =
-            <CurrentAuthoritySet<T, I>>::get().ok_or(Error::<T, I>::InvalidAuthoritySet)?;
 
-        let set_id = authority_set.set_id;
+        // track authority set changes signalled by the range, which determine the set signing the justification
+        let mut authority_set = <CurrentAuthoritySetMap<T, I>>::get(gateway_id)
+            .ok_or(Error::<T, I>::InvalidAuthoritySet)?;
+        let mut pending_change = <PendingAuthorityChangeMap<T, I>>::get(gateway_id);
+        let mut signalled_change = None;
+        for header in range.iter().chain(sp_std::iter::once(&signed_header)) {
+            try_enact_authority_change_single::<T, I>(
+                header,
+                &mut authority_set,
+                &mut pending_change,
+                &mut signalled_change,
+                gateway_id,
+            )?;
+        }
+
         // °°°°° Begin Check: #2 °°°°°
         verify_justification_single::<T, I>(
             &justification,
             signed_hash,
             *signed_number,
-            authority_set,
-            gateway_id,
+            &authority_set,
         )?;
         // °°°°° Checked: #2 °°°°°°
 
-        // check for authority set update and enact if available.
-        let _enacted =
-            try_enact_authority_change_single::<T, I>(&signed_header, set_id, gateway_id)?;
-
         // We get the latest buffer_index, which maps to the next header we can overwrite, and the index where we insert the verified header
         let mut buffer_index =
             <MultiImportedHashesPointer<T, I>>::get(gateway_id).unwrap_or_default();
@@ -352,6 +377,26 @@ pub mod pallet {
         // Update pointer
         <MultiImportedHashesPointer<T, I>>::insert(gateway_id, buffer_index);
 
+        // The range is finalized now, so changes reaching their enactment height with it are enacted.
+        // A forced change signalled by the range, yet to be enacted, replaces the pending one.
+        let changes = match signalled_change {
+            Some(change) if change.is_forced => vec![change],
+            maybe_change => pending_change.into_iter().chain(maybe_change).collect(),
+        };
+        let mut remaining_change = None;
+        for change in changes {
+            if change.enact_at <= *signed_number {
+                enact_authority_change::<T, I>(&mut authority_set, change, gateway_id);
+            } else {
+                remaining_change = Some(change);
+            }
+        }
+        <CurrentAuthoritySetMap<T, I>>::insert(gateway_id, authority_set);
+        match remaining_change {
+            Some(change) => <PendingAuthorityChangeMap<T, I>>::insert(gateway_id, change),
+            None => <PendingAuthorityChangeMap<T, I>>::remove(gateway_id),
+        }
+
         let height: usize = signed_number.as_();
         match u32::try_from(height) {
             Ok(number) => Ok(number.to_be_bytes().to_vec()),
@@ -450,57 +495,102 @@ pub mod pallet {
         }
     }
 
-    /// Check the given header for a GRANDPA scheduled authority set change. If a change
-    /// is found it will be enacted immediately.
+    /// Check the given header for a GRANDPA authority set change and track it as pending until its
+    /// enactment height, which is the height of the signalling header plus the change's delay.
+    ///
+    /// A pending scheduled change is enacted once a header above its enactment height is visited,
+    /// since that header is finalized by the next authority set. Scheduled changes signalled by the
+    /// visited headers are only returned as `signalled_change`, as they can't be trusted before the
+    /// signalling header is finalized by the current set.
     ///
-    /// This function does not support forced changes, or scheduled changes with delays
-    /// since these types of changes are indicitive of abnormal behaviour from GRANDPA.
+    /// Forced changes are signalled when the current set has stalled and won't finalize anything
+    /// anymore. They replace the pending change and are enacted as soon as a header at their
+    /// enactment height is visited, so the justification is verified against the next set.
+    ///
+    /// GRANDPA doesn't signal a scheduled change while another one is pending, so such a signal is
+    /// ignored rather than stalling the import of the gateway's headers.
     pub(crate) fn try_enact_authority_change_single<T: Config<I>, I: 'static>(
         header: &BridgedHeader<T, I>,
-        current_set_id: sp_finality_grandpa::SetId,
+        authority_set: &mut bp_header_chain::AuthoritySet,
+        pending_change: &mut Option<PendingAuthorityChange<BridgedBlockNumber<T, I>>>,
+        signalled_change: &mut Option<PendingAuthorityChange<BridgedBlockNumber<T, I>>>,
         gateway_id: ChainId,
     ) -> Result<bool, sp_runtime::DispatchError> {
         let mut change_enacted = false;
+        let number = *header.number();
 
-        // We don't support forced changes - at that point governance intervention is required.
-        ensure!(
-            super::find_forced_change(header).is_none(),
-            <Error<T, I>>::UnsupportedScheduledChange
-        );
+        if let Some(change) = pending_change.take() {
+            if change.enact_at < number || (change.is_forced && change.enact_at <= number) {
+                enact_authority_change::<T, I>(authority_set, change, gateway_id);
+                change_enacted = true;
+            } else {
+                *pending_change = Some(change);
+            }
+        }
 
-        if let Some(change) = super::find_scheduled_change(header) {
-            // GRANDPA only includes a `delay` for forced changes, so this isn't valid.
-            ensure!(
-                change.delay == Zero::zero(),
-                <Error<T, I>>::UnsupportedScheduledChange
-            );
+        if let Some((_median_last_finalized, change)) = super::find_forced_change(header) {
+            *signalled_change = Some(PendingAuthorityChange {
+                next_authorities: change.next_authorities,
+                enact_at: number.saturating_add(change.delay),
+                is_forced: true,
+            });
+        } else if let Some(change) = super::find_scheduled_change(header) {
+            if pending_change.is_some() || signalled_change.is_some() {
+                log::warn!(
+                    "Ignored scheduled authority set change at {:?} while another one is pending for gateway: {:?}",
+                    number,
+                    gateway_id,
+                );
+            } else {
+                *signalled_change = Some(PendingAuthorityChange {
+                    next_authorities: change.next_authorities,
+                    enact_at: number.saturating_add(change.delay),
+                    is_forced: false,
+                });
+            }
+        }
 
-            // TODO [#788]: Stop manually increasing the `set_id` here.
-            let next_authorities = bp_header_chain::AuthoritySet {
-                authorities: change.next_authorities,
-                set_id: current_set_id + 1,
-            };
+        // a forced change reaching its enactment height takes over before the justification check
+        if let Some(change) = signalled_change.take() {
+            if change.is_forced && change.enact_at <= number {
+                *pending_change = None;
+                enact_authority_change::<T, I>(authority_set, change, gateway_id);
+                change_enacted = true;
+            } else {
+                *signalled_change = Some(change);
+            }
+        }
+
+        Ok(change_enacted)
+    }
 
-            // Since our header schedules a change and we know the delay is 0, it must also enact
-            // the change.
-            <CurrentAuthoritySet<T, I>>::put(&next_authorities);
-            change_enacted = true;
-
-            log::info!(
-				"Transitioned from authority set {} to {}! New authorities are: {:?} for gateway: {:?}",
-				current_set_id,
-				current_set_id + 1,
-				next_authorities,
-				gateway_id,
-			);
+    /// Replaces the given authority set with the next one of the change.
+    pub(crate) fn enact_authority_change<T: Config<I>, I: 'static>(
+        authority_set: &mut bp_header_chain::AuthoritySet,
+        change: PendingAuthorityChange<BridgedBlockNumber<T, I>>,
+        gateway_id: ChainId,
+    ) {
+        let current_set_id = authority_set.set_id;
+
+        // TODO [#788]: Stop manually increasing the `set_id` here.
+        *authority_set = bp_header_chain::AuthoritySet {
+            authorities: change.next_authorities,
+            set_id: current_set_id + 1,
         };
 
-        Ok(change_enacted)
+        log::info!(
+            "Transitioned from authority set {} to {} (forced: {})! New authorities are: {:?} for gateway: {:?}",
+            current_set_id,
+            current_set_id + 1,
+            change.is_forced,
+            authority_set,
+            gateway_id,
+        );
     }
 
     /// Verify a GRANDPA justification (finality proof) for a given header.
     ///
-    /// Will use the GRANDPA current authorities known to the pallet.
+    /// Will use the GRANDPA authorities of the gateway, after enacting the changes preceding the header.
     ///
     /// If succesful it returns the decoded GRANDPA justification so we can refund any weight which
     /// was overcharged in the initial call.
@@ -508,8 +598,7 @@ pub mod pallet {
         justification: &GrandpaJustification<BridgedHeader<T, I>>,
         hash: BridgedBlockHash<T, I>,
         number: BridgedBlockNumber<T, I>,
-        authority_set: bp_header_chain::AuthoritySet,
-        _gateway_id: ChainId,
+        authority_set: &bp_header_chain::AuthoritySet,
     ) -> Result<(), Error<T, I>> {
         use bp_header_chain::justification::verify_justification;
 
@@ -535,8 +624,6 @@ pub mod pallet {
         init_params: super::InitializationData<BridgedHeader<T, I>>,
         owner: T::AccountId,
     ) -> Result<(), &'static str> {
-        can_init_relay_chain::<T, I>()?;
-
         let super::InitializationData {
             header,
             authority_list,
@@ -551,9 +638,8 @@ pub mod pallet {
         <BestFinalizedMap<T, I>>::insert(gateway_id, initial_hash);
         <MultiImportedHeaders<T, I>>::insert(gateway_id, initial_hash, header);
         <MultiImportedHashesPointer<T, I>>::insert(gateway_id, 0); // one ahead of first value
-        <RelayChainId<T, I>>::put(gateway_id);
         let authority_set = bp_header_chain::AuthoritySet::new(authority_list, set_id);
-        <CurrentAuthoritySet<T, I>>::put(authority_set);
+        <CurrentAuthoritySetMap<T, I>>::insert(gateway_id, authority_set);
 
         // Other configs
         <IsHaltedMap<T, I>>::insert(gateway_id, is_halted);
@@ -731,6 +817,40 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
         }
     }
 
+    /// Removes all the state of the gateway, so it can be initialized again.
+    ///
+    /// May only be called either by root, or by `PalletOwner`.
+    pub fn deinitialize(origin: T::Origin, gateway_id: ChainId) -> Result<(), &'static str> {
+        ensure_owner_or_root_single::<T, I>(origin, gateway_id)?;
+        ensure!(
+            <BestFinalizedMap<T, I>>::contains_key(gateway_id),
+            "Not initialized"
+        );
+
+        // the ring buffer holds at most `HeadersToStore` hashes of the gateway
+        for (_, hash) in <MultiImportedHashes<T, I>>::drain_prefix(gateway_id) {
+            <MultiImportedHeaders<T, I>>::remove(gateway_id, hash);
+            <MultiImportedRoots<T, I>>::remove(gateway_id, hash);
+        }
+        if let Some(initial_hash) = <InitialHashMap<T, I>>::take(gateway_id) {
+            <MultiImportedHeaders<T, I>>::remove(gateway_id, initial_hash);
+            <MultiImportedRoots<T, I>>::remove(gateway_id, initial_hash);
+        }
+
+        <BestFinalizedMap<T, I>>::remove(gateway_id);
+        <MultiImportedHashesPointer<T, I>>::remove(gateway_id);
+        <CurrentAuthoritySetMap<T, I>>::remove(gateway_id);
+        <PendingAuthorityChangeMap<T, I>>::remove(gateway_id);
+        <ParachainIdMap<T, I>>::remove(gateway_id);
+        <IsHaltedMap<T, I>>::remove(gateway_id);
+        <PalletOwnerMap<T, I>>::remove(gateway_id);
+        <InstantiatedGatewaysMap<T, I>>::mutate(|gateways| {
+            gateways.retain(|instantiated| *instantiated != gateway_id)
+        });
+
+        Ok(())
+    }
+
     /// Change `PalletOwner`.
     ///
     /// May only be called either by root, or by `PalletOwner`.
@@ -786,7 +906,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
     ) -> Result<Vec<u8>, DispatchError> {
         ensure_operational_single::<T, I>(gateway_id)?;
         ensure_signed(origin)?;
-        if Some(gateway_id) == <RelayChainId<T, I>>::get() {
+        if <CurrentAuthoritySetMap<T, I>>::contains_key(gateway_id) {
             let data: RelaychainHeaderData<BridgedHeader<T, I>> =
                 Decode::decode(&mut &*encoded_header_data)
                     .map_err(|_| Error::<T, I>::HeaderDataDecodingError)?;
@@ -822,8 +942,11 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
             Decode::decode(&mut &*encoded_inclusion_data)
                 .map_err(|_| Error::<T, I>::InclusionDataDecodeError)?;
 
-        // ensures old equal side_effects can't be replayed
-        executed_after_creation::<T, I>(gateway_id, submission_target_height)?;
+        let header = <MultiImportedHeaders<T, I>>::get(gateway_id, inclusion_data.block_hash)
+            .ok_or(Error::<T, I>::UnknownHeader)?;
+
+        // ensures old equal side_effects can't be replayed with events of earlier blocks
+        executed_after_creation::<T, I>(&header, submission_target_height)?;
 
         match &side_effect_id {
             b"tran" => verify_event_storage_proof::<T, I>(
@@ -836,6 +959,46 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
         }
     }
 
+    /// Confirms that the encoded event was emitted in the block with the given hash.
+    ///
+    /// The block must have been imported into the pallet, which makes its storage root the
+    /// one verified by the GRANDPA justification, and must be finalized after the SideEffect
+    /// was submitted. The inclusion proof is checked against that storage root.
+    pub fn confirm_event_inclusion(
+        gateway_id: ChainId,
+        encoded_event: Vec<u8>,
+        submission_target_height: Vec<u8>,
+        inclusion_proof: Vec<Vec<u8>>,
+        encoded_block_hash: Vec<u8>,
+        value_abi_unsigned_type: &[u8],
+        side_effect_id: [u8; 4],
+    ) -> Result<(Vec<Vec<u8>>, Vec<u8>), DispatchError> {
+        let block_hash: BridgedBlockHash<T, I> = Decode::decode(&mut &*encoded_block_hash)
+            .map_err(|_| Error::<T, I>::InclusionDataDecodeError)?;
+
+        let header = <MultiImportedHeaders<T, I>>::get(gateway_id, block_hash)
+            .ok_or(Error::<T, I>::UnknownHeader)?;
+
+        // ensures old equal side_effects can't be replayed with events of earlier blocks
+        executed_after_creation::<T, I>(&header, submission_target_height)?;
+
+        let inclusion_data = InclusionData::<BridgedHeader<T, I>> {
+            encoded_payload: encoded_event,
+            proof: StorageProof::new(inclusion_proof),
+            block_hash,
+        };
+
+        match &side_effect_id {
+            b"tran" => verify_event_storage_proof::<T, I>(
+                gateway_id,
+                inclusion_data,
+                value_abi_unsigned_type,
+                side_effect_id,
+            ),
+            _ => Err(Error::<T, I>::UnkownSideEffect.into()),
+        }
+    }
+
     pub fn get_latest_finalized_header(gateway_id: ChainId) -> Option<Vec<u8>> {
         if let Some(header_hash) = <BestFinalizedMap<T, I>>::get(gateway_id) {
             return Some(header_hash.encode())
@@ -922,49 +1085,60 @@ fn ensure_owner_or_root_single<T: Config<I>, I: 'static>(
     }
 }
 
-/// Ensure that no relaychain has been set so far. Relaychains are unique
-fn can_init_relay_chain<T: Config<I>, I: 'static>() -> Result<(), &'static str> {
-    match <RelayChainId<T, I>>::exists() {
-        true => Err("Duplicate relaychain"), // we have a relaychain registered already
-        false => Ok(()),                     // is first relaychain
+/// Moves the authority set of the single relaychain, kept in `CurrentAuthoritySet` before
+/// authority sets were stored per gateway, under its `RelayChainId` in `CurrentAuthoritySetMap`.
+pub(crate) fn migrate_authority_set_to_map<T: Config<I>, I: 'static>() -> Weight {
+    if StorageVersion::get::<Pallet<T, I>>() >= STORAGE_VERSION {
+        return T::DbWeight::get().reads(1)
     }
+
+    let pallet_name = <Pallet<T, I> as PalletInfoAccess>::name().as_bytes();
+    let maybe_relay_chain_id = take_storage_value::<ChainId>(pallet_name, b"RelayChainId", &[]);
+    let maybe_authority_set = take_storage_value::<bp_header_chain::AuthoritySet>(
+        pallet_name,
+        b"CurrentAuthoritySet",
+        &[],
+    );
+
+    if let (Some(relay_chain_id), Some(authority_set)) = (maybe_relay_chain_id, maybe_authority_set)
+    {
+        <CurrentAuthoritySetMap<T, I>>::insert(relay_chain_id, authority_set);
+    }
+
+    STORAGE_VERSION.put::<Pallet<T, I>>();
+
+    T::DbWeight::get().reads_writes(3, 4)
 }
 
-/// Ensure that no relaychain has been set so far. Relaychains are unique
+/// Ensure that the parachain's relaychain has been registered before and that the parachain
+/// isn't tracked under another gateway_id already.
 fn can_init_para_chain<T: Config<I>, I: 'static>(
     parachain: &Parachain,
 ) -> Result<(), &'static str> {
-    match <RelayChainId<T, I>>::get() {
-        Some(relay_chain_id) => {
-            // we have a relaychain setup
-            match relay_chain_id == parachain.relay_chain_id {
-                true => Ok(()), // registering relachchain_id matches the stored one
-                false => Err("Invalid relaychainId"), // wrong relaychain_id was passed
-            }
-        },
-        None => Err("No relaychain"), // we have no relaychain setup
+    if <ParachainIdMap<T, I>>::iter_values().any(|registered| registered == *parachain) {
+        Err("Parachain already registered")
+    } else if <CurrentAuthoritySetMap<T, I>>::contains_key(parachain.relay_chain_id) {
+        Ok(()) // registering relachchain_id matches a stored one
+    } else if <CurrentAuthoritySetMap<T, I>>::iter_keys().next().is_some() {
+        Err("Invalid relaychainId") // wrong relaychain_id was passed
+    } else {
+        Err("No relaychain") // we have no relaychain setup
     }
 }
 
-/// Ensure that the SideEffect was executed after it was created.
+/// Ensure that the block including the SideEffect's event was produced after the SideEffect was created.
 fn executed_after_creation<T: Config<I>, I: 'static>(
-    gateway_id: ChainId,
+    header: &BridgedHeader<T, I>,
     submission_target_height: Vec<u8>,
 ) -> Result<(), &'static str> {
     let submission_target: BridgedBlockNumber<T, I> =
-        Decode::decode(&mut &*submission_target_height).unwrap();
-    if let Some(header_hash) = <BestFinalizedMap<T, I>>::get(gateway_id) {
-        if let Some(header) = <MultiImportedHeaders<T, I>>::get(gateway_id, header_hash) {
-            if submission_target < *header.number() {
-                return Ok(())
-            }
-            Err("Transaction executed before SideEffect creation")
-        } else {
-            Err("No gateway header found") // this shouldn't be possible tom happen
-        }
-    } else {
-        Err("No gateway header found") // this shouldn't be possible tom happen
-    }
+        Decode::decode(&mut &*submission_target_height)
+            .map_err(|_| "Submission target height decoding error")?;
+    ensure!(
+        submission_target < *header.number(),
+        "Transaction executed before SideEffect creation"
+    );
+    Ok(())
 }
 
 /// Checks the given header for a consensus digest signalling a **forced** scheduled change and
@@ -1263,7 +1437,7 @@ mod tests {
 
             // Reset storage so we can initialize the pallet again
             BestFinalizedMap::<TestRuntime>::remove(default_gateway);
-            RelayChainId::<TestRuntime>::kill();
+            CurrentAuthoritySetMap::<TestRuntime>::remove(default_gateway);
             PalletOwnerMap::<TestRuntime>::insert(default_gateway, 2);
             assert_ok!(initialize_relaychain(Origin::signed(2)));
         })
@@ -1289,17 +1463,157 @@ mod tests {
         })
     }
 
+    #[test]
+    fn deinitialize_removes_gateway_state() {
+        let default_gateway: ChainId = *b"pdot";
+
+        run_test(|| {
+            assert_ok!(initialize_relaychain(Origin::root()));
+            assert_ok!(submit_headers(1, 5));
+
+            assert_noop!(
+                Pallet::<TestRuntime>::deinitialize(Origin::signed(2), default_gateway),
+                "Bad origin"
+            );
+            assert_ok!(Pallet::<TestRuntime>::deinitialize(
+                Origin::root(),
+                default_gateway
+            ));
+
+            assert_eq!(
+                Pallet::<TestRuntime>::get_latest_finalized_header(default_gateway),
+                None
+            );
+            assert_eq!(
+                <MultiImportedHeaders<TestRuntime>>::iter_prefix(default_gateway).count(),
+                0
+            );
+            assert!(!<InstantiatedGatewaysMap<TestRuntime>>::get().contains(&default_gateway));
+            assert_ok!(initialize_relaychain(Origin::root()));
+        })
+    }
+
+    #[test]
+    fn cant_register_duplicate_relaychain() {
+        run_test(|| {
+            assert_ok!(initialize_relaychain(Origin::root()));
+            assert_noop!(
+                initialize_named_relaychain(Origin::root(), *b"pdot"),
+                "Already initialized"
+            );
+        })
+    }
+
     #[test]
     fn cant_register_duplicate_parachain() {
         run_test(|| {
             assert_ok!(initialize_relaychain(Origin::root()));
+            assert_ok!(initialize_parachain(Origin::root()));
             assert_noop!(
-                initialize_named_relaychain(Origin::root(), *b"roco"),
-                "Duplicate relaychain"
+                initialize_named_parachain(Origin::root(), *b"moo2"),
+                "Parachain already registered"
             );
         })
     }
 
+    #[test]
+    fn runtime_upgrade_moves_single_authority_set_under_relaychain_id() {
+        use frame_support::{storage::migration::put_storage_value, traits::Hooks};
+
+        run_test(|| {
+            let pallet_name = <Pallet<TestRuntime> as PalletInfoAccess>::name().as_bytes();
+            let authority_set = bp_header_chain::AuthoritySet::new(authority_list(), 3);
+
+            put_storage_value(pallet_name, b"RelayChainId", &[], *b"pdot");
+            put_storage_value(pallet_name, b"CurrentAuthoritySet", &[], &authority_set);
+            StorageVersion::new(0).put::<Pallet<TestRuntime>>();
+
+            <Pallet<TestRuntime> as Hooks<u64>>::on_runtime_upgrade();
+
+            assert_eq!(
+                CurrentAuthoritySetMap::<TestRuntime>::get(*b"pdot"),
+                Some(authority_set)
+            );
+            assert_eq!(
+                take_storage_value::<ChainId>(pallet_name, b"RelayChainId", &[]),
+                None
+            );
+            assert_eq!(
+                take_storage_value::<bp_header_chain::AuthoritySet>(
+                    pallet_name,
+                    b"CurrentAuthoritySet",
+                    &[]
+                ),
+                None
+            );
+            assert_eq!(
+                StorageVersion::get::<Pallet<TestRuntime>>(),
+                STORAGE_VERSION
+            );
+        })
+    }
+
+    #[test]
+    fn can_register_multiple_relaychains_with_own_authority_sets() {
+        run_test(|| {
+            assert_ok!(initialize_relaychain(Origin::root()));
+
+            let genesis = test_header(0);
+            let different_authorities: Vec<AuthorityId> = vec![ALICE.into(), DAVE.into()];
+            let init_data = GrandpaRegistrationData::<AccountId> {
+                authorities: Some(different_authorities),
+                first_header: genesis.encode(),
+                authority_set_id: Some(7),
+                owner: 1,
+                parachain: None,
+            };
+            assert_ok!(initialize_custom_relaychain(
+                Origin::root(),
+                *b"ksma",
+                init_data
+            ));
+
+            assert_eq!(
+                CurrentAuthoritySetMap::<TestRuntime>::get(*b"pdot"),
+                Some(bp_header_chain::AuthoritySet::new(authority_list(), 1)),
+            );
+            assert_eq!(
+                CurrentAuthoritySetMap::<TestRuntime>::get(*b"ksma")
+                    .unwrap()
+                    .set_id,
+                7
+            );
+
+            // justifications of the first relaychain still verify against its own set
+            assert_ok!(submit_headers(1, 3));
+        })
+    }
+
+    #[test]
+    fn can_register_parachain_of_second_relaychain() {
+        run_test(|| {
+            assert_ok!(initialize_relaychain(Origin::root()));
+            assert_ok!(initialize_named_relaychain(Origin::root(), *b"ksma"));
+
+            let genesis = test_header(0);
+            let init_data = GrandpaRegistrationData::<AccountId> {
+                authorities: None,
+                first_header: genesis.encode(),
+                authority_set_id: None,
+                owner: 1,
+                parachain: Some(Parachain {
+                    relay_chain_id: *b"ksma",
+                    id: 2000,
+                }),
+            };
+            assert_ok!(initialize_custom_parachain(
+                Origin::root(),
+                *b"karu",
+                init_data
+            ));
+        })
+    }
+
     #[test]
     fn cant_register_parachain_without_relaychain() {
         run_test(|| {
@@ -1403,7 +1717,7 @@ mod tests {
                 Some(header.hash())
             );
             assert_eq!(
-                CurrentAuthoritySet::<TestRuntime>::get()
+                CurrentAuthoritySetMap::<TestRuntime>::get(default_gateway)
                     .unwrap()
                     .authorities,
                 authority_list()
@@ -1811,7 +2125,7 @@ mod tests {
 
             // Make sure that the authority set actually changed upon importing our header
             assert_eq!(
-                <CurrentAuthoritySet<TestRuntime>>::get(),
+                <CurrentAuthoritySetMap<TestRuntime>>::get(default_gateway),
                 Some(bp_header_chain::AuthoritySet::new(
                     next_authorities,
                     next_set_id
@@ -1821,72 +2135,234 @@ mod tests {
     }
 
     #[test]
-    fn importing_header_rejects_header_with_scheduled_change_delay() {
+    fn importing_header_enacts_scheduled_change_at_delayed_height() {
         run_test(|| {
             let _ = initialize_relaychain(Origin::root());
 
             // Need to update the header digest to indicate that our header signals an authority set
-            // change. However, the change doesn't happen until the next block.
-            let headers: Vec<TestHeader> = test_header_range(2);
+            // change. However, the change doesn't happen until two blocks later.
+            let headers: Vec<TestHeader> = test_header_range(5);
             let mut signed_header = headers[2].clone();
-            let mut range: Vec<TestHeader> = headers[1..2].to_vec();
-            range.reverse();
+            let range: Vec<TestHeader> = headers[1..2].to_vec();
 
-            signed_header.digest = change_log(1);
+            signed_header.digest = change_log(2);
 
-            let justification = make_default_justification(&signed_header);
             let data = RelaychainHeaderData::<TestHeader> {
-                signed_header,
+                signed_header: signed_header.clone(),
                 range,
-                justification,
+                justification: make_default_justification(&signed_header),
             };
 
             let default_gateway: ChainId = *b"pdot";
 
-            // Should not be allowed to import this header
-            assert_err!(
-                Pallet::<TestRuntime>::submit_headers(
-                    Origin::signed(1),
-                    default_gateway,
-                    data.encode()
-                ),
-                <Error<TestRuntime>>::UnsupportedScheduledChange
+            assert_ok!(Pallet::<TestRuntime>::submit_headers(
+                Origin::signed(1),
+                default_gateway,
+                data.encode()
+            ));
+
+            // The change is pending and the current set stays in charge until the enactment height
+            assert_eq!(
+                <PendingAuthorityChangeMap<TestRuntime>>::get(default_gateway),
+                Some(PendingAuthorityChange {
+                    next_authorities: vec![(ALICE.into(), 1), (BOB.into(), 1)],
+                    enact_at: 4,
+                    is_forced: false,
+                })
+            );
+            assert_eq!(
+                <CurrentAuthoritySetMap<TestRuntime>>::get(default_gateway),
+                Some(bp_header_chain::AuthoritySet::new(authority_list(), 1)),
+            );
+
+            // The header at the enactment height is still finalized by the current set
+            let mut headers = headers;
+            headers[3].parent_hash = signed_header.hash();
+            headers[4].parent_hash = headers[3].hash();
+            let data = RelaychainHeaderData::<TestHeader> {
+                signed_header: headers[4].clone(),
+                range: headers[3..4].to_vec(),
+                justification: make_default_justification(&headers[4]),
+            };
+
+            assert_ok!(Pallet::<TestRuntime>::submit_headers(
+                Origin::signed(1),
+                default_gateway,
+                data.encode()
+            ));
+
+            assert!(!<PendingAuthorityChangeMap<TestRuntime>>::contains_key(
+                default_gateway
+            ));
+            assert_eq!(
+                <CurrentAuthoritySetMap<TestRuntime>>::get(default_gateway),
+                Some(bp_header_chain::AuthoritySet::new(
+                    vec![(ALICE.into(), 1), (BOB.into(), 1)],
+                    2
+                )),
+            );
+        })
+    }
+
+    #[test]
+    fn importing_header_ignores_scheduled_change_while_another_is_pending() {
+        run_test(|| {
+            let _ = initialize_relaychain(Origin::root());
+
+            let mut headers: Vec<TestHeader> = test_header_range(2);
+            headers[1].digest = change_log(5);
+            headers[2].parent_hash = headers[1].hash();
+            let mut signed_header = headers[2].clone();
+            signed_header.digest = change_log(5);
+
+            let data = RelaychainHeaderData::<TestHeader> {
+                signed_header: signed_header.clone(),
+                range: headers[1..2].to_vec(),
+                justification: make_default_justification(&signed_header),
+            };
+
+            let default_gateway: ChainId = *b"pdot";
+
+            assert_ok!(Pallet::<TestRuntime>::submit_headers(
+                Origin::signed(1),
+                default_gateway,
+                data.encode()
+            ));
+
+            // The first signalled change stays pending, and the import goes on
+            assert_eq!(
+                <BestFinalizedMap<TestRuntime>>::get(default_gateway),
+                Some(signed_header.hash())
+            );
+            assert_eq!(
+                <PendingAuthorityChangeMap<TestRuntime>>::get(default_gateway),
+                Some(PendingAuthorityChange {
+                    next_authorities: vec![(ALICE.into(), 1), (BOB.into(), 1)],
+                    enact_at: 6,
+                    is_forced: false,
+                })
             );
         })
     }
 
     #[test]
-    fn importing_header_rejects_header_with_forced_changes() {
+    fn importing_header_accepts_forced_change_signed_by_next_set() {
         run_test(|| {
             let _ = initialize_relaychain(Origin::root());
 
             // Need to update the header digest to indicate that it signals a forced authority set
-            // change.
+            // change, which is enacted right away as the current set has stalled.
             let headers: Vec<TestHeader> = test_header_range(2);
             let mut signed_header = headers[2].clone();
-            let mut range: Vec<TestHeader> = headers[1..2].to_vec();
-            range.reverse();
+            let range: Vec<TestHeader> = headers[1..2].to_vec();
 
-            signed_header.digest = change_log(1);
+            signed_header.digest = forced_change_log(0);
 
-            let justification = make_default_justification(&signed_header);
+            let next_authorities = vec![(ALICE, 1), (BOB, 1)];
+            let justification = make_justification_for_header(JustificationGeneratorParams {
+                header: signed_header.clone(),
+                set_id: 2,
+                authorities: next_authorities,
+                ..Default::default()
+            });
 
             let data = RelaychainHeaderData::<TestHeader> {
-                signed_header,
+                signed_header: signed_header.clone(),
                 range,
                 justification,
             };
 
             let default_gateway: ChainId = *b"pdot";
 
-            // Should not be allowed to import this header
+            assert_ok!(Pallet::<TestRuntime>::submit_headers(
+                Origin::signed(1),
+                default_gateway,
+                data.encode()
+            ));
+
+            assert_eq!(
+                <BestFinalizedMap<TestRuntime>>::get(default_gateway),
+                Some(signed_header.hash())
+            );
+            assert_eq!(
+                <CurrentAuthoritySetMap<TestRuntime>>::get(default_gateway),
+                Some(bp_header_chain::AuthoritySet::new(
+                    vec![(ALICE.into(), 1), (BOB.into(), 1)],
+                    2
+                )),
+            );
+        })
+    }
+
+    #[test]
+    fn importing_header_rejects_enacted_forced_change_signed_by_previous_set() {
+        run_test(|| {
+            let _ = initialize_relaychain(Origin::root());
+
+            let headers: Vec<TestHeader> = test_header_range(2);
+            let mut signed_header = headers[2].clone();
+            signed_header.digest = forced_change_log(0);
+
+            let data = RelaychainHeaderData::<TestHeader> {
+                signed_header: signed_header.clone(),
+                range: headers[1..2].to_vec(),
+                justification: make_default_justification(&signed_header),
+            };
+
+            let default_gateway: ChainId = *b"pdot";
+
             assert_err!(
                 Pallet::<TestRuntime>::submit_headers(
                     Origin::signed(1),
                     default_gateway,
                     data.encode()
                 ),
-                <Error<TestRuntime>>::UnsupportedScheduledChange
+                <Error<TestRuntime>>::InvalidGrandpaJustification
+            );
+            assert_eq!(
+                <CurrentAuthoritySetMap<TestRuntime>>::get(default_gateway),
+                Some(bp_header_chain::AuthoritySet::new(authority_list(), 1)),
+            );
+        })
+    }
+
+    #[test]
+    fn importing_header_replaces_pending_change_with_delayed_forced_change() {
+        run_test(|| {
+            let _ = initialize_relaychain(Origin::root());
+
+            let mut headers: Vec<TestHeader> = test_header_range(2);
+            headers[1].digest = change_log(5);
+            headers[2].parent_hash = headers[1].hash();
+            let mut signed_header = headers[2].clone();
+            signed_header.digest = forced_change_log(3);
+
+            let data = RelaychainHeaderData::<TestHeader> {
+                signed_header: signed_header.clone(),
+                range: headers[1..2].to_vec(),
+                justification: make_default_justification(&signed_header),
+            };
+
+            let default_gateway: ChainId = *b"pdot";
+
+            assert_ok!(Pallet::<TestRuntime>::submit_headers(
+                Origin::signed(1),
+                default_gateway,
+                data.encode()
+            ));
+
+            // The forced change replaces the scheduled one until its enactment height is reached
+            assert_eq!(
+                <PendingAuthorityChangeMap<TestRuntime>>::get(default_gateway),
+                Some(PendingAuthorityChange {
+                    next_authorities: vec![(ALICE.into(), 1), (BOB.into(), 1)],
+                    enact_at: 5,
+                    is_forced: true,
+                })
+            );
+            assert_eq!(
+                <CurrentAuthoritySetMap<TestRuntime>>::get(default_gateway),
+                Some(bp_header_chain::AuthoritySet::new(authority_list(), 1)),
             );
         })
     }
@@ -1934,6 +2410,74 @@ mod tests {
         });
     }
 
+    #[test]
+    fn confirm_event_inclusion_rejects_unknown_block_hash() {
+        let default_gateway: ChainId = *b"pdot";
+
+        run_test(|| {
+            let _ = initialize_relaychain(Origin::root());
+
+            assert_noop!(
+                Pallet::<TestRuntime>::confirm_event_inclusion(
+                    default_gateway,
+                    vec![1, 2, 3],
+                    0u64.encode(),
+                    vec![],
+                    test_header(7).hash().encode(),
+                    b"u128",
+                    *b"tran",
+                ),
+                Error::<TestRuntime>::UnknownHeader,
+            );
+        });
+    }
+
+    #[test]
+    fn confirm_event_inclusion_rejects_block_before_submission_height() {
+        let default_gateway: ChainId = *b"pdot";
+
+        run_test(|| {
+            let _ = initialize_relaychain(Origin::root());
+            let data = submit_headers(1, 3).unwrap();
+
+            assert_noop!(
+                Pallet::<TestRuntime>::confirm_event_inclusion(
+                    default_gateway,
+                    vec![1, 2, 3],
+                    3u64.encode(),
+                    vec![],
+                    data.range[0].hash().encode(),
+                    b"u128",
+                    *b"tran",
+                ),
+                "Transaction executed before SideEffect creation",
+            );
+        });
+    }
+
+    #[test]
+    fn confirm_event_inclusion_rejects_event_without_valid_proof() {
+        let default_gateway: ChainId = *b"pdot";
+
+        run_test(|| {
+            let _ = initialize_relaychain(Origin::root());
+            let data = submit_headers(1, 3).unwrap();
+
+            assert_noop!(
+                Pallet::<TestRuntime>::confirm_event_inclusion(
+                    default_gateway,
+                    vec![1, 2, 3],
+                    0u64.encode(),
+                    vec![],
+                    data.signed_header.hash().encode(),
+                    b"u128",
+                    *b"tran",
+                ),
+                "InvalidStorageProof",
+            );
+        });
+    }
+
     #[test]
     fn should_prune_headers_over_headers_to_keep_parameter() {
         let default_gateway: ChainId = *b"pdot";
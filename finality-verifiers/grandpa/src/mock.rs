@@ -181,3 +181,54 @@ pub fn brute_seed_block_1(gateway_id: [u8; 4]) {
 
     <BestFinalizedMap<TestRuntime>>::insert::<[u8; 4], H256>(gateway_id, block_hash_1);
 }
+
+/// Brute seeds a finalized header of the gateway, whose state root proves the given encoded
+/// `System::Events` of the block. Returns the hash of the header and the storage proof.
+#[cfg(feature = "testing")]
+pub fn brute_seed_block_with_events<T: Config<I>, I: 'static>(
+    gateway_id: [u8; 4],
+    number: crate::BridgedBlockNumber<T, I>,
+    encoded_events: Vec<u8>,
+) -> (crate::BridgedBlockHash<T, I>, Vec<Vec<u8>>)
+where
+    crate::BridgedBlockHash<T, I>: From<H256>,
+{
+    use sp_runtime::traits::Header as HeaderT;
+    use sp_state_machine::{backend::Backend, prove_read, InMemoryBackend};
+
+    // storage key for System_Events
+    let key: Vec<u8> = [
+        38, 170, 57, 78, 234, 86, 48, 224, 124, 72, 174, 12, 149, 88, 206, 247, 128, 212, 30, 94,
+        22, 5, 103, 101, 188, 132, 97, 133, 16, 114, 201, 215,
+    ]
+    .to_vec();
+    let state_version = sp_runtime::StateVersion::default();
+    let backend = <InMemoryBackend<sp_core::Blake2Hasher>>::from((
+        vec![(None, vec![(key.clone(), Some(encoded_events))])],
+        state_version,
+    ));
+    let state_root = backend.storage_root(sp_std::iter::empty(), state_version).0;
+    let proof = prove_read(backend, &[&key[..]])
+        .unwrap()
+        .iter_nodes()
+        .collect::<Vec<Vec<u8>>>();
+
+    let header = <crate::BridgedHeader<T, I> as HeaderT>::new(
+        number,
+        Default::default(),
+        state_root.into(),
+        Default::default(),
+        Default::default(),
+    );
+    let block_hash = header.hash();
+
+    <crate::MultiImportedRoots<T, I>>::insert(
+        gateway_id,
+        block_hash,
+        (header.extrinsics_root(), header.state_root()),
+    );
+    <MultiImportedHeaders<T, I>>::insert(gateway_id, block_hash, header);
+    <BestFinalizedMap<T, I>>::insert(gateway_id, block_hash);
+
+    (block_hash, proof)
+}
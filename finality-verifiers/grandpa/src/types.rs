@@ -1,6 +1,6 @@
 use crate::{bridges::header_chain::justification::GrandpaJustification, TypeInfo};
 use codec::{Decode, Encode};
-use sp_finality_grandpa::{AuthorityId, SetId};
+use sp_finality_grandpa::{AuthorityId, AuthorityList, SetId};
 use sp_std::vec::Vec;
 use sp_trie::StorageProof;
 
@@ -46,3 +46,13 @@ pub struct InclusionData<Header: sp_runtime::traits::Header> {
     pub proof: StorageProof,
     pub block_hash: Header::Hash,
 }
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+pub struct PendingAuthorityChange<BlockNumber> {
+    /// authorities of the next GRANDPA set
+    pub next_authorities: AuthorityList,
+    /// height of the bridged chain at which the change is enacted
+    pub enact_at: BlockNumber,
+    /// forced changes replace the pending change once their signalling header is finalized
+    pub is_forced: bool,
+}
@@ -1,6 +1,6 @@
 use circuit_standalone_runtime::{
-    AccountId, AuraConfig, BalancesConfig, EvmConfig, GenesisConfig, GrandpaConfig, Signature,
-    SudoConfig, SystemConfig, XDNSConfig, WASM_BINARY,
+    AccountId, AuraConfig, BalancesConfig, CircuitConfig, EvmConfig, GenesisConfig, GrandpaConfig,
+    Signature, SudoConfig, SystemConfig, XDNSConfig, WASM_BINARY,
 };
 use jsonrpc_runtime_client::{
     create_rpc_client, get_gtwy_init_data, get_metadata, get_parachain_id, ConnectionParams,
@@ -238,6 +238,14 @@ pub fn development_config() -> Result<ChainSpec, String> {
                     get_account_id_from_seed::<sr25519::Public>("Cli//default"),
                     get_account_id_from_seed::<sr25519::Public>("Ranger//default"),
                 ],
+                // Relayers
+                vec![
+                    get_account_id_from_seed::<sr25519::Public>("Alice"),
+                    get_account_id_from_seed::<sr25519::Public>("Bob"),
+                    get_account_id_from_seed::<sr25519::Public>("Executor//default"),
+                    get_account_id_from_seed::<sr25519::Public>("Cli//default"),
+                    get_account_id_from_seed::<sr25519::Public>("Ranger//default"),
+                ],
                 vec![],
                 standard_side_effects(),
                 vec![],
@@ -294,6 +302,12 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
                     get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
                     get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
                 ],
+                // Relayers
+                vec![
+                    get_account_id_from_seed::<sr25519::Public>("Alice"),
+                    get_account_id_from_seed::<sr25519::Public>("Bob"),
+                    get_account_id_from_seed::<sr25519::Public>("Charlie"),
+                ],
                 vec![],
                 standard_side_effects(),
                 vec![],
@@ -328,6 +342,7 @@ fn testnet_genesis(
     initial_authorities: Vec<(AuraId, GrandpaId)>,
     root_key: AccountId,
     endowed_accounts: Vec<AccountId>,
+    relayers: Vec<AccountId>,
     xdns_records: Vec<XdnsRecord<AccountId>>,
     standard_side_effects: Vec<SideEffectInterface>,
     _initial_gateways: Vec<InitializationData<Header>>,
@@ -366,8 +381,10 @@ fn testnet_genesis(
             standard_side_effects,
         },
         contracts_registry: Default::default(),
+        circuit: CircuitConfig { relayers },
         account_manager: Default::default(),
         treasury: Default::default(),
+        executors: Default::default(),
         three_vm: Default::default(), // TODO: genesis for this needs to be setup for the function pointers\
         evm: EvmConfig {
             // We need _some_ code inserted at the precompile address so that
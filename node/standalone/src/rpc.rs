@@ -12,6 +12,7 @@ use circuit_standalone_runtime::{
 };
 use pallet_3vm_contracts_rpc::{Contracts, ContractsApiServer};
 use pallet_3vm_evm_rpc::{Evm, EvmApiServer};
+use pallet_circuit_rpc::{Circuit, CircuitApiServer};
 use pallet_xdns_rpc::{Xdns, XdnsApiServer};
 use sc_client_api::AuxStore;
 pub use sc_rpc::{DenyUnsafe, SubscriptionTaskExecutor};
@@ -50,6 +51,7 @@ where
     C::Api:
         pallet_3vm_contracts_rpc::ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash>,
     C::Api: pallet_xdns_rpc::XdnsRuntimeApi<Block, AccountId>,
+    C::Api: pallet_circuit_rpc::CircuitRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash>,
     C::Api: pallet_3vm_evm_rpc::EvmRuntimeRPCApi<Block, AccountId, Balance>,
     C::Api: BlockBuilder<Block>,
     P: TransactionPool + Sync + Send + 'static,
@@ -68,6 +70,7 @@ where
     module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
     module.merge(Contracts::new(client.clone()).into_rpc())?;
     module.merge(Xdns::new(client.clone()).into_rpc())?;
+    module.merge(Circuit::new(client.clone()).into_rpc())?;
     module.merge(Evm::new(client).into_rpc())?;
 
     Ok(module)
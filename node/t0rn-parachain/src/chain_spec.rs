@@ -1,5 +1,6 @@
 use circuit_parachain_runtime::{
-    AccountId, AuraId, EvmConfig, Signature, SudoConfig, XDNSConfig, EXISTENTIAL_DEPOSIT,
+    AccountId, AuraId, CircuitConfig, EvmConfig, Signature, SudoConfig, XDNSConfig,
+    EXISTENTIAL_DEPOSIT,
 };
 use cumulus_primitives_core::ParaId;
 use jsonrpc_runtime_client::{
@@ -313,6 +314,11 @@ pub fn development_config() -> ChainSpec {
                 PARACHAIN_ID.into(),
                 // Sudo account
                 get_account_id_from_seed::<sr25519::Public>("Alice"),
+                // Relayers
+                vec![
+                    get_account_id_from_seed::<sr25519::Public>("Alice"),
+                    get_account_id_from_seed::<sr25519::Public>("Bob"),
+                ],
                 vec![],
                 standard_side_effects(),
                 vec![],
@@ -375,6 +381,11 @@ pub fn local_testnet_config() -> ChainSpec {
                 PARACHAIN_ID.into(),
                 // Sudo account
                 get_account_id_from_seed::<sr25519::Public>("Alice"),
+                // Relayers
+                vec![
+                    get_account_id_from_seed::<sr25519::Public>("Alice"),
+                    get_account_id_from_seed::<sr25519::Public>("Bob"),
+                ],
                 vec![],
                 standard_side_effects(),
                 vec![],
@@ -445,6 +456,10 @@ pub fn rococo_config() -> ChainSpec {
                 PARACHAIN_ID.into(),
                 // Sudo
                 get_account_id_from_adrs("5D333eBb5VugHioFoU5nGMbUaR2uYcoyk5qZj9tXRA5ers7A"),
+                // Relayers
+                vec![get_account_id_from_adrs(
+                    "5D333eBb5VugHioFoU5nGMbUaR2uYcoyk5qZj9tXRA5ers7A",
+                )],
                 vec![],
                 standard_side_effects(),
                 vec![],
@@ -481,6 +496,7 @@ fn testnet_genesis(
     endowed_accounts: Vec<AccountId>,
     id: ParaId,
     root_key: AccountId,
+    relayers: Vec<AccountId>,
     xdns_records: Vec<XdnsRecord<AccountId>>,
     standard_side_effects: Vec<SideEffectInterface>,
     _initial_gateways: Vec<InitializationData<Header>>,
@@ -534,8 +550,10 @@ fn testnet_genesis(
             standard_side_effects,
         },
         contracts_registry: Default::default(),
+        circuit: CircuitConfig { relayers },
         account_manager: Default::default(),
         treasury: Default::default(),
+        executors: Default::default(),
         three_vm: Default::default(), // TODO: genesis for this needs to be setup for the function pointers
         evm: EvmConfig {
             // We need _some_ code inserted at the precompile address so that
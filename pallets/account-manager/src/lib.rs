@@ -15,10 +15,10 @@ use frame_support::{
 use sp_runtime::traits::Convert;
 
 use t3rn_primitives::{
-    account_manager::{AccountManager, Outcome},
+    account_manager::{AccountManager, Outcome, SplitPolicy},
     claimable::{BenefitSource, CircuitRole},
     clock::Clock,
-    common::RoundInfo,
+    common::{RoundIndex, RoundInfo},
     executors::Executors,
     transfers::EscrowedBalanceOf,
     EscrowTrait,
@@ -45,7 +45,7 @@ pub mod pallet {
     use super::*;
     use frame_support::{
         pallet_prelude::*,
-        traits::{Currency, ReservableCurrency},
+        traits::{Currency, ReservableCurrency, StorageVersion},
     };
     use frame_system::pallet_prelude::*;
     use t3rn_primitives::account_manager::{ExecutionId, RequestCharge, Settlement};
@@ -69,12 +69,29 @@ pub mod pallet {
 
         #[pallet::constant]
         type EscrowAccount: Get<Self::AccountId>;
+
+        /// Number of rounds after which pending charges are refunded to their payee
+        #[pallet::constant]
+        type ChargeTimeoutRounds: Get<u32>;
+
+        /// Maximum number of pending charges and rounds visited by the stale charges sweep per block
+        #[pallet::constant]
+        type StaleChargesSweepLimit: Get<u32>;
+
+        /// Longest an Xtx can stay pending, in blocks. Charges are only swept as stale once the
+        /// Xtx they were deposited for must have been resolved, however short the rounds are.
+        #[pallet::constant]
+        type XtxTimeoutMax: Get<Self::BlockNumber>;
     }
 
+    /// The in-code storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
     // Simple declaration of the `Pallet` type. It is placeholder we use to implement traits and
     // method.
     #[pallet::pallet]
     #[pallet::generate_store(pub (super) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(PhantomData<T>);
 
@@ -92,6 +109,23 @@ pub mod pallet {
         RequestCharge<T::AccountId, <T::Currency as Currency<T::AccountId>>::Balance>,
     >;
 
+    /// Round each pending charge was deposited in, regardless of the current round
+    #[pallet::storage]
+    #[pallet::getter(fn pending_charge_round)]
+    pub type PendingChargeRounds<T: Config> =
+        StorageMap<_, Identity, T::Hash, RoundInfo<T::BlockNumber>>; // sfx_id
+
+    /// Rounds pending charges were deposited in, by round index
+    #[pallet::storage]
+    #[pallet::getter(fn pending_round)]
+    pub type PendingRounds<T: Config> =
+        StorageMap<_, Identity, RoundIndex, RoundInfo<T::BlockNumber>>;
+
+    /// Index of the oldest round that may still hold stale pending charges
+    #[pallet::storage]
+    #[pallet::getter(fn next_round_to_sweep)]
+    pub type NextRoundToSweep<T: Config> = StorageValue<_, RoundIndex, ValueQuery>;
+
     #[pallet::storage]
     #[pallet::getter(fn settlements_per_round)]
     pub type SettlementsPerRound<T: Config> = StorageDoubleMap<
@@ -103,6 +137,22 @@ pub mod pallet {
         Settlement<T::AccountId, <T::Currency as Currency<T::AccountId>>::Balance>,
     >;
 
+    /// Split policies with the version they were set at, charges without one use the default
+    #[pallet::storage]
+    #[pallet::getter(fn split_policies)]
+    pub type SplitPolicies<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128,
+        BenefitSource,
+        Blake2_128,
+        CircuitRole,
+        (u32, SplitPolicy), // (version, policy)
+    >;
+
+    /// Latest version assigned to a split policy
+    #[pallet::storage]
+    pub type SplitPolicyVersion<T: Config> = StorageValue<_, u32, ValueQuery>;
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         #[pallet::weight(10_000 + T::DbWeight::get().reads(2) + T::DbWeight::get().writes(1))]
@@ -147,6 +197,33 @@ pub mod pallet {
                 maybe_actual_fees,
             )
         }
+
+        #[pallet::weight(10_000 + T::DbWeight::get().reads(1) + T::DbWeight::get().writes(2))]
+        pub fn set_split_policy(
+            origin: OriginFor<T>,
+            source: BenefitSource,
+            role: CircuitRole,
+            policy: SplitPolicy,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            ensure!(policy.is_valid(), Error::<T>::InvalidSplitPolicy);
+
+            let version = <Self as Store>::SplitPolicyVersion::get()
+                .checked_add(1)
+                .ok_or(Error::<T>::ChargeOrSettlementCalculationOverflow)?;
+
+            <Self as Store>::SplitPolicyVersion::put(version);
+            <Self as Store>::SplitPolicies::insert(source.clone(), role.clone(), (version, policy));
+
+            Self::deposit_event(Event::SplitPolicySet {
+                source,
+                role,
+                version,
+            });
+
+            Ok(())
+        }
     }
 
     // Pallet implements [`Hooks`] trait to define some logic to execute in some context.
@@ -163,9 +240,25 @@ pub mod pallet {
         // This function must return the weight consumed by `on_initialize` and `on_finalize`.
         fn on_initialize(_n: T::BlockNumber) -> Weight {
             // TODO: we may want to retry failed transactions here, ensuring a max weight and max retry list
-            // Anything that needs to be done at the start of the block.
-            // We don't do anything here.
-            0
+            T::DbWeight::get().reads(1) + Self::sweep_stale_charges(T::Clock::current_round())
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain_version = StorageVersion::get::<Pallet<T>>();
+            if on_chain_version >= STORAGE_VERSION {
+                return T::DbWeight::get().reads(1)
+            }
+
+            let mut weight = T::DbWeight::get().reads_writes(1, 1);
+            if on_chain_version < 1 {
+                weight = weight.saturating_add(Self::index_pending_charges());
+            }
+            if on_chain_version < 2 {
+                weight = weight.saturating_add(Self::translate_legacy_settlements());
+            }
+            STORAGE_VERSION.put::<Pallet<T>>();
+
+            weight
         }
 
         // A runtime code run after every block and have access to extended set of APIs.
@@ -196,6 +289,15 @@ pub mod pallet {
             recipient: T::AccountId,
             amount: BalanceOf<T>,
         },
+        SplitPolicySet {
+            source: BenefitSource,
+            role: CircuitRole,
+            version: u32,
+        },
+        StaleChargeFinalized {
+            charge_id: T::Hash,
+            payee: T::AccountId,
+        },
     }
 
     #[pallet::error]
@@ -209,6 +311,7 @@ pub mod pallet {
         ChargeAlreadyRegistered,
         ChargeOrSettlementCalculationOverflow,
         DecodingExecutionIDFailed,
+        InvalidSplitPolicy,
     }
 
     #[pallet::genesis_config]
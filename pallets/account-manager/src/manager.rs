@@ -1,15 +1,18 @@
 use crate::{
     AccountManager as AccountManagerExt, BalanceOf, Config, ContractsRegistryExecutionNonce, Error,
-    Outcome, Pallet, PendingChargesPerRound, SettlementsPerRound,
+    NextRoundToSweep, Outcome, Pallet, PendingChargeRounds, PendingChargesPerRound, PendingRounds,
+    SettlementsPerRound, SplitPolicies,
 };
 use codec::{Decode, Encode};
 use frame_support::{
     dispatch::DispatchResult,
+    storage::{PrefixIterator, StoragePrefixedMap},
     traits::{Currency, Get, ReservableCurrency},
+    weights::Weight,
 };
 use sp_runtime::{
-    traits::{CheckedDiv, CheckedMul, Zero},
-    DispatchError,
+    traits::{CheckedDiv, CheckedMul, CheckedSub, Saturating, Zero},
+    DispatchError, Perbill,
 };
 use sp_std::{prelude::*, vec};
 
@@ -23,6 +26,17 @@ use t3rn_primitives::{
 
 use pallet_xbi_portal::sabi::Sabi;
 
+/// Settlement as stored before the split policy version was recorded
+#[derive(Decode)]
+struct LegacySettlement<Account, Balance> {
+    requester: Account,
+    recipient: Account,
+    settlement_amount: Balance,
+    outcome: Outcome,
+    source: BenefitSource,
+    role: CircuitRole,
+}
+
 pub struct ActiveSetClaimablePerRound<Account, Balance> {
     pub executor: Account,
     pub claimable: Balance,
@@ -41,8 +55,8 @@ impl<T: Config> AccountManagerExt<T::AccountId, BalanceOf<T>, T::Hash, T::BlockN
     fn get_charge_or_fail(
         charge_id: T::Hash,
     ) -> Result<RequestCharge<T::AccountId, BalanceOf<T>>, DispatchError> {
-        if let Some(pending_charge) =
-            PendingChargesPerRound::<T>::get(T::Clock::current_round(), charge_id)
+        if let Some(pending_charge) = PendingChargeRounds::<T>::get(charge_id)
+            .and_then(|round| PendingChargesPerRound::<T>::get(round, charge_id))
         {
             Ok(pending_charge)
         } else {
@@ -51,9 +65,7 @@ impl<T: Config> AccountManagerExt<T::AccountId, BalanceOf<T>, T::Hash, T::BlockN
     }
 
     fn no_charge_or_fail(charge_id: T::Hash) -> Result<(), DispatchError> {
-        if let Some(_pending_charge) =
-            PendingChargesPerRound::<T>::get(T::Clock::current_round(), charge_id)
-        {
+        if PendingChargeRounds::<T>::contains_key(charge_id) {
             Err(Error::<T>::ChargeAlreadyRegistered.into())
         } else {
             Ok(())
@@ -103,8 +115,11 @@ impl<T: Config> AccountManagerExt<T::AccountId, BalanceOf<T>, T::Hash, T::BlockN
             T::EscrowAccount::get()
         };
 
+        let round = T::Clock::current_round();
+        PendingRounds::<T>::insert(round.index, round);
+        PendingChargeRounds::<T>::insert(charge_id, round);
         PendingChargesPerRound::<T>::insert(
-            T::Clock::current_round(),
+            round,
             charge_id,
             RequestCharge {
                 payee: payee.clone(),
@@ -126,6 +141,23 @@ impl<T: Config> AccountManagerExt<T::AccountId, BalanceOf<T>, T::Hash, T::BlockN
         Ok(())
     }
 
+    fn reduce_offered_reward(charge_id: T::Hash, offered_reward: BalanceOf<T>) -> DispatchResult {
+        let mut charge = Self::get_charge_or_fail(charge_id)?;
+        let round = PendingChargeRounds::<T>::get(charge_id)
+            .ok_or(Error::<T>::NoChargeOfGivenIdRegistered)?;
+
+        if offered_reward > charge.offered_reward {
+            return Err(Error::<T>::ChargeOrSettlementCalculationOverflow.into())
+        }
+
+        T::Currency::unreserve(&charge.payee, charge.offered_reward - offered_reward);
+        charge.offered_reward = offered_reward;
+
+        PendingChargesPerRound::<T>::insert(round, charge_id, charge);
+
+        Ok(())
+    }
+
     fn finalize(
         charge_id: T::Hash,
         outcome: Outcome,
@@ -133,24 +165,33 @@ impl<T: Config> AccountManagerExt<T::AccountId, BalanceOf<T>, T::Hash, T::BlockN
         maybe_actual_fees: Option<BalanceOf<T>>,
     ) -> DispatchResult {
         let charge = Self::get_charge_or_fail(charge_id)?;
+        let charge_round = PendingChargeRounds::<T>::get(charge_id)
+            .ok_or(Error::<T>::NoChargeOfGivenIdRegistered)?;
 
-        // Decide on charges split
-        // Simple rules for splitting, for now, we take 1% to keep the account manager alive
-        let (payee_split, recipient_split, recipient_bonus): (u8, u8, BalanceOf<T>) = match outcome
-        {
-            Outcome::Commit => (0, 99, charge.offered_reward),
-            Outcome::Revert => (89, 10, Zero::zero()),
-            Outcome::UnexpectedFailure => (49, 50, Zero::zero()),
-        };
+        // Decide on charges split, the default policy takes 1% to keep the account manager alive
+        let (policy_version, policy) =
+            SplitPolicies::<T>::get(&charge.source, &charge.role).unwrap_or_default();
+        let split = policy.split(&outcome);
+        let (payee_split, recipient_split) = (split.payee, split.recipient);
 
         let payee_refund: BalanceOf<T> = if let Some(actual_fees) = maybe_actual_fees {
-            percent_ratio::<T>(charge.charge_fee - actual_fees, payee_split)?
+            let unspent_fees = charge
+                .charge_fee
+                .checked_sub(&actual_fees)
+                .ok_or(Error::<T>::ChargeOrSettlementCalculationOverflow)?;
+            percent_ratio::<T>(unspent_fees, payee_split)?
         } else {
             percent_ratio::<T>(charge.charge_fee, payee_split)?
         };
 
+        // The offered reward only goes to the recipient on Commit, otherwise it's returned to the payee
+        let (recipient_bonus, payee_reward_refund): (BalanceOf<T>, BalanceOf<T>) = match outcome {
+            Outcome::Commit => (charge.offered_reward, Zero::zero()),
+            Outcome::Revert | Outcome::UnexpectedFailure => (Zero::zero(), charge.offered_reward),
+        };
+
         T::Currency::slash_reserved(&charge.payee, charge.charge_fee + charge.offered_reward);
-        T::Currency::deposit_creating(&charge.payee, payee_refund);
+        T::Currency::deposit_creating(&charge.payee, payee_refund + payee_reward_refund);
 
         // Check if recipient has been updated
         let recipient = if let Some(recipient) = maybe_recipient {
@@ -172,15 +213,18 @@ impl<T: Config> AccountManagerExt<T::AccountId, BalanceOf<T>, T::Hash, T::BlockN
                 outcome,
                 source: charge.source,
                 role: charge.role,
+                policy_version,
             },
         );
 
-        PendingChargesPerRound::<T>::remove(T::Clock::current_round(), charge_id);
+        PendingChargesPerRound::<T>::remove(charge_round, charge_id);
+        PendingChargeRounds::<T>::remove(charge_id);
 
-        // Take what's left - 1% to keep the account manager alive
+        // Take what's left - the escrow split to keep the account manager alive,
+        //  together with the settlement held in the escrow until it's claimed
         T::Currency::deposit_creating(
             &T::EscrowAccount::get(),
-            charge.charge_fee - recipient_fee_rewards - payee_refund,
+            charge.charge_fee - payee_refund + recipient_bonus,
         );
 
         Ok(())
@@ -192,17 +236,23 @@ impl<T: Config> AccountManagerExt<T::AccountId, BalanceOf<T>, T::Hash, T::BlockN
         maybe_recipient: Option<T::AccountId>,
         maybe_actual_fees: Option<BalanceOf<T>>,
     ) {
-        if PendingChargesPerRound::<T>::get(T::Clock::current_round(), charge_id).is_some() {
-            <Self as AccountManagerExt<T::AccountId, BalanceOf<T>, T::Hash, T::BlockNumber>>::finalize(
-                charge_id,
-                outcome,
-                maybe_recipient,
-                maybe_actual_fees
-            ).expect("Expect try finalize to be infallible");
+        if PendingChargeRounds::<T>::contains_key(charge_id) {
+            if let Err(err) = <Self as AccountManagerExt<
+                T::AccountId,
+                BalanceOf<T>,
+                T::Hash,
+                T::BlockNumber,
+            >>::finalize(
+                charge_id, outcome, maybe_recipient, maybe_actual_fees
+            ) {
+                log::error!("failed to finalize charge {:?} -- {:?}", charge_id, err);
+            }
         }
     }
 
-    /// Collect claimable (only SFX execution rewards) for Executors and Stakers submitted by Circuit at the duration of the current Round
+    /// Collect claimable for all roles from the settlements submitted by Circuit at the duration
+    /// of the current Round. Settlements of active executors are shared with their stakers pro rata
+    /// to the executor's own collateral and the stake nominated to it, the rest go to their recipient.
     fn on_collect_claimable(
         _n: T::BlockNumber,
         r: RoundInfo<T::BlockNumber>,
@@ -218,11 +268,24 @@ impl<T: Config> AccountManagerExt<T::AccountId, BalanceOf<T>, T::Hash, T::BlockN
                 .collect::<Vec<ActiveSetClaimablePerRound<T::AccountId, BalanceOf<T>>>>();
 
         for settlement in SettlementsPerRound::<T>::iter_prefix_values(r) {
-            // fixme: test that actually updates active_set_claimables or are the references wrong
-            for active_set_claimable in active_set_claimables.iter_mut() {
-                if active_set_claimable.executor == settlement.recipient {
-                    active_set_claimable.claimable += settlement.settlement_amount;
-                }
+            let maybe_active_set_claimable = match settlement.role {
+                CircuitRole::Executor => active_set_claimables
+                    .iter_mut()
+                    .find(|claimable| claimable.executor == settlement.recipient),
+                _ => None,
+            };
+
+            match maybe_active_set_claimable {
+                Some(active_set_claimable) =>
+                    active_set_claimable.claimable = active_set_claimable
+                        .claimable
+                        .saturating_add(settlement.settlement_amount),
+                None => claimable_artifacts.push(ClaimableArtifacts {
+                    beneficiary: settlement.recipient,
+                    role: settlement.role,
+                    total_round_claim: settlement.settlement_amount,
+                    benefit_source: settlement.source,
+                }),
             }
         }
 
@@ -230,28 +293,38 @@ impl<T: Config> AccountManagerExt<T::AccountId, BalanceOf<T>, T::Hash, T::BlockN
             let collateral_bond = T::Executors::collateral_bond(&active_set_claimable.executor);
             let nominated_stake =
                 T::Executors::total_nominated_stake(&active_set_claimable.executor);
-            // calculate % ratio of rewards proportionally to Executor's own Collateral to Nominated Stake
-            let total_stake_power = collateral_bond + nominated_stake;
-
-            // todo: ensure it's in range (0,1>
-            let collateral_bond_power = collateral_bond / total_stake_power;
+            let total_stake_power = collateral_bond.saturating_add(nominated_stake);
+
+            // Executors without any stake keep the whole claim
+            let collateral_bond_power = if total_stake_power.is_zero() {
+                Perbill::from_percent(100)
+            } else {
+                Perbill::from_rational(collateral_bond, total_stake_power)
+            };
+            let claimable_by_executor = collateral_bond_power * active_set_claimable.claimable;
+            let claimable_by_all_stakers_of_executor = active_set_claimable
+                .claimable
+                .saturating_sub(claimable_by_executor);
 
             claimable_artifacts.push(ClaimableArtifacts {
                 beneficiary: active_set_claimable.executor.clone(),
                 role: CircuitRole::Executor,
-                total_round_claim: collateral_bond_power * active_set_claimable.claimable,
+                total_round_claim: claimable_by_executor,
                 benefit_source: BenefitSource::TrafficRewards,
             });
 
-            // todo: ensure it's in range <0,1)
-            let nominated_stake_power = nominated_stake / total_stake_power;
+            let stakes = T::Executors::stakes_per_executor(&active_set_claimable.executor);
+            let total_staked = stakes.iter().fold(BalanceOf::<T>::zero(), |total, stake| {
+                total.saturating_add(stake.nominated_stake)
+            });
 
-            let claimable_by_all_stakers_of_executor =
-                nominated_stake_power * active_set_claimable.claimable;
+            if total_staked.is_zero() {
+                continue
+            }
 
-            for nominated_stake in T::Executors::stakes_per_executor(&active_set_claimable.executor)
-            {
-                let staker_power = nominated_stake.nominated_stake / nominated_stake_power;
+            for nominated_stake in stakes {
+                let staker_power =
+                    Perbill::from_rational(nominated_stake.nominated_stake, total_staked);
                 claimable_artifacts.push(ClaimableArtifacts {
                     beneficiary: nominated_stake.staker,
                     role: CircuitRole::Staker,
@@ -265,15 +338,157 @@ impl<T: Config> AccountManagerExt<T::AccountId, BalanceOf<T>, T::Hash, T::BlockN
     }
 }
 
+impl<T: Config> Pallet<T> {
+    /// Refunds the pending charges deposited at least `ChargeTimeoutRounds` and `XtxTimeoutMax`
+    /// blocks ago to their payee, releasing the reserve of charges that were never resolved.
+    /// Rounds are swept oldest first, visiting at most `StaleChargesSweepLimit` rounds and charges
+    /// per block, so a backlog of stale charges is worked off over the following blocks.
+    pub fn sweep_stale_charges(current_round: RoundInfo<T::BlockNumber>) -> Weight {
+        let mut budget = T::StaleChargesSweepLimit::get();
+        let mut next_round = NextRoundToSweep::<T>::get();
+        let mut weight = T::DbWeight::get().reads_writes(1, 1);
+
+        while budget > 0
+            && next_round.saturating_add(T::ChargeTimeoutRounds::get()) <= current_round.index
+        {
+            budget -= 1;
+            weight = weight.saturating_add(T::DbWeight::get().reads(1));
+
+            let round = match PendingRounds::<T>::get(next_round) {
+                Some(round) => round,
+                None => {
+                    next_round = next_round.saturating_add(1);
+                    continue
+                },
+            };
+
+            // The Xtx of charges deposited during the round may still be pending
+            if round
+                .head
+                .saturating_add(round.term.into())
+                .saturating_add(T::XtxTimeoutMax::get())
+                > current_round.head
+            {
+                break
+            }
+
+            let stale_charges = PendingChargesPerRound::<T>::iter_prefix(round)
+                .take(budget as usize)
+                .collect::<Vec<_>>();
+
+            budget = budget.saturating_sub(stale_charges.len() as u32);
+            weight = weight.saturating_add(T::DbWeight::get().reads_writes(
+                stale_charges.len() as Weight,
+                3 * stale_charges.len() as Weight,
+            ));
+
+            for (charge_id, charge) in stale_charges {
+                T::Currency::unreserve(
+                    &charge.payee,
+                    charge.charge_fee.saturating_add(charge.offered_reward),
+                );
+                PendingChargesPerRound::<T>::remove(round, charge_id);
+                PendingChargeRounds::<T>::remove(charge_id);
+
+                Self::deposit_event(crate::Event::StaleChargeFinalized {
+                    charge_id,
+                    payee: charge.payee,
+                });
+            }
+
+            weight = weight.saturating_add(T::DbWeight::get().reads(1));
+            if PendingChargesPerRound::<T>::iter_prefix(round)
+                .next()
+                .is_some()
+            {
+                break
+            }
+
+            PendingRounds::<T>::remove(next_round);
+            weight = weight.saturating_add(T::DbWeight::get().writes(1));
+            next_round = next_round.saturating_add(1);
+        }
+
+        NextRoundToSweep::<T>::put(next_round);
+
+        weight
+    }
+
+    /// Re-indexes the pending charges deposited before their rounds were tracked under the current
+    /// round, so they can be found by their id and are swept once stale.
+    pub fn index_pending_charges() -> Weight {
+        let round = T::Clock::current_round();
+        let prefix = PendingChargesPerRound::<T>::final_prefix().to_vec();
+
+        let charges = PrefixIterator::<(T::Hash, RequestCharge<T::AccountId, BalanceOf<T>>)>::new(
+            prefix.clone(),
+            prefix,
+            |raw_key, raw_value| {
+                // The round is hashed with Blake2_128 and the charge id is kept as is
+                let mut raw_charge_id = raw_key.get(16..).ok_or("pending charge key too short")?;
+                Ok((
+                    T::Hash::decode(&mut raw_charge_id)?,
+                    RequestCharge::decode(&mut &raw_value[..])?,
+                ))
+            },
+        )
+        .drain()
+        .collect::<Vec<_>>();
+
+        for (charge_id, charge) in charges.iter() {
+            PendingChargeRounds::<T>::insert(charge_id, round);
+            PendingChargesPerRound::<T>::insert(round, charge_id, charge);
+        }
+
+        if !charges.is_empty() {
+            PendingRounds::<T>::insert(round.index, round);
+        }
+        NextRoundToSweep::<T>::put(round.index);
+
+        T::DbWeight::get()
+            .reads_writes(charges.len() as Weight + 1, 3 * charges.len() as Weight + 2)
+    }
+
+    /// Translates the settlements stored before the split policy version was recorded, as
+    /// calculated with the default policy.
+    pub fn translate_legacy_settlements() -> Weight {
+        let mut translated: Weight = 0;
+
+        // Rounds are hashed with Blake2_128, so the settlements are translated by value only
+        SettlementsPerRound::<T>::translate_values(
+            |legacy: LegacySettlement<T::AccountId, BalanceOf<T>>| {
+                translated = translated.saturating_add(1);
+                Some(Settlement {
+                    requester: legacy.requester,
+                    recipient: legacy.recipient,
+                    settlement_amount: legacy.settlement_amount,
+                    outcome: legacy.outcome,
+                    source: legacy.source,
+                    role: legacy.role,
+                    policy_version: 0,
+                })
+            },
+        );
+
+        T::DbWeight::get().reads_writes(translated, translated)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use circuit_mock_runtime::*;
 
-    use frame_support::{assert_err, assert_ok};
+    use frame_support::{
+        assert_err, assert_ok,
+        traits::{Hooks, StorageVersion},
+    };
 
     use sp_core::H256;
-    use t3rn_primitives::common::RoundInfo;
+    use t3rn_primitives::{
+        account_manager::{OutcomeSplit, SplitPolicy},
+        common::RoundInfo,
+    };
 
     const DEFAULT_BALANCE: Balance = 1_000_000;
 
@@ -397,7 +612,8 @@ mod tests {
                 Balances::free_balance(
                     &<Runtime as pallet_account_manager::Config>::EscrowAccount::get()
                 ),
-                one_percent_charge_amt + DEFAULT_BALANCE // 1% left now
+                // 1% left now, next to the 10% settlement held until claimed
+                one_percent_charge_amt + ten_percent_charge_amt + DEFAULT_BALANCE
             );
 
             assert_eq!(
@@ -425,6 +641,63 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_finalize_revert_refunds_offered_reward() {
+        ExtBuilder::default().build().execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, DEFAULT_BALANCE);
+            let _ = Balances::deposit_creating(
+                &<Runtime as pallet_account_manager::Config>::EscrowAccount::get(),
+                DEFAULT_BALANCE,
+            );
+            let charge_amt = 100;
+            let reward_amt = 50;
+            let execution_id: H256 = H256::repeat_byte(0);
+
+            assert_ok!(<AccountManager as AccountManagerExt<
+                AccountId,
+                Balance,
+                Hash,
+                BlockNumber,
+            >>::deposit(
+                execution_id,
+                &ALICE,
+                charge_amt,
+                reward_amt,
+                BenefitSource::TrafficRewards,
+                CircuitRole::ContractAuthor,
+                Some(BOB),
+            ));
+
+            assert_eq!(Balances::reserved_balance(&ALICE), charge_amt + reward_amt);
+
+            assert_ok!(<AccountManager as AccountManagerExt<
+                AccountId,
+                Balance,
+                Hash,
+                BlockNumber,
+            >>::finalize(
+                execution_id, Outcome::Revert, None, None,
+            ));
+
+            let one_percent_charge_amt = charge_amt / 100;
+            let ten_percent_charge_amt = charge_amt / 10;
+
+            assert_eq!(Balances::reserved_balance(&ALICE), 0);
+            assert_eq!(
+                Balances::free_balance(&ALICE),
+                DEFAULT_BALANCE - ten_percent_charge_amt - one_percent_charge_amt
+            );
+
+            let settlement = AccountManager::settlements_per_round::<RoundInfo<BlockNumber>, H256>(
+                Default::default(),
+                execution_id,
+            )
+            .unwrap();
+
+            assert_eq!(settlement.settlement_amount, ten_percent_charge_amt);
+        });
+    }
+
     #[test]
     fn test_finalize_commit_works() {
         ExtBuilder::default().build().execute_with(|| {
@@ -468,7 +741,8 @@ mod tests {
                 Balances::free_balance(
                     &<Runtime as pallet_account_manager::Config>::EscrowAccount::get()
                 ),
-                one_percent_charge_amt + DEFAULT_BALANCE // 1% left now
+                // 1% left now, next to the 99% settlement held until claimed
+                charge_amt + DEFAULT_BALANCE
             );
             assert_eq!(Balances::free_balance(&ALICE), DEFAULT_BALANCE - charge_amt);
 
@@ -540,7 +814,8 @@ mod tests {
                 Balances::free_balance(
                     &<Runtime as pallet_account_manager::Config>::EscrowAccount::get()
                 ),
-                one_percent_charge_amt + DEFAULT_BALANCE // 1% left now
+                // 1% left now, next to the 50% settlement held until claimed
+                one_percent_charge_amt + fifty_percent_charge_amt + DEFAULT_BALANCE
             );
 
             assert_eq!(
@@ -567,4 +842,389 @@ mod tests {
             assert_eq!(settlement.settlement_amount, fifty_percent_charge_amt);
         });
     }
+
+    #[test]
+    fn test_finalize_follows_split_policy_set_by_root() {
+        ExtBuilder::default().build().execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, DEFAULT_BALANCE);
+            let _ = Balances::deposit_creating(
+                &<Runtime as pallet_account_manager::Config>::EscrowAccount::get(),
+                DEFAULT_BALANCE,
+            );
+            let charge_amt = 100;
+            let execution_id: H256 = H256::repeat_byte(0);
+
+            let mut policy = SplitPolicy::default();
+            policy.revert = OutcomeSplit {
+                payee: 70,
+                recipient: 25,
+                escrow: 5,
+            };
+
+            assert_ok!(AccountManager::set_split_policy(
+                Origin::root(),
+                BenefitSource::TrafficRewards,
+                CircuitRole::ContractAuthor,
+                policy.clone(),
+            ));
+            assert_eq!(
+                AccountManager::split_policies(
+                    BenefitSource::TrafficRewards,
+                    CircuitRole::ContractAuthor
+                ),
+                Some((1, policy))
+            );
+
+            assert_ok!(<AccountManager as AccountManagerExt<
+                AccountId,
+                Balance,
+                Hash,
+                BlockNumber,
+            >>::deposit(
+                execution_id,
+                &ALICE,
+                charge_amt,
+                0,
+                BenefitSource::TrafficRewards,
+                CircuitRole::ContractAuthor,
+                Some(BOB),
+            ));
+
+            assert_ok!(<AccountManager as AccountManagerExt<
+                AccountId,
+                Balance,
+                Hash,
+                BlockNumber,
+            >>::finalize(
+                execution_id, Outcome::Revert, None, None,
+            ));
+
+            assert_eq!(Balances::free_balance(&ALICE), DEFAULT_BALANCE - 30);
+            assert_eq!(
+                Balances::free_balance(
+                    &<Runtime as pallet_account_manager::Config>::EscrowAccount::get()
+                ),
+                DEFAULT_BALANCE + 30
+            );
+
+            let settlement = AccountManager::settlements_per_round::<RoundInfo<BlockNumber>, H256>(
+                Default::default(),
+                execution_id,
+            )
+            .unwrap();
+
+            assert_eq!(settlement.settlement_amount, 25);
+            assert_eq!(settlement.policy_version, 1);
+        });
+    }
+
+    #[test]
+    fn test_set_split_policy_rejects_shares_not_summing_to_hundred() {
+        ExtBuilder::default().build().execute_with(|| {
+            let mut policy = SplitPolicy::default();
+            policy.commit.escrow = 2;
+
+            assert_err!(
+                AccountManager::set_split_policy(
+                    Origin::root(),
+                    BenefitSource::TrafficRewards,
+                    CircuitRole::Executor,
+                    policy,
+                ),
+                pallet_account_manager::Error::<Runtime>::InvalidSplitPolicy
+            );
+            assert_err!(
+                AccountManager::set_split_policy(
+                    Origin::signed(ALICE),
+                    BenefitSource::TrafficRewards,
+                    CircuitRole::Executor,
+                    SplitPolicy::default(),
+                ),
+                DispatchError::BadOrigin
+            );
+            assert_eq!(
+                AccountManager::split_policies(
+                    BenefitSource::TrafficRewards,
+                    CircuitRole::Executor
+                ),
+                None
+            );
+        });
+    }
+
+    #[test]
+    fn test_finalize_resolves_charge_deposited_in_previous_round() {
+        ExtBuilder::default().build().execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, DEFAULT_BALANCE);
+            let charge_amt = 100;
+            let execution_id: H256 = H256::repeat_byte(0);
+
+            assert_ok!(<AccountManager as AccountManagerExt<
+                AccountId,
+                Balance,
+                Hash,
+                BlockNumber,
+            >>::deposit(
+                execution_id,
+                &ALICE,
+                charge_amt,
+                0,
+                BenefitSource::TrafficRewards,
+                CircuitRole::ContractAuthor,
+                Some(BOB),
+            ));
+
+            let next_round = RoundInfo::new(2, 21, 20);
+            pallet_treasury::CurrentRound::<Runtime>::put(next_round);
+
+            assert_ok!(<AccountManager as AccountManagerExt<
+                AccountId,
+                Balance,
+                Hash,
+                BlockNumber,
+            >>::finalize(
+                execution_id, Outcome::Commit, None, None,
+            ));
+
+            assert_eq!(Balances::reserved_balance(&ALICE), 0);
+            assert_eq!(AccountManager::pending_charge_round(execution_id), None);
+            assert_eq!(
+                AccountManager::pending_charges_per_round::<RoundInfo<BlockNumber>, H256>(
+                    Default::default(),
+                    execution_id,
+                ),
+                None
+            );
+            assert!(
+                AccountManager::settlements_per_round::<RoundInfo<BlockNumber>, H256>(
+                    next_round,
+                    execution_id,
+                )
+                .is_some()
+            );
+        });
+    }
+
+    #[test]
+    fn test_on_initialize_finalizes_stale_charges_as_reverted() {
+        ExtBuilder::default().build().execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, DEFAULT_BALANCE);
+            let charge_amt = 100;
+            let stale_id: H256 = H256::repeat_byte(0);
+            let fresh_id: H256 = H256::repeat_byte(1);
+
+            let deposit = |execution_id: H256| {
+                <AccountManager as AccountManagerExt<
+                    AccountId,
+                    Balance,
+                    Hash,
+                    BlockNumber,
+                >>::deposit(
+                    execution_id,
+                    &ALICE,
+                    charge_amt,
+                    0,
+                    BenefitSource::TrafficRewards,
+                    CircuitRole::ContractAuthor,
+                    Some(BOB),
+                )
+            };
+
+            assert_ok!(deposit(stale_id));
+            pallet_treasury::CurrentRound::<Runtime>::put(RoundInfo::new(2, 21, 20));
+            assert_ok!(deposit(fresh_id));
+
+            let timeout = <Runtime as pallet_account_manager::Config>::ChargeTimeoutRounds::get();
+            // The Xtx of the stale charge timed out by the start of the current round
+            let stale_round = AccountManager::pending_round(1).unwrap();
+            let head = stale_round.head
+                + stale_round.term
+                + <Runtime as pallet_account_manager::Config>::XtxTimeoutMax::get();
+            pallet_treasury::CurrentRound::<Runtime>::put(RoundInfo::new(1 + timeout, head, 20));
+            AccountManager::on_initialize(head);
+
+            assert_eq!(AccountManager::pending_charge_round(stale_id), None);
+            assert!(AccountManager::pending_charge_round(fresh_id).is_some());
+            assert_eq!(AccountManager::next_round_to_sweep(), 2);
+            assert!(AccountManager::pending_round(2).is_some());
+
+            // The stale charge is refunded in full, without settling anything to the recipient
+            assert_eq!(Balances::reserved_balance(&ALICE), charge_amt);
+            assert_eq!(Balances::free_balance(&ALICE), DEFAULT_BALANCE - charge_amt);
+            assert_eq!(
+                SettlementsPerRound::<Runtime>::iter_prefix_values(RoundInfo::new(
+                    1 + timeout,
+                    head,
+                    20
+                ))
+                .count(),
+                0
+            );
+        });
+    }
+
+    #[test]
+    fn test_on_initialize_sweeps_stale_charges_up_to_the_limit_per_block() {
+        ExtBuilder::default().build().execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, DEFAULT_BALANCE);
+            let limit = <Runtime as pallet_account_manager::Config>::StaleChargesSweepLimit::get();
+            let charges = limit as u8 + 1;
+
+            pallet_treasury::CurrentRound::<Runtime>::put(RoundInfo::new(1, 1, 20));
+            for id in 0..charges {
+                assert_ok!(<AccountManager as AccountManagerExt<
+                    AccountId,
+                    Balance,
+                    Hash,
+                    BlockNumber,
+                >>::deposit(
+                    H256::repeat_byte(id),
+                    &ALICE,
+                    1,
+                    0,
+                    BenefitSource::TrafficRewards,
+                    CircuitRole::ContractAuthor,
+                    Some(BOB),
+                ));
+            }
+
+            let timeout = <Runtime as pallet_account_manager::Config>::ChargeTimeoutRounds::get();
+            let xtx_timeout_max = <Runtime as pallet_account_manager::Config>::XtxTimeoutMax::get();
+            let head = 21 + xtx_timeout_max;
+            pallet_treasury::CurrentRound::<Runtime>::put(RoundInfo::new(1 + timeout, head, 20));
+
+            AccountManager::on_initialize(head);
+
+            // Visiting the empty round 0 and the stale round 1 takes from the budget
+            assert_eq!(Balances::reserved_balance(&ALICE), 3);
+            assert_eq!(AccountManager::next_round_to_sweep(), 1);
+
+            AccountManager::on_initialize(head + 1);
+
+            assert_eq!(Balances::reserved_balance(&ALICE), 0);
+            assert_eq!(AccountManager::next_round_to_sweep(), 2);
+            assert_eq!(AccountManager::pending_round(1), None);
+        });
+    }
+
+    #[test]
+    fn test_on_initialize_keeps_charges_of_xtx_which_may_still_be_pending() {
+        ExtBuilder::default().build().execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, DEFAULT_BALANCE);
+            let charge_id: H256 = H256::repeat_byte(0);
+
+            pallet_treasury::CurrentRound::<Runtime>::put(RoundInfo::new(1, 1, 20));
+            assert_ok!(<AccountManager as AccountManagerExt<
+                AccountId,
+                Balance,
+                Hash,
+                BlockNumber,
+            >>::deposit(
+                charge_id,
+                &ALICE,
+                100,
+                0,
+                BenefitSource::TrafficRewards,
+                CircuitRole::ContractAuthor,
+                Some(BOB),
+            ));
+
+            // Enough rounds passed, but they were too short for the Xtx to time out
+            let timeout = <Runtime as pallet_account_manager::Config>::ChargeTimeoutRounds::get();
+            pallet_treasury::CurrentRound::<Runtime>::put(RoundInfo::new(1 + timeout, 201, 20));
+            AccountManager::on_initialize(201);
+
+            assert!(AccountManager::pending_charge_round(charge_id).is_some());
+            assert_eq!(Balances::reserved_balance(&ALICE), 100);
+            assert_eq!(AccountManager::next_round_to_sweep(), 1);
+        });
+    }
+
+    #[test]
+    fn test_runtime_upgrade_indexes_pending_charges_under_the_current_round() {
+        ExtBuilder::default().build().execute_with(|| {
+            let charge_id: H256 = H256::repeat_byte(7);
+            let current_round = RoundInfo::new(5, 81, 20);
+
+            PendingChargesPerRound::<Runtime>::insert(
+                RoundInfo::new(3, 41, 20),
+                charge_id,
+                RequestCharge {
+                    payee: ALICE,
+                    offered_reward: 0,
+                    charge_fee: 100,
+                    recipient: BOB,
+                    source: BenefitSource::TrafficRewards,
+                    role: CircuitRole::ContractAuthor,
+                },
+            );
+            pallet_treasury::CurrentRound::<Runtime>::put(current_round);
+            StorageVersion::new(0).put::<AccountManager>();
+
+            AccountManager::on_runtime_upgrade();
+
+            assert_eq!(
+                AccountManager::pending_charge_round(charge_id),
+                Some(current_round)
+            );
+            assert_eq!(AccountManager::pending_round(5), Some(current_round));
+            assert_eq!(AccountManager::next_round_to_sweep(), 5);
+            assert_eq!(
+                PendingChargesPerRound::<Runtime>::iter_prefix(RoundInfo::new(3, 41, 20)).count(),
+                0
+            );
+            assert_ok!(<AccountManager as AccountManagerExt<
+                AccountId,
+                Balance,
+                Hash,
+                BlockNumber,
+            >>::get_charge_or_fail(charge_id));
+            assert_eq!(
+                StorageVersion::get::<AccountManager>(),
+                StorageVersion::new(2)
+            );
+        });
+    }
+
+    #[test]
+    fn test_runtime_upgrade_translates_legacy_settlements_to_the_default_policy_version() {
+        ExtBuilder::default().build().execute_with(|| {
+            let charge_id: H256 = H256::repeat_byte(7);
+            let round = RoundInfo::new(3, 41, 20);
+
+            // Settlements stored before the split policy version was recorded
+            frame_support::storage::unhashed::put_raw(
+                &SettlementsPerRound::<Runtime>::hashed_key_for(round, charge_id),
+                &(
+                    ALICE,
+                    BOB,
+                    100 as Balance,
+                    Outcome::Commit,
+                    BenefitSource::TrafficRewards,
+                    CircuitRole::ContractAuthor,
+                )
+                    .encode(),
+            );
+            StorageVersion::new(1).put::<AccountManager>();
+
+            AccountManager::on_runtime_upgrade();
+
+            assert_eq!(
+                AccountManager::settlements_per_round(round, charge_id),
+                Some(Settlement {
+                    requester: ALICE,
+                    recipient: BOB,
+                    settlement_amount: 100,
+                    outcome: Outcome::Commit,
+                    source: BenefitSource::TrafficRewards,
+                    role: CircuitRole::ContractAuthor,
+                    policy_version: 0,
+                })
+            );
+            assert_eq!(
+                StorageVersion::get::<AccountManager>(),
+                StorageVersion::new(2)
+            );
+        });
+    }
 }
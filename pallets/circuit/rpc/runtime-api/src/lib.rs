@@ -0,0 +1,47 @@
+//! Runtime API definition required by Circuit RPC extensions.
+//!
+//! This API should be imported and implemented by the runtime,
+//! of a node that wants to use the custom RPC extension
+//! adding Circuit access methods.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+pub use pallet_circuit::state::{CircuitStatus, InsuranceDeposit, OpenSideEffect, XExecSignal};
+use sp_std::vec::Vec;
+pub use t3rn_primitives::{side_effect::FullSideEffect, ChainId};
+
+sp_api::decl_runtime_apis! {
+    /// The API to query Xtx and side effects of pallet Circuit
+    pub trait CircuitRuntimeApi<AccountId, Balance, BlockNumber, Hash> where
+        AccountId: Codec,
+        Balance: Codec,
+        BlockNumber: Codec,
+        Hash: Codec,
+    {
+        /// Returns the Xtx with its status and step counter
+        fn get_xtx(xtx_id: Hash) -> Option<XExecSignal<AccountId, BlockNumber, Balance>>;
+
+        /// Returns the full side effects of the Xtx per step, with their confirmations
+        fn get_full_side_effects(
+            xtx_id: Hash,
+        ) -> Option<Vec<Vec<FullSideEffect<AccountId, BlockNumber, Balance>>>>;
+
+        /// Returns the insurance deposits of Xtx that haven't finished all of the steps yet
+        fn get_open_insurance_deposits(
+        ) -> Vec<(Hash, Hash, InsuranceDeposit<AccountId, BlockNumber, Balance>)>;
+
+        /// Returns side effects of the kind on the target gateway open for executors to bond
+        fn get_open_side_effects(
+            target: ChainId,
+            kind: [u8; 4],
+        ) -> Vec<(Hash, OpenSideEffect<AccountId, Hash, BlockNumber, Balance>)>;
+
+        /// Returns a page of Xtx requested by the account
+        fn get_xtx_by_requester(
+            requester: AccountId,
+            offset: u32,
+            limit: u32,
+        ) -> Vec<(Hash, XExecSignal<AccountId, BlockNumber, Balance>)>;
+    }
+}
@@ -0,0 +1,187 @@
+//! RPC interface for the Circuit pallet.
+
+use codec::Codec;
+use jsonrpsee::{
+    core::{async_trait, Error as JsonRpseeError, RpcResult},
+    proc_macros::rpc,
+    types::error::CallError,
+};
+pub use pallet_circuit_rpc_runtime_api::CircuitRuntimeApi;
+use pallet_circuit_rpc_runtime_api::{
+    ChainId, FullSideEffect, InsuranceDeposit, OpenSideEffect, XExecSignal,
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+    generic::BlockId,
+    traits::{Block as BlockT, MaybeDisplay},
+};
+use std::sync::Arc;
+
+const RUNTIME_ERROR: i64 = 1;
+
+/// The largest page of Xtx returned for a requester at once
+const MAX_XTX_PAGE_SIZE: u32 = 100;
+
+#[rpc(client, server)]
+pub trait CircuitApi<AccountId, Balance, BlockNumber, Hash> {
+    /// Returns the Xtx with its status and step counter
+    #[method(name = "circuit_getXtx")]
+    fn get_xtx(&self, xtx_id: Hash) -> RpcResult<XExecSignal<AccountId, BlockNumber, Balance>>;
+
+    /// Returns the full side effects of the Xtx per step, with their confirmations
+    #[method(name = "circuit_getFullSideEffects")]
+    fn get_full_side_effects(
+        &self,
+        xtx_id: Hash,
+    ) -> RpcResult<Vec<Vec<FullSideEffect<AccountId, BlockNumber, Balance>>>>;
+
+    /// Returns the insurance deposits of Xtx that haven't finished all of the steps yet
+    #[method(name = "circuit_getOpenInsuranceDeposits")]
+    fn get_open_insurance_deposits(
+        &self,
+    ) -> RpcResult<
+        Vec<(
+            Hash,
+            Hash,
+            InsuranceDeposit<AccountId, BlockNumber, Balance>,
+        )>,
+    >;
+
+    /// Returns side effects of the kind on the target gateway open for executors to bond
+    #[method(name = "circuit_getOpenSideEffects")]
+    fn get_open_side_effects(
+        &self,
+        target: ChainId,
+        kind: [u8; 4],
+    ) -> RpcResult<Vec<(Hash, OpenSideEffect<AccountId, Hash, BlockNumber, Balance>)>>;
+
+    /// Returns a page of at most 100 Xtx requested by the account
+    #[method(name = "circuit_getXtxByRequester")]
+    fn get_xtx_by_requester(
+        &self,
+        requester: AccountId,
+        offset: u32,
+        limit: u32,
+    ) -> RpcResult<Vec<(Hash, XExecSignal<AccountId, BlockNumber, Balance>)>>;
+}
+
+/// A struct that implements the [`CircuitApiServer`].
+pub struct Circuit<C, P> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> Circuit<C, P> {
+    /// Create new `Circuit` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl<C, Block, AccountId, Balance, BlockNumber, Hash>
+    CircuitApiServer<AccountId, Balance, BlockNumber, Hash> for Circuit<C, Block>
+where
+    AccountId: Codec + MaybeDisplay,
+    Balance: Codec + MaybeDisplay,
+    BlockNumber: Codec + MaybeDisplay,
+    Hash: Codec + MaybeDisplay,
+    Block: BlockT,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: CircuitRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash>,
+{
+    fn get_xtx(&self, xtx_id: Hash) -> RpcResult<XExecSignal<AccountId, BlockNumber, Balance>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(self.client.info().best_hash);
+
+        let result: Option<XExecSignal<AccountId, BlockNumber, Balance>> = api
+            .get_xtx(&at, xtx_id)
+            .map_err(runtime_error_into_rpc_err)?;
+
+        match result {
+            Some(xtx) => Ok(xtx),
+            None => Err("Xtx doesn't exist"),
+        }
+        .map_err(runtime_error_into_rpc_err)
+    }
+
+    fn get_full_side_effects(
+        &self,
+        xtx_id: Hash,
+    ) -> RpcResult<Vec<Vec<FullSideEffect<AccountId, BlockNumber, Balance>>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(self.client.info().best_hash);
+
+        let result: Option<Vec<Vec<FullSideEffect<AccountId, BlockNumber, Balance>>>> = api
+            .get_full_side_effects(&at, xtx_id)
+            .map_err(runtime_error_into_rpc_err)?;
+
+        match result {
+            Some(full_side_effects) => Ok(full_side_effects),
+            None => Err("Xtx doesn't exist"),
+        }
+        .map_err(runtime_error_into_rpc_err)
+    }
+
+    fn get_open_insurance_deposits(
+        &self,
+    ) -> RpcResult<
+        Vec<(
+            Hash,
+            Hash,
+            InsuranceDeposit<AccountId, BlockNumber, Balance>,
+        )>,
+    > {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(self.client.info().best_hash);
+
+        let result = api
+            .get_open_insurance_deposits(&at)
+            .map_err(runtime_error_into_rpc_err)?;
+
+        Ok(result)
+    }
+
+    fn get_open_side_effects(
+        &self,
+        target: ChainId,
+        kind: [u8; 4],
+    ) -> RpcResult<Vec<(Hash, OpenSideEffect<AccountId, Hash, BlockNumber, Balance>)>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(self.client.info().best_hash);
+
+        let result = api
+            .get_open_side_effects(&at, target, kind)
+            .map_err(runtime_error_into_rpc_err)?;
+
+        Ok(result)
+    }
+
+    fn get_xtx_by_requester(
+        &self,
+        requester: AccountId,
+        offset: u32,
+        limit: u32,
+    ) -> RpcResult<Vec<(Hash, XExecSignal<AccountId, BlockNumber, Balance>)>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(self.client.info().best_hash);
+
+        let result = api
+            .get_xtx_by_requester(&at, requester, offset, limit.min(MAX_XTX_PAGE_SIZE))
+            .map_err(runtime_error_into_rpc_err)?;
+
+        Ok(result)
+    }
+}
+
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> JsonRpseeError {
+    JsonRpseeError::Call(CallError::Custom(jsonrpsee::types::ErrorObject::owned(
+        RUNTIME_ERROR as i32,
+        "Runtime Error - Circuit RPC",
+        Some(format!("{:?}", err)),
+    )))
+}
@@ -1,7 +1,9 @@
 use crate::{pallet::Error, *};
 
 use codec::Decode;
+use frame_support::storage::with_storage_layer;
 
+use sp_runtime::SaturatedConversion;
 use sp_std::marker::PhantomData;
 use t3rn_primitives::transfers::EscrowedBalanceOf;
 
@@ -36,10 +38,10 @@ impl<T: Config> Escrow<T> {
     ) -> Result<(), &'static str> {
         match encoded_type {
             b"tran" => Transfer::<T>::exec(encoded_args, escrow_account, executioner),
-            // b"mult" => TransferMulti::exec(encoded_args, escrow_account, executioner),
-            // b"swap" => Swap::exec(encoded_args, escrow_account, executioner),
-            // b"aliq" => AddLiquidity::exec(encoded_args, escrow_account, executioner),
-            // b"call" => Call::exec(encoded_args, escrow_account, executioner),
+            b"mult" => TransferMulti::<T>::exec(encoded_args, escrow_account, executioner),
+            b"swap" => Swap::<T>::exec(encoded_args, escrow_account, executioner),
+            b"aliq" => AddLiquidity::<T>::exec(encoded_args, escrow_account, executioner),
+            b"call" => ContractCall::<T>::exec(encoded_args, escrow_account, executioner),
             // b"wasm" => CallWasm::exec(encoded_args, escrow_account, executioner),
             // b"cevm" => CallEvm::exec(encoded_args, escrow_account, executioner),
             // b"comp" => CallComposable::exec(encoded_args, escrow_account, executioner),
@@ -55,10 +57,10 @@ impl<T: Config> Escrow<T> {
     ) -> Result<(), &'static str> {
         match encoded_type {
             b"tran" => Transfer::<T>::commit(encoded_args, escrow_account, executioner),
-            // b"mult" => TransferMulti::commit(encoded_args, escrow_account, executioner),
-            // b"swap" => Swap::commit(encoded_args, escrow_account, executioner),
-            // b"aliq" => AddLiquidity::commit(encoded_args, escrow_account, executioner),
-            // b"call" => Call::commit(encoded_args, escrow_account, executioner),
+            b"mult" => TransferMulti::<T>::commit(encoded_args, escrow_account, executioner),
+            b"swap" => Swap::<T>::commit(encoded_args, escrow_account, executioner),
+            b"aliq" => AddLiquidity::<T>::commit(encoded_args, escrow_account, executioner),
+            b"call" => ContractCall::<T>::commit(encoded_args, escrow_account, executioner),
             // b"wasm" => CallWasm::commit(encoded_args, escrow_account, executioner),
             // b"cevm" => CallEvm::commit(encoded_args, escrow_account, executioner),
             // b"comp" => CallComposable::commit(encoded_args, escrow_account, executioner),
@@ -74,10 +76,10 @@ impl<T: Config> Escrow<T> {
     ) -> Result<(), &'static str> {
         match encoded_type {
             b"tran" => Transfer::<T>::revert(encoded_args, escrow_account, executioner),
-            // b"mult" => TransferMulti::revert(encoded_args, escrow_account, executioner),
-            // b"swap" => Swap::revert(encoded_args, escrow_account, executioner),
-            // b"aliq" => AddLiquidity::revert(encoded_args, escrow_account, executioner),
-            // b"call" => Call::revert(encoded_args, escrow_account, executioner),
+            b"mult" => TransferMulti::<T>::revert(encoded_args, escrow_account, executioner),
+            b"swap" => Swap::<T>::revert(encoded_args, escrow_account, executioner),
+            b"aliq" => AddLiquidity::<T>::revert(encoded_args, escrow_account, executioner),
+            b"call" => ContractCall::<T>::revert(encoded_args, escrow_account, executioner),
             // b"wasm" => CallWasm::revert(encoded_args, escrow_account, executioner),
             // b"cevm" => CallEvm::revert(encoded_args, escrow_account, executioner),
             // b"comp" => CallComposable::revert(encoded_args, escrow_account, executioner),
@@ -170,10 +172,381 @@ impl<T: Config> EscrowExec<T> for Transfer<T> {
     }
 }
 
+fn decode_arg<D: Decode>(encoded_args: &[Vec<u8>], index: usize) -> Result<D, &'static str> {
+    let encoded_arg = encoded_args.get(index).ok_or("Missing escrow argument")?;
+    Decode::decode(&mut encoded_arg.as_ref()).map_err(|_e| "Decoding err")
+}
+
+fn transfer_asset<T: Config>(
+    asset: AssetIdOf<T>,
+    from: T::AccountId,
+    to: T::AccountId,
+    value: AssetBalanceOf<T>,
+) -> Result<(), &'static str> {
+    log::debug!(
+        "escrow transfer asset {:?} from {:?} to {:?} value {:?}",
+        asset,
+        from,
+        to,
+        value
+    );
+    <T::Assets as fungibles::Transfer<T::AccountId>>::transfer(asset, &from, &to, value, false)
+        .map_err(|_| Error::<T>::EscrowAssetTransferFailed)?;
+
+    <pallet::Pallet<T>>::deposit_event(Event::EscrowAssetTransfer(asset, from, to, value));
+
+    Ok(())
+}
+
+/// Positions of the asset the executioner provides, its value and its beneficiary
+/// within the encoded arguments of a multi-asset side effect, next to the positions
+/// of the assets and values the requester (`caller`) pays the executioner with.
+struct EscrowedAssetArgs {
+    asset: usize,
+    value: usize,
+    dest: usize,
+    caller: usize,
+    requester_assets: &'static [(usize, usize)],
+}
+
+impl EscrowedAssetArgs {
+    fn requester_assets<T: Config>(
+        &self,
+        encoded_args: &[Vec<u8>],
+    ) -> Result<Vec<(AssetIdOf<T>, AssetBalanceOf<T>)>, &'static str> {
+        self.requester_assets
+            .iter()
+            .map(|(asset, value)| {
+                Ok((
+                    decode_arg(encoded_args, *asset)?,
+                    decode_arg(encoded_args, *value)?,
+                ))
+            })
+            .collect()
+    }
+
+    fn exec<T: Config>(
+        &self,
+        encoded_args: Vec<Vec<u8>>,
+        escrow_account: T::AccountId,
+        executioner: T::AccountId,
+    ) -> Result<(), &'static str> {
+        let asset: AssetIdOf<T> = decode_arg(&encoded_args, self.asset)?;
+        let value: AssetBalanceOf<T> = decode_arg(&encoded_args, self.value)?;
+        let requester_assets = self.requester_assets::<T>(&encoded_args)?;
+
+        // both sides are escrowed or none
+        with_storage_layer(|| {
+            if !requester_assets.is_empty() {
+                let caller: T::AccountId = decode_arg(&encoded_args, self.caller)?;
+                for (requester_asset, requester_value) in requester_assets {
+                    transfer_asset::<T>(
+                        requester_asset,
+                        caller.clone(),
+                        escrow_account.clone(),
+                        requester_value,
+                    )?;
+                }
+            }
+
+            transfer_asset::<T>(asset, executioner, escrow_account, value)
+        })
+    }
+
+    fn revert<T: Config>(
+        &self,
+        encoded_args: Vec<Vec<u8>>,
+        escrow_account: T::AccountId,
+        executioner: T::AccountId,
+    ) -> Result<(), &'static str> {
+        let asset: AssetIdOf<T> = decode_arg(&encoded_args, self.asset)?;
+        let value: AssetBalanceOf<T> = decode_arg(&encoded_args, self.value)?;
+        let requester_assets = self.requester_assets::<T>(&encoded_args)?;
+
+        with_storage_layer(|| {
+            if !requester_assets.is_empty() {
+                let caller: T::AccountId = decode_arg(&encoded_args, self.caller)?;
+                for (requester_asset, requester_value) in requester_assets {
+                    transfer_asset::<T>(
+                        requester_asset,
+                        escrow_account.clone(),
+                        caller.clone(),
+                        requester_value,
+                    )?;
+                }
+            }
+
+            transfer_asset::<T>(asset, escrow_account, executioner, value)
+        })
+    }
+
+    fn commit<T: Config>(
+        &self,
+        encoded_args: Vec<Vec<u8>>,
+        escrow_account: T::AccountId,
+        executioner: T::AccountId,
+    ) -> Result<(), &'static str> {
+        let asset: AssetIdOf<T> = decode_arg(&encoded_args, self.asset)?;
+        let value: AssetBalanceOf<T> = decode_arg(&encoded_args, self.value)?;
+        let dest: T::AccountId = decode_arg(&encoded_args, self.dest)?;
+        let requester_assets = self.requester_assets::<T>(&encoded_args)?;
+
+        with_storage_layer(|| {
+            for (requester_asset, requester_value) in requester_assets {
+                transfer_asset::<T>(
+                    requester_asset,
+                    escrow_account.clone(),
+                    executioner.clone(),
+                    requester_value,
+                )?;
+            }
+
+            transfer_asset::<T>(asset, escrow_account, dest, value)
+        })
+    }
+}
+
+// mult: asset, from, to, amount, insurance
+const TRANSFER_MULTI_ARGS: EscrowedAssetArgs = EscrowedAssetArgs {
+    asset: 0,
+    value: 3,
+    dest: 2,
+    caller: 1,
+    requester_assets: &[],
+};
+
+// swap: caller, to, amount_from, amount_to, asset_from, asset_to, insurance
+const SWAP_ARGS: EscrowedAssetArgs = EscrowedAssetArgs {
+    asset: 5,
+    value: 3,
+    dest: 1,
+    caller: 0,
+    requester_assets: &[(4, 2)],
+};
+
+// aliq: caller, to, asset_left, asset_right, liquidity_token,
+//  amount_left, amount_right, amount_liquidity_token, insurance
+const ADD_LIQUIDITY_ARGS: EscrowedAssetArgs = EscrowedAssetArgs {
+    asset: 4,
+    value: 7,
+    dest: 1,
+    caller: 0,
+    requester_assets: &[(2, 5), (3, 6)],
+};
+
+/// Executioner escrows the transferred asset, which is released to the recipient on commit.
+pub struct TransferMulti<T: Config> {
+    _phantom: PhantomData<T>,
+}
+
+impl<T: Config> EscrowExec<T> for TransferMulti<T> {
+    fn exec(
+        encoded_args: Vec<Vec<u8>>,
+        escrow_account: T::AccountId,
+        executioner: T::AccountId,
+    ) -> Result<(), &'static str> {
+        TRANSFER_MULTI_ARGS.exec::<T>(encoded_args, escrow_account, executioner)
+    }
+
+    fn revert(
+        encoded_args: Vec<Vec<u8>>,
+        escrow_account: T::AccountId,
+        executioner: T::AccountId,
+    ) -> Result<(), &'static str> {
+        TRANSFER_MULTI_ARGS.revert::<T>(encoded_args, escrow_account, executioner)
+    }
+
+    fn commit(
+        encoded_args: Vec<Vec<u8>>,
+        escrow_account: T::AccountId,
+        executioner: T::AccountId,
+    ) -> Result<(), &'static str> {
+        TRANSFER_MULTI_ARGS.commit::<T>(encoded_args, escrow_account, executioner)
+    }
+}
+
+/// Requester escrows `amount_from` of `asset_from`, released to the executioner on commit, and
+/// executioner escrows `amount_to` of `asset_to`, which is released to `to` on commit.
+pub struct Swap<T: Config> {
+    _phantom: PhantomData<T>,
+}
+
+impl<T: Config> EscrowExec<T> for Swap<T> {
+    fn exec(
+        encoded_args: Vec<Vec<u8>>,
+        escrow_account: T::AccountId,
+        executioner: T::AccountId,
+    ) -> Result<(), &'static str> {
+        SWAP_ARGS.exec::<T>(encoded_args, escrow_account, executioner)
+    }
+
+    fn revert(
+        encoded_args: Vec<Vec<u8>>,
+        escrow_account: T::AccountId,
+        executioner: T::AccountId,
+    ) -> Result<(), &'static str> {
+        SWAP_ARGS.revert::<T>(encoded_args, escrow_account, executioner)
+    }
+
+    fn commit(
+        encoded_args: Vec<Vec<u8>>,
+        escrow_account: T::AccountId,
+        executioner: T::AccountId,
+    ) -> Result<(), &'static str> {
+        SWAP_ARGS.commit::<T>(encoded_args, escrow_account, executioner)
+    }
+}
+
+/// Requester escrows both of the provided assets, released to the executioner on commit, and
+/// executioner escrows the minted liquidity tokens, which are released to `to` on commit.
+pub struct AddLiquidity<T: Config> {
+    _phantom: PhantomData<T>,
+}
+
+impl<T: Config> EscrowExec<T> for AddLiquidity<T> {
+    fn exec(
+        encoded_args: Vec<Vec<u8>>,
+        escrow_account: T::AccountId,
+        executioner: T::AccountId,
+    ) -> Result<(), &'static str> {
+        ADD_LIQUIDITY_ARGS.exec::<T>(encoded_args, escrow_account, executioner)
+    }
+
+    fn revert(
+        encoded_args: Vec<Vec<u8>>,
+        escrow_account: T::AccountId,
+        executioner: T::AccountId,
+    ) -> Result<(), &'static str> {
+        ADD_LIQUIDITY_ARGS.revert::<T>(encoded_args, escrow_account, executioner)
+    }
+
+    fn commit(
+        encoded_args: Vec<Vec<u8>>,
+        escrow_account: T::AccountId,
+        executioner: T::AccountId,
+    ) -> Result<(), &'static str> {
+        ADD_LIQUIDITY_ARGS.commit::<T>(encoded_args, escrow_account, executioner)
+    }
+}
+
+/// Executioner escrows the call value, the escrow account calls the 3VM contract with it on commit.
+///
+/// Arguments: caller, dest, value, gas_limit, optional storage_deposit_limit, data
+pub struct ContractCall<T: Config> {
+    _phantom: PhantomData<T>,
+}
+
+/// Decodes the gas limit, capped by `EscrowedCallGasLimit`, and the storage deposit limit of
+/// a 3VM contract call. The storage deposit limit is either missing, a single `0` byte for none,
+/// or the limit itself.
+fn decode_call_limits<T: Config>(
+    encoded_args: &[Vec<u8>],
+) -> Result<
+    (
+        Weight,
+        Option<EscrowedBalanceOf<T, <T as Config>::Escrowed>>,
+    ),
+    &'static str,
+> {
+    let gas_limit: EscrowedBalanceOf<T, <T as Config>::Escrowed> = decode_arg(encoded_args, 3)?;
+    let gas_limit = gas_limit.saturated_into::<Weight>();
+    if gas_limit > T::EscrowedCallGasLimit::get() {
+        return Err("Escrowed contract call gas limit too high")
+    }
+
+    let storage_deposit_limit = match encoded_args.len() {
+        5 => None,
+        6 => match encoded_args[4].as_slice() {
+            [0] => None,
+            _ => Some(decode_arg(encoded_args, 4)?),
+        },
+        _ => return Err("Unexpected escrowed contract call arguments"),
+    };
+
+    Ok((gas_limit, storage_deposit_limit))
+}
+
+impl<T: Config> EscrowExec<T> for ContractCall<T> {
+    fn exec(
+        encoded_args: Vec<Vec<u8>>,
+        escrow_account: T::AccountId,
+        executioner: T::AccountId,
+    ) -> Result<(), &'static str> {
+        let value: EscrowedBalanceOf<T, <T as Config>::Escrowed> = decode_arg(&encoded_args, 2)?;
+        // the call is only escrowed if it can be committed
+        decode_call_limits::<T>(&encoded_args)?;
+
+        EscrowCurrencyOf::<T>::transfer(&executioner, &escrow_account, value, AllowDeath)
+            .map_err(|_| Error::<T>::EscrowContractCallFailed)?;
+
+        <pallet::Pallet<T>>::deposit_event(Event::EscrowTransfer(
+            executioner,
+            escrow_account,
+            value,
+        ));
+
+        Ok(())
+    }
+
+    fn revert(
+        encoded_args: Vec<Vec<u8>>,
+        escrow_account: T::AccountId,
+        executioner: T::AccountId,
+    ) -> Result<(), &'static str> {
+        let value: EscrowedBalanceOf<T, <T as Config>::Escrowed> = decode_arg(&encoded_args, 2)?;
+
+        EscrowCurrencyOf::<T>::transfer(&escrow_account, &executioner, value, AllowDeath)
+            .map_err(|_| Error::<T>::EscrowContractCallFailed)?;
+
+        <pallet::Pallet<T>>::deposit_event(Event::EscrowTransfer(
+            escrow_account,
+            executioner,
+            value,
+        ));
+
+        Ok(())
+    }
+
+    fn commit(
+        encoded_args: Vec<Vec<u8>>,
+        escrow_account: T::AccountId,
+        _executioner: T::AccountId,
+    ) -> Result<(), &'static str> {
+        let dest: T::AccountId = decode_arg(&encoded_args, 1)?;
+        let value: EscrowedBalanceOf<T, <T as Config>::Escrowed> = decode_arg(&encoded_args, 2)?;
+        let (gas_limit, storage_deposit_limit) = decode_call_limits::<T>(&encoded_args)?;
+        let data = encoded_args.last().cloned().unwrap_or_default();
+
+        log::debug!(
+            "escrow commit call from {:?} to {:?} value {:?}",
+            escrow_account,
+            dest,
+            value
+        );
+        T::Contracts::call(
+            escrow_account.clone(),
+            dest.clone(),
+            value,
+            gas_limit,
+            storage_deposit_limit,
+            data,
+        )
+        .map_err(|_| Error::<T>::EscrowContractCallFailed)?;
+
+        <pallet::Pallet<T>>::deposit_event(Event::EscrowContractCall(escrow_account, dest, value));
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 pub mod test {
 
-    use frame_support::{assert_ok, traits::Currency};
+    use codec::Encode;
+    use frame_support::{
+        assert_err, assert_noop, assert_ok,
+        traits::{Currency, Get},
+    };
     use frame_system::{EventRecord, Phase};
 
     use t3rn_primitives::{abi::Type, xtx::LocalState};
@@ -223,6 +596,9 @@ pub mod test {
                     side_effects,
                     fee,
                     sequential,
+                    None,
+                    None,
+                    None,
                 ));
 
                 let _xtx_id: sp_core::H256 =
@@ -307,6 +683,9 @@ pub mod test {
                     side_effects,
                     fee,
                     sequential,
+                    None,
+                    None,
+                    None,
                 ));
 
                 assert_ok!(Escrow::<Runtime>::exec(
@@ -370,4 +749,311 @@ pub mod test {
                 );
             });
     }
+
+    #[test]
+    fn escrow_multi_transfer_execute_and_commit_work() {
+        let asset_id: u32 = 1;
+        // mult: asset, from, to, amount, insurance
+        let encoded_args = vec![
+            asset_id.encode(),
+            ALICE.encode(),
+            BOB.encode(),
+            10u128.encode(),
+            vec![],
+        ];
+
+        ExtBuilder::default().build().execute_with(|| {
+            assert_ok!(Assets::force_create(
+                Origin::root(),
+                asset_id,
+                ALICE.into(),
+                true,
+                1
+            ));
+            assert_ok!(Assets::mint(
+                Origin::signed(ALICE),
+                asset_id,
+                CHARLIE.into(),
+                10
+            ));
+
+            assert_ok!(Escrow::<Runtime>::exec(
+                b"mult",
+                encoded_args.clone(),
+                Circuit::account_id(),
+                CHARLIE,
+            ));
+
+            assert_eq!(Assets::balance(asset_id, &CHARLIE), 0);
+            assert_eq!(Assets::balance(asset_id, &Circuit::account_id()), 10);
+            assert_eq!(
+                System::events().pop().unwrap().event,
+                Event::Circuit(
+                    pallet_circuit::pallet::Event::<Runtime>::EscrowAssetTransfer(
+                        asset_id,
+                        CHARLIE,
+                        Circuit::account_id(),
+                        10u128,
+                    )
+                ),
+            );
+
+            assert_ok!(Escrow::<Runtime>::commit(
+                b"mult",
+                encoded_args,
+                Circuit::account_id(),
+                CHARLIE,
+            ));
+
+            assert_eq!(Assets::balance(asset_id, &Circuit::account_id()), 0);
+            assert_eq!(Assets::balance(asset_id, &BOB), 10);
+        });
+    }
+
+    #[test]
+    fn escrow_swap_execute_and_revert_work() {
+        let (asset_from, asset_to): (u32, u32) = (1, 2);
+        // swap: caller, to, amount_from, amount_to, asset_from, asset_to, insurance
+        let encoded_args = vec![
+            ALICE.encode(),
+            BOB.encode(),
+            5u128.encode(),
+            10u128.encode(),
+            asset_from.encode(),
+            asset_to.encode(),
+            vec![],
+        ];
+
+        ExtBuilder::default().build().execute_with(|| {
+            for (asset_id, owner, amount) in [(asset_from, ALICE, 5), (asset_to, CHARLIE, 10)] {
+                assert_ok!(Assets::force_create(
+                    Origin::root(),
+                    asset_id,
+                    ALICE.into(),
+                    true,
+                    1
+                ));
+                assert_ok!(Assets::mint(
+                    Origin::signed(ALICE),
+                    asset_id,
+                    owner.into(),
+                    amount
+                ));
+            }
+
+            assert_ok!(Escrow::<Runtime>::exec(
+                b"swap",
+                encoded_args.clone(),
+                Circuit::account_id(),
+                CHARLIE,
+            ));
+
+            assert_eq!(Assets::balance(asset_from, &ALICE), 0);
+            assert_eq!(Assets::balance(asset_from, &Circuit::account_id()), 5);
+            assert_eq!(Assets::balance(asset_to, &Circuit::account_id()), 10);
+
+            assert_ok!(Escrow::<Runtime>::revert(
+                b"swap",
+                encoded_args,
+                Circuit::account_id(),
+                CHARLIE,
+            ));
+
+            assert_eq!(Assets::balance(asset_from, &Circuit::account_id()), 0);
+            assert_eq!(Assets::balance(asset_from, &ALICE), 5);
+            assert_eq!(Assets::balance(asset_to, &Circuit::account_id()), 0);
+            assert_eq!(Assets::balance(asset_to, &CHARLIE), 10);
+            assert_eq!(Assets::balance(asset_to, &BOB), 0);
+        });
+    }
+
+    #[test]
+    fn escrow_swap_commit_pays_both_sides() {
+        let (asset_from, asset_to): (u32, u32) = (1, 2);
+        // swap: caller, to, amount_from, amount_to, asset_from, asset_to, insurance
+        let encoded_args = vec![
+            ALICE.encode(),
+            BOB.encode(),
+            5u128.encode(),
+            10u128.encode(),
+            asset_from.encode(),
+            asset_to.encode(),
+            vec![],
+        ];
+
+        ExtBuilder::default().build().execute_with(|| {
+            for (asset_id, owner, amount) in [(asset_from, ALICE, 5), (asset_to, CHARLIE, 10)] {
+                assert_ok!(Assets::force_create(
+                    Origin::root(),
+                    asset_id,
+                    ALICE.into(),
+                    true,
+                    1
+                ));
+                assert_ok!(Assets::mint(
+                    Origin::signed(ALICE),
+                    asset_id,
+                    owner.into(),
+                    amount
+                ));
+            }
+
+            assert_ok!(Escrow::<Runtime>::exec(
+                b"swap",
+                encoded_args.clone(),
+                Circuit::account_id(),
+                CHARLIE,
+            ));
+            assert_ok!(Escrow::<Runtime>::commit(
+                b"swap",
+                encoded_args,
+                Circuit::account_id(),
+                CHARLIE,
+            ));
+
+            assert_eq!(Assets::balance(asset_from, &CHARLIE), 5);
+            assert_eq!(Assets::balance(asset_to, &BOB), 10);
+            assert_eq!(Assets::balance(asset_from, &Circuit::account_id()), 0);
+            assert_eq!(Assets::balance(asset_to, &Circuit::account_id()), 0);
+        });
+    }
+
+    #[test]
+    fn escrow_swap_exec_fails_without_the_requester_side() {
+        let (asset_from, asset_to): (u32, u32) = (1, 2);
+        // swap: caller, to, amount_from, amount_to, asset_from, asset_to, insurance
+        let encoded_args = vec![
+            ALICE.encode(),
+            BOB.encode(),
+            5u128.encode(),
+            10u128.encode(),
+            asset_from.encode(),
+            asset_to.encode(),
+            vec![],
+        ];
+
+        ExtBuilder::default().build().execute_with(|| {
+            for asset_id in [asset_from, asset_to] {
+                assert_ok!(Assets::force_create(
+                    Origin::root(),
+                    asset_id,
+                    ALICE.into(),
+                    true,
+                    1
+                ));
+            }
+            assert_ok!(Assets::mint(
+                Origin::signed(ALICE),
+                asset_to,
+                CHARLIE.into(),
+                10
+            ));
+
+            assert_noop!(
+                Escrow::<Runtime>::exec(b"swap", encoded_args, Circuit::account_id(), CHARLIE),
+                "EscrowAssetTransferFailed"
+            );
+            assert_eq!(Assets::balance(asset_to, &CHARLIE), 10);
+        });
+    }
+
+    #[test]
+    fn escrow_contract_call_escrows_value_and_fails_commit_to_non_contract() {
+        // call: caller, dest, value, gas_limit, storage_deposit_limit, data
+        let encoded_args = vec![
+            ALICE.encode(),
+            BOB.encode(),
+            10u128.encode(),
+            1_000_000u128.encode(),
+            vec![0],
+            vec![],
+        ];
+
+        ExtBuilder::default().build().execute_with(|| {
+            let _ = Balances::deposit_creating(&CHARLIE, 10);
+
+            assert_ok!(Escrow::<Runtime>::exec(
+                b"call",
+                encoded_args.clone(),
+                Circuit::account_id(),
+                CHARLIE,
+            ));
+            assert_eq!(Balances::free_balance(&CHARLIE), 0);
+            assert_eq!(Balances::free_balance(&Circuit::account_id()), 10);
+
+            assert_err!(
+                Escrow::<Runtime>::commit(
+                    b"call",
+                    encoded_args.clone(),
+                    Circuit::account_id(),
+                    CHARLIE,
+                ),
+                "EscrowContractCallFailed"
+            );
+
+            assert_ok!(Escrow::<Runtime>::revert(
+                b"call",
+                encoded_args,
+                Circuit::account_id(),
+                CHARLIE,
+            ));
+            assert_eq!(Balances::free_balance(&CHARLIE), 10);
+        });
+    }
+
+    #[test]
+    fn escrow_contract_call_exec_fails_for_too_high_gas_limit() {
+        let gas_limit =
+            <Runtime as pallet_circuit::Config>::EscrowedCallGasLimit::get() as u128 + 1;
+        // call: caller, dest, value, gas_limit, data
+        let encoded_args = vec![
+            ALICE.encode(),
+            BOB.encode(),
+            10u128.encode(),
+            gas_limit.encode(),
+            vec![],
+        ];
+
+        ExtBuilder::default().build().execute_with(|| {
+            let _ = Balances::deposit_creating(&CHARLIE, 10);
+
+            assert_noop!(
+                Escrow::<Runtime>::exec(b"call", encoded_args, Circuit::account_id(), CHARLIE),
+                "Escrowed contract call gas limit too high"
+            );
+        });
+    }
+
+    #[test]
+    fn escrow_exec_fails_for_missing_asset_balance() {
+        let asset_id: u32 = 1;
+        // aliq: caller, to, asset_left, asset_right, liquidity_token,
+        //  amount_left, amount_right, amount_liquidity_token, insurance
+        let encoded_args = vec![
+            ALICE.encode(),
+            BOB.encode(),
+            2u32.encode(),
+            3u32.encode(),
+            asset_id.encode(),
+            1u128.encode(),
+            1u128.encode(),
+            10u128.encode(),
+            vec![],
+        ];
+
+        ExtBuilder::default().build().execute_with(|| {
+            assert_ok!(Assets::force_create(
+                Origin::root(),
+                asset_id,
+                ALICE.into(),
+                true,
+                1
+            ));
+
+            assert_noop!(
+                Escrow::<Runtime>::exec(b"aliq", encoded_args, Circuit::account_id(), CHARLIE),
+                "EscrowAssetTransferFailed"
+            );
+        });
+    }
 }
@@ -30,7 +30,8 @@ use crate::{optimistic::Optimistic, state::*};
 use codec::{Decode, Encode};
 use frame_support::{
     dispatch::{Dispatchable, GetDispatchInfo},
-    traits::{Currency, ExistenceRequirement::AllowDeath, Get},
+    ensure,
+    traits::{fungibles, Currency, ExistenceRequirement::AllowDeath, Get},
     weights::Weight,
     RuntimeDebug,
 };
@@ -44,7 +45,10 @@ use pallet_xbi_portal::{
     xbi_format::{XBICheckIn, XBICheckOut, XBIInstr},
 };
 use pallet_xbi_portal_enter::t3rn_sfx::xbi_result_2_sfx_confirmation;
-use sp_runtime::{traits::Zero, KeyTypeId};
+use sp_runtime::{
+    traits::{Saturating, Zero},
+    DispatchError, KeyTypeId,
+};
 use sp_std::{boxed::Box, convert::TryInto, vec, vec::Vec};
 use t3rn_primitives::account_manager::Outcome;
 
@@ -56,9 +60,10 @@ pub use t3rn_primitives::{
     executors::Executors,
     portal::Portal,
     side_effect::{
-        ConfirmedSideEffect, FullSideEffect, HardenedSideEffect, SecurityLvl, SideEffect,
-        SideEffectId,
+        ConfirmationOutcome, ConfirmedSideEffect, FullSideEffect, HardenedSideEffect, SecurityLvl,
+        SideEffect, SideEffectId,
     },
+    threevm::ContractsCall,
     transfers::EscrowedBalanceOf,
     volatile::LocalState,
     xdns::Xdns,
@@ -98,6 +103,13 @@ pub type EscrowCurrencyOf<T> = <<T as pallet::Config>::Escrowed as EscrowTrait<T
 
 type BalanceOf<T> = EscrowBalance<T>;
 
+pub type AssetIdOf<T> = <<T as pallet::Config>::Assets as fungibles::Inspect<
+    <T as frame_system::Config>::AccountId,
+>>::AssetId;
+pub type AssetBalanceOf<T> = <<T as pallet::Config>::Assets as fungibles::Inspect<
+    <T as frame_system::Config>::AccountId,
+>>::Balance;
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -106,7 +118,7 @@ pub mod pallet {
         pallet_prelude::*,
         traits::{
             fungible::{Inspect, Mutate},
-            Get,
+            Get, StorageVersion,
         },
     };
     use frame_system::pallet_prelude::*;
@@ -212,6 +224,84 @@ pub mod pallet {
         OptionQuery,
     >;
 
+    /// Set when the last timeout sweep left due Xtx behind, so the sweep carries on in the
+    /// following blocks regardless of `XtxTimeoutCheckInterval`.
+    #[pallet::storage]
+    #[pallet::getter(fn get_xtx_timeouts_carried_over)]
+    pub type XtxTimeoutsCarriedOver<T> = StorageValue<_, bool, ValueQuery>;
+
+    /// Xtx by the block they time out at, so the timeout sweep only reads the blocks it passed.
+    /// Entries of Xtx which finished in time are dropped as their block is swept.
+    #[pallet::storage]
+    pub type XtxTimeoutsAt<T> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        <T as frame_system::Config>::BlockNumber,
+        Identity,
+        XExecSignalId<T>,
+        (),
+        OptionQuery,
+    >;
+
+    /// First block whose timed out Xtx may not have been reverted yet.
+    #[pallet::storage]
+    #[pallet::getter(fn get_next_xtx_timeouts_block)]
+    pub type NextXtxTimeoutsBlock<T> =
+        StorageValue<_, <T as frame_system::Config>::BlockNumber, ValueQuery>;
+
+    /// Relayers allowed to confirm side effects on the Circuit.
+    ///
+    /// Managed by root via `register_relayer` and `deregister_relayer`.
+    #[pallet::storage]
+    #[pallet::getter(fn get_relayers)]
+    pub type Relayers<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+    /// Side effects open for executors to bond, by target gateway and side effect kind.
+    #[pallet::storage]
+    #[pallet::getter(fn get_open_side_effects)]
+    pub type OpenSideEffects<T> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        (ChainId, [u8; 4]),
+        Identity,
+        SideEffectId<T>,
+        OpenSideEffect<
+            <T as frame_system::Config>::AccountId,
+            XExecSignalId<T>,
+            <T as frame_system::Config>::BlockNumber,
+            EscrowedBalanceOf<T, <T as Config>::Escrowed>,
+        >,
+        OptionQuery,
+    >;
+
+    /// Confirmed optimistic side effects open to challenges until the stored block, together with
+    /// the encoded hash of the target block their execution was proven in.
+    #[pallet::storage]
+    #[pallet::getter(fn get_challengeable_confirmations)]
+    pub type ChallengeableConfirmations<T> = StorageDoubleMap<
+        _,
+        Identity,
+        XExecSignalId<T>,
+        Identity,
+        SideEffectId<T>,
+        (<T as frame_system::Config>::BlockNumber, Vec<u8>),
+        OptionQuery,
+    >;
+
+    /// The lowest bid to execute an optimistic side effect so far, placed by the bonded relayer
+    /// of its insurance deposit.
+    #[pallet::storage]
+    #[pallet::getter(fn get_side_effect_bids)]
+    pub type SideEffectBids<T> = StorageDoubleMap<
+        _,
+        Identity,
+        XExecSignalId<T>,
+        Identity,
+        SideEffectId<T>,
+        EscrowedBalanceOf<T, <T as Config>::Escrowed>,
+        OptionQuery,
+    >;
+
     /// Handles queued signals
     ///
     /// This operation is performed lazily in `on_initialize`.
@@ -242,15 +332,47 @@ pub mod pallet {
         #[pallet::constant]
         type XtxTimeoutDefault: Get<Self::BlockNumber>;
 
+        /// The shortest Xtx timeout requesters can ask for
+        #[pallet::constant]
+        type XtxTimeoutMin: Get<Self::BlockNumber>;
+
+        /// The longest Xtx timeout requesters can ask for
+        #[pallet::constant]
+        type XtxTimeoutMax: Get<Self::BlockNumber>;
+
         /// The Circuit's Xtx timeout check interval
         #[pallet::constant]
         type XtxTimeoutCheckInterval: Get<Self::BlockNumber>;
 
+        /// For how many blocks after confirmation other executors can challenge
+        ///     an optimistic side effect with a proof of misbehaviour
+        #[pallet::constant]
+        type MisbehaviourChallengeWindow: Get<Self::BlockNumber>;
+
+        /// For how many blocks after the side effect is requested executors can bid
+        ///     to execute it for less than its prize
+        #[pallet::constant]
+        type SideEffectBiddingPeriod: Get<Self::BlockNumber>;
+
+        /// For how many blocks after bidding closes only the winning bidder can bond
+        ///     the insurance deposit, before anyone can bond it at the winning bid
+        #[pallet::constant]
+        type SideEffectBondingGracePeriod: Get<Self::BlockNumber>;
+
         /// The Circuit's deletion queue limit - preventing potential
         ///     delay when queue is too long in on_initialize
         #[pallet::constant]
         type DeletionQueueLimit: Get<u32>;
 
+        /// The most side effects a single Xtx can request - bounding the weight
+        ///     of the calls that load the Xtx
+        #[pallet::constant]
+        type MaxSideEffectsPerXtx: Get<u32>;
+
+        /// Relayers registered on the runtime upgrade of chains started before relayers had to
+        ///     be registered, so side effects can still be confirmed
+        type BootstrapRelayers: Get<Vec<Self::AccountId>>;
+
         /// The overarching event type.
         type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
@@ -296,6 +418,17 @@ pub mod pallet {
         /// A type that manages escrow, and therefore balances
         type Escrowed: EscrowTrait<Self>;
 
+        /// A type that moves the multi-asset funds of escrowed side effects
+        type Assets: fungibles::Transfer<Self::AccountId>;
+
+        /// A type that calls into the 3VM contracts for escrowed side effects
+        type Contracts: ContractsCall<Self::AccountId, EscrowedBalanceOf<Self, Self::Escrowed>>;
+
+        /// The most gas escrowed 3VM contract calls can be given, accounted for
+        ///     in the weight of confirming side effects
+        #[pallet::constant]
+        type EscrowedCallGasLimit: Get<Weight>;
+
         /// A type that gives access to the new portal functionality
         type Portal: Portal<Self>;
 
@@ -316,11 +449,42 @@ pub mod pallet {
         type SignalQueueDepth: Get<u32>;
     }
 
+    /// The in-code storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
     #[pallet::generate_store(pub (super) trait Store)]
     #[pallet::without_storage_info]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
+    #[pallet::genesis_config]
+    pub struct GenesisConfig<T: Config> {
+        /// Relayers allowed to confirm side effects from genesis on.
+        pub relayers: Vec<T::AccountId>,
+    }
+
+    /// The default value for the genesis config type.
+    #[cfg(feature = "std")]
+    impl<T: Config> Default for GenesisConfig<T> {
+        fn default() -> Self {
+            Self {
+                relayers: Default::default(),
+            }
+        }
+    }
+
+    /// The build of genesis for the pallet.
+    /// Registers the genesis relayers.
+    #[pallet::genesis_build]
+    impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+        fn build(&self) {
+            for relayer in &self.relayers {
+                <Relayers<T>>::insert(relayer, ());
+            }
+        }
+    }
+
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
         // `on_initialize` is executed at the beginning of the block before any extrinsic are
@@ -330,50 +494,24 @@ pub mod pallet {
         fn on_initialize(n: T::BlockNumber) -> Weight {
             let weight = Self::process_signal_queue();
 
-            // Check every XtxTimeoutCheckInterval blocks
-
-            // what happens if the weight for the block is consumed, do these timeouts need to wait
-            // for the next check interval to handle them? maybe we need an immediate queue
-            //
-            // Scenario 1: all the timeouts can be handled in the block space
-            // Scenario 2: all but 5 timeouts can be handled
-            //     - add the 5 timeouts to an immediate queue for the next block
-            if n % T::XtxTimeoutCheckInterval::get() == T::BlockNumber::from(0u8) {
-                let mut deletion_counter: u32 = 0;
-                // Go over all unfinished Xtx to find those that timed out
-                <ActiveXExecSignalsTimingLinks<T>>::iter()
-                    .find(|(_xtx_id, timeout_at)| {
-                        timeout_at <= &frame_system::Pallet::<T>::block_number()
-                    })
-                    .map(|(xtx_id, _timeout_at)| {
-                        if deletion_counter > T::DeletionQueueLimit::get() {
-                            return
-                        }
-                        let mut local_xtx_ctx = Self::setup(
-                            CircuitStatus::RevertTimedOut,
-                            &Self::account_id(),
-                            Zero::zero(),
-                            Some(xtx_id),
-                        )
-                        .unwrap();
+            // Timeouts get the half of the block the signal queue didn't use up
+            let max_timeouts_weight = (<T as frame_system::Config>::BlockWeights::get().max_block
+                / 2)
+            .saturating_sub(weight);
 
-                        Self::kill(&mut local_xtx_ctx, CircuitStatus::RevertTimedOut);
+            weight.saturating_add(Self::process_xtx_timeouts(n, max_timeouts_weight))
+        }
 
-                        Self::emit(
-                            local_xtx_ctx.xtx_id,
-                            Some(local_xtx_ctx.xtx),
-                            &Self::account_id(),
-                            &vec![],
-                            None,
-                        );
-                        deletion_counter += 1;
-                    });
+        fn on_runtime_upgrade() -> Weight {
+            if StorageVersion::get::<Pallet<T>>() >= STORAGE_VERSION {
+                return T::DbWeight::get().reads(1)
             }
 
-            // Anything that needs to be done at the start of the block.
-            // We don't do anything here.
-            // ToDo: Do active xtx signals overview and Cancel if time elapsed
-            weight
+            let weight =
+                Self::index_xtx_timeouts().saturating_add(Self::register_bootstrap_relayers());
+            STORAGE_VERSION.put::<Pallet<T>>();
+
+            weight.saturating_add(T::DbWeight::get().reads_writes(1, 1))
         }
 
         fn on_finalize(_n: T::BlockNumber) {
@@ -511,7 +649,13 @@ pub mod pallet {
             // ToDo: Align whether 3vm wants enfore side effects sequence into steps
             let sequential = false;
             // Validate: Side Effects
-            Self::validate(&side_effects, &mut local_xtx_ctx, &requester, sequential)?;
+            Self::validate(
+                &side_effects,
+                &mut local_xtx_ctx,
+                &requester,
+                sequential,
+                None,
+            )?;
 
             // Account fees and charges
             Self::square_up(&mut local_xtx_ctx, Some(requester.clone()), None)?;
@@ -570,7 +714,15 @@ pub mod pallet {
             unimplemented!();
         }
 
-        #[pallet::weight(<T as pallet::Config>::WeightInfo::on_extrinsic_trigger())]
+        /// Requests execution of the side effects as a new Xtx.
+        ///
+        /// By default Circuit lines up the side effects into steps by their security level.
+        /// `sequential` puts each of them into its own step in the submitted order, while `steps`
+        /// explicitly assigns every side effect to a step by its index and takes precedence.
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::on_extrinsic_trigger(
+            side_effects.len() as u32,
+            delay_steps_at.as_ref().map_or(0, |delays| delays.len() as u32),
+        ))]
         pub fn on_extrinsic_trigger(
             origin: OriginFor<T>,
             side_effects: Vec<
@@ -578,20 +730,38 @@ pub mod pallet {
             >,
             fee: EscrowedBalanceOf<T, T::Escrowed>,
             sequential: bool,
+            steps: Option<Vec<u32>>,
+            delay_steps_at: Option<Vec<T::BlockNumber>>,
+            timeout: Option<T::BlockNumber>,
         ) -> DispatchResultWithPostInfo {
             // Authorize: Retrieve sender of the transaction.
             let requester = Self::authorize(origin, CircuitRole::Requester)?;
+            ensure!(
+                side_effects.len() as u32 <= T::MaxSideEffectsPerXtx::get(),
+                Error::<T>::TooManySideEffects
+            );
             // Setup: new xtx context
             let mut local_xtx_ctx: LocalXtxCtx<T> =
                 Self::setup(CircuitStatus::Requested, &requester, fee, None)?;
 
+            // Timeout: the requested one or derived from the slowest target
+            Self::set_timeout(&mut local_xtx_ctx, &side_effects, timeout)?;
+
             // Validate: Side Effects
-            Self::validate(&side_effects, &mut local_xtx_ctx, &requester, sequential).map_err(
-                |e| {
-                    log::error!("Self::validate hit an error -- {:?}", e);
-                    Error::<T>::SideEffectsValidationFailed
-                },
-            )?;
+            Self::validate(
+                &side_effects,
+                &mut local_xtx_ctx,
+                &requester,
+                sequential,
+                steps,
+            )
+            .map_err(|e| {
+                log::error!("Self::validate hit an error -- {:?}", e);
+                Error::<T>::SideEffectsValidationFailed
+            })?;
+
+            // Schedule: earliest blocks the steps can start at
+            Self::schedule_steps(&mut local_xtx_ctx, delay_steps_at)?;
 
             // Account fees and charges
             Self::square_up(&mut local_xtx_ctx, Some(requester.clone()), None)?;
@@ -615,7 +785,89 @@ pub mod pallet {
             Ok(().into())
         }
 
-        #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_insurance_deposit())]
+        /// Cancels the Xtx before its execution starts, refunding the requester
+        /// and dropping the Xtx from storage.
+        ///
+        /// Allowed to the requester while the Xtx awaits insurance and none of its side effects
+        /// has been bonded, bid for or confirmed yet.
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::cancel_xtx(
+            T::MaxSideEffectsPerXtx::get()
+        ))]
+        pub fn cancel_xtx(
+            origin: OriginFor<T>,
+            xtx_id: XExecSignalId<T>,
+        ) -> DispatchResultWithPostInfo {
+            let requester = Self::authorize(origin, CircuitRole::Requester)?;
+
+            let local_xtx_ctx: LocalXtxCtx<T> = Self::setup(
+                CircuitStatus::PendingInsurance,
+                &requester,
+                Zero::zero(),
+                Some(xtx_id),
+            )?;
+
+            ensure!(
+                local_xtx_ctx.xtx.requester == requester,
+                Error::<T>::CancellationUnauthorized
+            );
+
+            let current_step_fsx = local_xtx_ctx
+                .full_side_effects
+                .get(local_xtx_ctx.xtx.steps_cnt.0 as usize)
+                .cloned()
+                .unwrap_or_default();
+
+            // Bonds of later steps are checked too, so that no executor is left with a reserve
+            ensure!(
+                local_xtx_ctx.xtx.status <= CircuitStatus::PendingInsurance
+                    && current_step_fsx.iter().all(|fsx| fsx.confirmed.is_none())
+                    && local_xtx_ctx
+                        .insurance_deposits
+                        .iter()
+                        .all(|(_, insurance_deposit)| insurance_deposit.bonded_relayer.is_none()),
+                Error::<T>::CancellationNotAllowed
+            );
+
+            // Refund the rewards charged to the requester for the current step
+            for fsx in current_step_fsx.iter() {
+                if fsx.input.prize > Zero::zero() {
+                    <T as Config>::AccountManager::finalize(
+                        fsx.input.generate_id::<SystemHashing<T>>(),
+                        Outcome::Revert,
+                        None,
+                        None,
+                    )
+                    .map_err(|_e| Error::<T>::RefundTransferFailed)?;
+                }
+            }
+
+            Self::drop_xtx(&local_xtx_ctx);
+
+            Self::deposit_event(Event::CancelledSideEffects(
+                requester,
+                xtx_id,
+                local_xtx_ctx
+                    .full_side_effects
+                    .iter()
+                    .flatten()
+                    .map(|fsx| fsx.input.clone())
+                    .collect(),
+            ));
+
+            Ok(Some(<T as pallet::Config>::WeightInfo::cancel_xtx(
+                Self::side_effects_count(&local_xtx_ctx),
+            ))
+            .into())
+        }
+
+        /// Bonds the insurance deposit of an optimistic side effect.
+        ///
+        /// Without bids the slot goes straight to the first executor to bond, which ends the
+        /// bidding. Once bids were placed, bonding waits for the bidding to close - the winning
+        /// bidder bonds at their bid, or anyone does once `SideEffectBondingGracePeriod` passed.
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::bond_insurance_deposit(
+            T::MaxSideEffectsPerXtx::get()
+        ))]
         pub fn bond_insurance_deposit(
             origin: OriginFor<T>, // Active relayer
             xtx_id: XExecSignalId<T>,
@@ -632,8 +884,34 @@ pub mod pallet {
                 Some(xtx_id),
             )?;
 
-            let insurance_deposit_copy =
-                Optimistic::<T>::bond_4_sfx(&executor, &mut local_xtx_ctx, side_effect_id)?;
+            let now = frame_system::Pallet::<T>::block_number();
+            let bidding_closes_at = Self::bidding_closes_at(&local_xtx_ctx, side_effect_id)?;
+
+            // Once bidding closes the slot goes to the lowest bid, otherwise to the first to bond
+            let insurance_deposit_copy = if let Some(winning_bid) =
+                <Self as Store>::SideEffectBids::get(xtx_id, side_effect_id)
+            {
+                ensure!(now > bidding_closes_at, Error::<T>::BiddingStillOpen);
+                let (insurance_deposit, prize) = Optimistic::<T>::close_bidding_4_sfx(
+                    &executor,
+                    &mut local_xtx_ctx,
+                    side_effect_id,
+                    winning_bid,
+                    now > bidding_closes_at + T::SideEffectBondingGracePeriod::get(),
+                )?;
+                // Return what's left of the prize above the winning bid to the requester
+                if winning_bid < prize {
+                    <T as Config>::AccountManager::reduce_offered_reward(
+                        side_effect_id,
+                        winning_bid,
+                    )
+                    .map_err(|_e| Error::<T>::RefundTransferFailed)?;
+                }
+                <Self as Store>::SideEffectBids::remove(xtx_id, side_effect_id);
+                insurance_deposit
+            } else {
+                Optimistic::<T>::bond_4_sfx(&executor, &mut local_xtx_ctx, side_effect_id)?
+            };
 
             let status_change = Self::update(&mut local_xtx_ctx)?;
 
@@ -644,6 +922,8 @@ pub mod pallet {
                 status_change,
             );
 
+            Self::delist_open_side_effect(&local_xtx_ctx, side_effect_id);
+
             Self::deposit_event(Event::SideEffectInsuranceReceived(
                 side_effect_id,
                 executor.clone(),
@@ -658,10 +938,75 @@ pub mod pallet {
                 None,
             );
 
-            Ok(().into())
+            Ok(
+                Some(<T as pallet::Config>::WeightInfo::bond_insurance_deposit(
+                    Self::side_effects_count(&local_xtx_ctx),
+                ))
+                .into(),
+            )
+        }
+
+        /// Bids to execute an optimistic side effect for less than the prize offered by the
+        /// requester.
+        ///
+        /// Accepted for `SideEffectBiddingPeriod` blocks after the side effect was requested, unless
+        /// an executor bonded it without bids in the meantime. After that, the lowest bidder bonds
+        /// the insurance deposit with `bond_insurance_deposit` and the requester is refunded the
+        /// rest of the prize.
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::bid_side_effect(
+            T::MaxSideEffectsPerXtx::get()
+        ))]
+        pub fn bid_side_effect(
+            origin: OriginFor<T>,
+            xtx_id: XExecSignalId<T>,
+            side_effect_id: SideEffectId<T>,
+            bid: EscrowedBalanceOf<T, T::Escrowed>,
+        ) -> DispatchResultWithPostInfo {
+            let executor = Self::authorize(origin, CircuitRole::Executor)?;
+
+            let mut local_xtx_ctx: LocalXtxCtx<T> = Self::setup(
+                CircuitStatus::PendingInsurance,
+                &executor,
+                Zero::zero(),
+                Some(xtx_id),
+            )?;
+
+            ensure!(
+                frame_system::Pallet::<T>::block_number()
+                    <= Self::bidding_closes_at(&local_xtx_ctx, side_effect_id)?,
+                Error::<T>::BiddingClosed
+            );
+
+            let insurance_deposit_copy = Optimistic::<T>::bid_4_sfx(
+                &executor,
+                &mut local_xtx_ctx,
+                side_effect_id,
+                bid,
+                <Self as Store>::SideEffectBids::get(xtx_id, side_effect_id),
+            )?;
+
+            let status_change = Self::update(&mut local_xtx_ctx)?;
+
+            Self::apply(
+                &mut local_xtx_ctx,
+                Some((side_effect_id, insurance_deposit_copy)),
+                None,
+                status_change,
+            );
+
+            <Self as Store>::SideEffectBids::insert(xtx_id, side_effect_id, bid);
+
+            Self::deposit_event(Event::SideEffectBidReceived(side_effect_id, executor, bid));
+
+            Ok(Some(<T as pallet::Config>::WeightInfo::bid_side_effect(
+                Self::side_effects_count(&local_xtx_ctx),
+            ))
+            .into())
         }
 
-        #[pallet::weight(<T as pallet::Config>::WeightInfo::execute_side_effects_with_xbi())]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::execute_side_effects_with_xbi(
+            T::MaxSideEffectsPerXtx::get()
+        ))]
         pub fn execute_side_effects_with_xbi(
             origin: OriginFor<T>, // Active relayer
             xtx_id: XExecSignalId<T>,
@@ -689,6 +1034,14 @@ pub mod pallet {
                 Some(xtx_id),
             )?;
 
+            Self::ensure_current_step_open(&local_xtx_ctx)?;
+
+            // Check against the stored side effect, not the copy submitted by the executor
+            let side_effect = Self::find_current_step_side_effect(&local_xtx_ctx, sfx_id)
+                .cloned()
+                .ok_or(Error::<T>::SideEffectNotInCurrentStep)?;
+            Self::ensure_enforced_executioner(&side_effect, &executor)?;
+
             let xbi =
                 sfx_2_xbi::<T, T::Escrowed>(
                     &side_effect,
@@ -720,10 +1073,17 @@ pub mod pallet {
                 pallet::Call::<T>::on_xbi_sfx_resolved { sfx_id }.into(),
             )?;
 
-            Ok(().into())
+            Ok(Some(
+                <T as pallet::Config>::WeightInfo::execute_side_effects_with_xbi(
+                    Self::side_effects_count(&local_xtx_ctx),
+                ),
+            )
+            .into())
         }
 
-        #[pallet::weight(< T as Config >::WeightInfo::confirm_side_effect())]
+        #[pallet::weight(< T as Config >::WeightInfo::confirm_side_effect(
+            T::MaxSideEffectsPerXtx::get()
+        ))]
         pub fn on_xbi_sfx_resolved(
             _origin: OriginFor<T>,
             sfx_id: T::Hash,
@@ -735,8 +1095,14 @@ pub mod pallet {
             Ok(().into())
         }
 
-        /// Blind version should only be used for testing - unsafe since skips inclusion proof check.
-        #[pallet::weight(< T as Config >::WeightInfo::confirm_side_effect())]
+        /// Confirms execution of the side effect on its target gateway.
+        ///
+        /// Optimistic and Dirty side effects require the inclusion proof of the event and the hash of
+        /// the block it was emitted in, which must be known to the gateway's finality verifier.
+        #[pallet::weight(
+            < T as Config >::WeightInfo::confirm_side_effect(T::MaxSideEffectsPerXtx::get())
+                .saturating_add(T::EscrowedCallGasLimit::get())
+        )]
         pub fn confirm_side_effect(
             origin: OriginFor<T>,
             xtx_id: XtxId<T>,
@@ -750,8 +1116,8 @@ pub mod pallet {
                 <T as frame_system::Config>::BlockNumber,
                 EscrowedBalanceOf<T, T::Escrowed>,
             >,
-            _inclusion_proof: Option<Vec<Vec<u8>>>,
-            _block_hash: Option<Vec<u8>>,
+            inclusion_proof: Option<Vec<Vec<u8>>>,
+            block_hash: Option<Vec<u8>>,
         ) -> DispatchResultWithPostInfo {
             // Authorize: Retrieve sender of the transaction.
             let relayer = Self::authorize(origin, CircuitRole::Relayer)?;
@@ -764,7 +1130,36 @@ pub mod pallet {
                 Some(xtx_id),
             )?;
 
-            Self::confirm(&mut local_xtx_ctx, &relayer, &side_effect, &confirmation)?;
+            Self::ensure_current_step_open(&local_xtx_ctx)?;
+
+            let actual_weight = <T as Config>::WeightInfo::confirm_side_effect(
+                Self::side_effects_count(&local_xtx_ctx),
+            )
+            .saturating_add(T::EscrowedCallGasLimit::get());
+
+            let maybe_inclusion_proof = match (inclusion_proof, block_hash) {
+                (Some(inclusion_proof), Some(block_hash)) => Some((inclusion_proof, block_hash)),
+                _ => None,
+            };
+            let maybe_proven_block_hash = maybe_inclusion_proof
+                .as_ref()
+                .map(|(_, block_hash)| block_hash.clone());
+
+            let sfx_id = side_effect.generate_id::<SystemHashing<T>>();
+
+            let maybe_misbehaviour = Self::confirm(
+                &mut local_xtx_ctx,
+                &relayer,
+                &side_effect,
+                &confirmation,
+                maybe_inclusion_proof,
+            )?;
+
+            // The proven execution differs from the requested side effect - revert the Xtx
+            if maybe_misbehaviour.is_some() {
+                Self::punish_misbehaviour(&mut local_xtx_ctx, sfx_id, &relayer)?;
+                return Ok(Some(actual_weight).into())
+            }
 
             let status_change = Self::update(&mut local_xtx_ctx)?;
 
@@ -772,9 +1167,30 @@ pub mod pallet {
             let (maybe_xtx_changed, assert_full_side_effects_changed) =
                 Self::apply(&mut local_xtx_ctx, None, None, status_change);
 
-            Self::deposit_event(Event::SideEffectConfirmed(
-                side_effect.generate_id::<SystemHashing<T>>(),
-            ));
+            Self::delist_open_side_effect(&local_xtx_ctx, sfx_id);
+
+            if local_xtx_ctx.xtx.status == CircuitStatus::FinishedAllSteps {
+                Self::close_challenge_windows(&local_xtx_ctx);
+            } else if let Some(block_hash) = maybe_proven_block_hash {
+                // Only insured, optimistic side effects can be challenged by other executors
+                if local_xtx_ctx
+                    .insurance_deposits
+                    .iter()
+                    .any(|(id, _)| *id == sfx_id)
+                {
+                    <Self as Store>::ChallengeableConfirmations::insert(
+                        local_xtx_ctx.xtx_id,
+                        sfx_id,
+                        (
+                            frame_system::Pallet::<T>::block_number()
+                                + T::MisbehaviourChallengeWindow::get(),
+                            block_hash,
+                        ),
+                    );
+                }
+            }
+
+            Self::deposit_event(Event::SideEffectConfirmed(sfx_id));
 
             // Emit: From Circuit events
             Self::emit(
@@ -785,8 +1201,139 @@ pub mod pallet {
                 assert_full_side_effects_changed,
             );
 
+            Ok(Some(actual_weight).into())
+        }
+
+        /// Registers a relayer allowed to confirm side effects. Root only access.
+        #[pallet::weight(<T as Config>::WeightInfo::register_relayer())]
+        pub fn register_relayer(
+            origin: OriginFor<T>,
+            relayer: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            ensure!(
+                !<Relayers<T>>::contains_key(&relayer),
+                Error::<T>::RelayerAlreadyRegistered
+            );
+
+            <Relayers<T>>::insert(&relayer, ());
+
+            Self::deposit_event(Event::RelayerRegistered(relayer));
+
+            Ok(().into())
+        }
+
+        /// Removes a relayer from the registered set. Root only access.
+        #[pallet::weight(<T as Config>::WeightInfo::deregister_relayer())]
+        pub fn deregister_relayer(
+            origin: OriginFor<T>,
+            relayer: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            ensure!(
+                <Relayers<T>>::contains_key(&relayer),
+                Error::<T>::RelayerNotRegistered
+            );
+
+            <Relayers<T>>::remove(&relayer);
+
+            Self::deposit_event(Event::RelayerDeregistered(relayer));
+
             Ok(().into())
         }
+
+        /// Challenges the confirmation of an optimistic side effect with the inclusion proof of
+        /// the execution it was confirmed with, which does not match the side effect.
+        ///
+        /// Only the event the confirmation was made with, proven in the same target block, can
+        /// be challenged, so other executions of the executor can't get them slashed. Accepted
+        /// from other executors within `MisbehaviourChallengeWindow` blocks after the
+        /// confirmation. A proven misbehaviour slashes the executor and reverts the Xtx.
+        #[pallet::weight(<T as Config>::WeightInfo::challenge_side_effect(
+            T::MaxSideEffectsPerXtx::get()
+        ))]
+        pub fn challenge_side_effect(
+            origin: OriginFor<T>,
+            xtx_id: XtxId<T>,
+            side_effect: SideEffect<
+                <T as frame_system::Config>::AccountId,
+                <T as frame_system::Config>::BlockNumber,
+                EscrowedBalanceOf<T, T::Escrowed>,
+            >,
+            inclusion_proof: Vec<Vec<u8>>,
+        ) -> DispatchResultWithPostInfo {
+            let challenger = Self::authorize(origin, CircuitRole::Executor)?;
+
+            let sfx_id = side_effect.generate_id::<SystemHashing<T>>();
+            let (challengeable_until, block_hash) =
+                <Self as Store>::ChallengeableConfirmations::get(xtx_id, sfx_id)
+                    .ok_or(Error::<T>::ConfirmationNotChallengeable)?;
+            ensure!(
+                frame_system::Pallet::<T>::block_number() <= challengeable_until,
+                Error::<T>::ChallengeWindowClosed
+            );
+
+            let mut local_xtx_ctx: LocalXtxCtx<T> = Self::setup(
+                CircuitStatus::RevertMisbehaviour,
+                &challenger,
+                Zero::zero(),
+                Some(xtx_id),
+            )?;
+            ensure!(
+                local_xtx_ctx.xtx.status < CircuitStatus::FinishedAllSteps,
+                Error::<T>::ConfirmationNotChallengeable
+            );
+
+            let fsx = local_xtx_ctx
+                .full_side_effects
+                .iter()
+                .flatten()
+                .find(|fsx| fsx.input.generate_id::<SystemHashing<T>>() == sfx_id)
+                .cloned()
+                .ok_or(Error::<T>::ConfirmationNotChallengeable)?;
+            let confirmed = fsx
+                .confirmed
+                .clone()
+                .ok_or(Error::<T>::ConfirmationNotChallengeable)?;
+            ensure!(
+                challenger != confirmed.executioner,
+                Error::<T>::ChallengerIsExecutioner
+            );
+
+            let mut action_id: [u8; 4] = [0, 0, 0, 0];
+            action_id.copy_from_slice(&side_effect.encoded_action[0..4]);
+
+            // Re-prove the very event the confirmation was made with
+            let (params, source) = <T as Config>::Portal::confirm_event_inclusion(
+                side_effect.target,
+                confirmed.inclusion_data.clone(),
+                fsx.submission_target_height.clone(),
+                inclusion_proof,
+                block_hash,
+                action_id,
+            )
+            .map_err(|_| "SideEffect confirmation failed!")?;
+
+            let misbehaviour = Self::check_execution(
+                &local_xtx_ctx.local_state,
+                &side_effect,
+                fsx.security_lvl,
+                &confirmed.executioner,
+                params,
+                source,
+            )?
+            .ok_or(Error::<T>::MisbehaviourNotProven)?;
+
+            Self::mark_misbehaviour(&mut local_xtx_ctx, sfx_id, misbehaviour);
+            <Self as Store>::ChallengeableConfirmations::remove(xtx_id, sfx_id);
+
+            Self::punish_misbehaviour(&mut local_xtx_ctx, sfx_id, &challenger)?;
+
+            Ok(Some(<T as Config>::WeightInfo::challenge_side_effect(
+                Self::side_effects_count(&local_xtx_ctx),
+            ))
+            .into())
+        }
     }
 
     use pallet_xbi_portal::xbi_abi::{
@@ -857,7 +1404,6 @@ pub mod pallet {
             Vec<SideEffectId<T>>,
         ),
         // Listeners - executioners/relayers to know that certain SideEffects are no longer valid
-        // ToDo: Implement Xtx timeout!
         CancelledSideEffects(
             <T as frame_system::Config>::AccountId,
             XtxId<T>,
@@ -888,6 +1434,35 @@ pub mod pallet {
             T::AccountId,                                  // to
             EscrowedBalanceOf<T, <T as Config>::Escrowed>, // value
         ),
+        EscrowAssetTransfer(
+            AssetIdOf<T>,      // asset
+            T::AccountId,      // from
+            T::AccountId,      // to
+            AssetBalanceOf<T>, // value
+        ),
+        EscrowContractCall(
+            T::AccountId,                                  // origin
+            T::AccountId,                                  // dest
+            EscrowedBalanceOf<T, <T as Config>::Escrowed>, // value
+        ),
+        // A relayer was allowed to confirm side effects.
+        RelayerRegistered(<T as frame_system::Config>::AccountId),
+        // A relayer is no longer allowed to confirm side effects.
+        RelayerDeregistered(<T as frame_system::Config>::AccountId),
+        // An executor proven to execute something different than the side effect was slashed
+        //  and the Xtx reverted.
+        ExecutorMisbehaviourSlashed(
+            XExecSignalId<T>,
+            SideEffectId<T>,
+            <T as frame_system::Config>::AccountId, // executor
+            <T as frame_system::Config>::AccountId, // reporter
+        ),
+        // An executor placed the lowest bid to execute the side effect so far.
+        SideEffectBidReceived(
+            SideEffectId<T>,
+            <T as frame_system::Config>::AccountId, // executor
+            EscrowedBalanceOf<T, <T as Config>::Escrowed>, // bid
+        ),
     }
 
     #[pallet::error]
@@ -934,26 +1509,199 @@ pub mod pallet {
         UnsupportedRole,
         InvalidLocalTrigger,
         SignalQueueFull,
+        InvalidDelayedStepsSchedule,
+        StepNotOpenYet,
+        InvalidXtxTimeout,
+        UnauthorizedExecutor,
+        UnauthorizedRelayer,
+        RelayerAlreadyRegistered,
+        RelayerNotRegistered,
+        EscrowAssetTransferFailed,
+        EscrowContractCallFailed,
+        ConfirmationNotChallengeable,
+        ChallengeWindowClosed,
+        ChallengerIsExecutioner,
+        MisbehaviourNotProven,
+        BiddingClosed,
+        BiddingStillOpen,
+        BidAboveMaxPrize,
+        BidNotCompetitive,
+        BidderNotWinning,
+        EnforcedExecutionerMismatch,
+        CancellationUnauthorized,
+        CancellationNotAllowed,
+        SideEffectNotInCurrentStep,
+        InsuranceSlashFailed,
+        TooManySideEffects,
+    }
+}
+
+/// Payload used by this example crate to hold price
+/// data required to submit a transaction.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Payload<Public, BlockNumber> {
+    block_number: BlockNumber,
+    public: Public,
+}
+
+impl<T: SigningTypes> SignedPayload<T> for Payload<T::Public, T::BlockNumber> {
+    fn public(&self) -> T::Public {
+        self.public.clone()
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Returns the insurance deposits of Xtx that haven't finished all of the steps yet,
+    /// together with the ids of their Xtx and side effects.
+    pub fn get_open_insurance_deposits() -> Vec<(
+        XExecSignalId<T>,
+        SideEffectId<T>,
+        InsuranceDeposit<T::AccountId, T::BlockNumber, EscrowedBalanceOf<T, T::Escrowed>>,
+    )> {
+        <Self as Store>::InsuranceDeposits::iter()
+            .filter(|(xtx_id, _, _)| {
+                <Self as Store>::XExecSignals::get(xtx_id)
+                    .map(|xtx| xtx.status < CircuitStatus::FinishedAllSteps)
+                    .unwrap_or(false)
+            })
+            .collect()
+    }
+
+    /// Returns side effects of the kind on the target gateway open for executors to bond.
+    pub fn get_open_side_effects_by_kind(
+        target: ChainId,
+        kind: [u8; 4],
+    ) -> Vec<(
+        SideEffectId<T>,
+        OpenSideEffect<
+            T::AccountId,
+            XExecSignalId<T>,
+            T::BlockNumber,
+            EscrowedBalanceOf<T, T::Escrowed>,
+        >,
+    )> {
+        <Self as Store>::OpenSideEffects::iter_prefix((target, kind)).collect()
+    }
+
+    /// Returns a page of Xtx requested by the account, skipping the first `offset` of them.
+    pub fn get_xtx_by_requester(
+        requester: &T::AccountId,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<(
+        XExecSignalId<T>,
+        XExecSignal<T::AccountId, T::BlockNumber, EscrowedBalanceOf<T, T::Escrowed>>,
+    )> {
+        <Self as Store>::XExecSignals::iter()
+            .filter(|(_, xtx)| xtx.requester == *requester)
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect()
     }
-}
 
-pub fn get_xtx_status() {}
+    /// Finds the side effect among all of the steps of the Xtx.
+    fn find_side_effect(
+        local_ctx: &LocalXtxCtx<T>,
+        sfx_id: SideEffectId<T>,
+    ) -> Option<&SideEffect<T::AccountId, T::BlockNumber, EscrowedBalanceOf<T, T::Escrowed>>> {
+        local_ctx
+            .full_side_effects
+            .iter()
+            .flatten()
+            .map(|fsx| &fsx.input)
+            .find(|side_effect| side_effect.generate_id::<SystemHashing<T>>() == sfx_id)
+    }
 
-/// Payload used by this example crate to hold price
-/// data required to submit a transaction.
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
-pub struct Payload<Public, BlockNumber> {
-    block_number: BlockNumber,
-    public: Public,
-}
+    /// Finds the side effect among the side effects of the current step of the Xtx.
+    fn find_current_step_side_effect(
+        local_ctx: &LocalXtxCtx<T>,
+        sfx_id: SideEffectId<T>,
+    ) -> Option<&SideEffect<T::AccountId, T::BlockNumber, EscrowedBalanceOf<T, T::Escrowed>>> {
+        local_ctx
+            .full_side_effects
+            .get(local_ctx.xtx.steps_cnt.0 as usize)?
+            .iter()
+            .map(|fsx| &fsx.input)
+            .find(|side_effect| side_effect.generate_id::<SystemHashing<T>>() == sfx_id)
+    }
 
-impl<T: SigningTypes> SignedPayload<T> for Payload<T::Public, T::BlockNumber> {
-    fn public(&self) -> T::Public {
-        self.public.clone()
+    /// Number of side effects across all of the steps of the Xtx, which its calls are weighed by.
+    fn side_effects_count(local_ctx: &LocalXtxCtx<T>) -> u32 {
+        local_ctx
+            .full_side_effects
+            .iter()
+            .map(|step| step.len() as u32)
+            .sum()
+    }
+
+    /// Key of the side effect in the open market - its target gateway and kind.
+    fn open_side_effect_key(
+        local_ctx: &LocalXtxCtx<T>,
+        sfx_id: SideEffectId<T>,
+    ) -> Option<(ChainId, [u8; 4])> {
+        Self::find_side_effect(local_ctx, sfx_id).and_then(|side_effect| {
+            let kind: [u8; 4] = side_effect.encoded_action.get(0..4)?.try_into().ok()?;
+            Some((side_effect.target, kind))
+        })
+    }
+
+    /// Fails unless the account is the executioner the side effect enforces, if any.
+    fn ensure_enforced_executioner(
+        side_effect: &SideEffect<T::AccountId, T::BlockNumber, EscrowedBalanceOf<T, T::Escrowed>>,
+        executioner: &T::AccountId,
+    ) -> Result<(), Error<T>> {
+        match &side_effect.enforce_executioner {
+            Some(enforced_executioner) if enforced_executioner != executioner =>
+                Err(Error::<T>::EnforcedExecutionerMismatch),
+            _ => Ok(()),
+        }
+    }
+
+    fn list_open_side_effect(
+        local_ctx: &LocalXtxCtx<T>,
+        sfx_id: SideEffectId<T>,
+        insurance_deposit: &InsuranceDeposit<
+            T::AccountId,
+            T::BlockNumber,
+            EscrowedBalanceOf<T, T::Escrowed>,
+        >,
+    ) {
+        if let Some(key) = Self::open_side_effect_key(local_ctx, sfx_id) {
+            <Self as Store>::OpenSideEffects::insert(
+                key,
+                sfx_id,
+                OpenSideEffect {
+                    xtx_id: local_ctx.xtx_id,
+                    prize: insurance_deposit.reward,
+                    insurance: insurance_deposit.insurance,
+                    timeouts_at: local_ctx.xtx.timeouts_at,
+                    reserved_for: Self::find_side_effect(local_ctx, sfx_id)
+                        .and_then(|side_effect| side_effect.enforce_executioner.clone()),
+                },
+            );
+        }
+    }
+
+    fn delist_open_side_effect(local_ctx: &LocalXtxCtx<T>, sfx_id: SideEffectId<T>) {
+        if let Some(key) = Self::open_side_effect_key(local_ctx, sfx_id) {
+            <Self as Store>::OpenSideEffects::remove(key, sfx_id);
+        }
+    }
+
+    fn bidding_closes_at(
+        local_ctx: &LocalXtxCtx<T>,
+        sfx_id: SideEffectId<T>,
+    ) -> Result<T::BlockNumber, Error<T>> {
+        local_ctx
+            .insurance_deposits
+            .iter()
+            .find(|(id, _)| *id == sfx_id)
+            .map(|(_, insurance_deposit)| {
+                insurance_deposit.requested_at + T::SideEffectBiddingPeriod::get()
+            })
+            .ok_or(Error::<T>::InsuranceBondNotRequired)
     }
-}
 
-impl<T: Config> Pallet<T> {
     fn setup(
         current_status: CircuitStatus,
         requester: &T::AccountId,
@@ -997,7 +1745,8 @@ impl<T: Config> Pallet<T> {
             | CircuitStatus::PendingExecution
             | CircuitStatus::PendingInsurance
             | CircuitStatus::Finished
-            | CircuitStatus::RevertTimedOut => {
+            | CircuitStatus::RevertTimedOut
+            | CircuitStatus::RevertMisbehaviour => {
                 if let Some(id) = xtx_id {
                     let xtx = <Self as Store>::XExecSignals::get(id)
                         .ok_or(Error::<T>::SetupFailedUnknownXtx)?;
@@ -1015,20 +1764,21 @@ impl<T: Config> Pallet<T> {
                     let insurance_deposits = <Self as Store>::XtxInsuranceLinks::get(id)
                         .iter()
                         .map(|&se_id| {
-                            (
-                                se_id,
-                                <Self as Store>::InsuranceDeposits::get(id, se_id)
-                                    .expect("Should not be state inconsistency"),
-                            )
+                            <Self as Store>::InsuranceDeposits::get(id, se_id)
+                                .map(|insurance_deposit| (se_id, insurance_deposit))
+                                .ok_or(Error::<T>::SetupFailedXtxStorageArtifactsNotFound)
                         })
-                        .collect::<Vec<(
-                            SideEffectId<T>,
-                            InsuranceDeposit<
-                                T::AccountId,
-                                T::BlockNumber,
-                                EscrowedBalanceOf<T, T::Escrowed>,
-                            >,
-                        )>>();
+                        .collect::<Result<
+                            Vec<(
+                                SideEffectId<T>,
+                                InsuranceDeposit<
+                                    T::AccountId,
+                                    T::BlockNumber,
+                                    EscrowedBalanceOf<T, T::Escrowed>,
+                                >,
+                            )>,
+                            Error<T>,
+                        >>()?;
 
                     let full_side_effects = <Self as Store>::FullSideEffects::get(id)
                         .ok_or(Error::<T>::SetupFailedXtxStorageArtifactsNotFound)?;
@@ -1204,6 +1954,7 @@ impl<T: Config> Pallet<T> {
                     >(
                         local_ctx.xtx_id, *side_effect_id, insurance_deposit.clone()
                     );
+                    Self::list_open_side_effect(local_ctx, *side_effect_id, insurance_deposit);
                     ids_with_insurance.push(*side_effect_id);
                 }
                 <XtxInsuranceLinks<T>>::insert::<XExecSignalId<T>, Vec<SideEffectId<T>>>(
@@ -1218,6 +1969,7 @@ impl<T: Config> Pallet<T> {
                     local_ctx.xtx_id,
                     local_ctx.xtx.timeouts_at,
                 );
+                <XtxTimeoutsAt<T>>::insert(local_ctx.xtx.timeouts_at, local_ctx.xtx_id, ());
 
                 <XExecSignals<T>>::insert::<
                     XExecSignalId<T>,
@@ -1258,6 +2010,22 @@ impl<T: Config> Pallet<T> {
                     Some(local_ctx.full_side_effects.clone()),
                 )
             },
+            CircuitStatus::RevertMisbehaviour => {
+                // Keep the misbehaviour recorded in the confirmation of the side effect
+                <Self as Store>::FullSideEffects::mutate(local_ctx.xtx_id, |x| {
+                    *x = Some(local_ctx.full_side_effects.clone())
+                });
+
+                <Self as Store>::XExecSignals::mutate(local_ctx.xtx_id, |x| {
+                    *x = Some(local_ctx.xtx.clone())
+                });
+
+                <Self as Store>::ActiveXExecSignalsTimingLinks::remove(local_ctx.xtx_id);
+                (
+                    Some(local_ctx.xtx.clone()),
+                    Some(local_ctx.full_side_effects.clone()),
+                )
+            },
             CircuitStatus::Ready
             | CircuitStatus::Bonded
             | CircuitStatus::PendingExecution
@@ -1348,14 +2116,65 @@ impl<T: Config> Pallet<T> {
 
     fn kill(local_ctx: &mut LocalXtxCtx<T>, cause: CircuitStatus) {
         local_ctx.xtx.status = cause.clone();
-        Optimistic::<T>::try_slash(local_ctx);
 
-        Self::square_up(local_ctx, None, None)
-            .expect("Expect Revert and RevertKill options to square up to be infallible");
+        for sfx_id in local_ctx
+            .insurance_deposits
+            .iter()
+            .map(|(sfx_id, _)| *sfx_id)
+            .collect::<Vec<SideEffectId<T>>>()
+        {
+            Self::delist_open_side_effect(local_ctx, sfx_id);
+            <Self as Store>::SideEffectBids::remove(local_ctx.xtx_id, sfx_id);
+        }
+        Self::close_challenge_windows(local_ctx);
+
+        if let Err(err) = Self::square_up(local_ctx, None, None) {
+            log::error!(
+                target: "runtime::circuit",
+                "square up failed while killing xtx {:?} -- {:?}",
+                local_ctx.xtx_id,
+                err
+            );
+        }
 
         Self::apply(local_ctx, None, None, (cause.clone(), cause));
     }
 
+    /// Closes the challenge windows of the optimistic confirmations of the Xtx.
+    fn close_challenge_windows(local_ctx: &LocalXtxCtx<T>) {
+        for sfx_id in local_ctx
+            .full_side_effects
+            .iter()
+            .flatten()
+            .map(|fsx| fsx.input.generate_id::<SystemHashing<T>>())
+        {
+            <Self as Store>::ChallengeableConfirmations::remove(local_ctx.xtx_id, sfx_id);
+        }
+    }
+
+    /// Removes the Xtx and everything linked to it from storage.
+    fn drop_xtx(local_ctx: &LocalXtxCtx<T>) {
+        for side_effect_id in local_ctx
+            .full_side_effects
+            .iter()
+            .flatten()
+            .map(|fsx| fsx.input.generate_id::<SystemHashing<T>>())
+            .collect::<Vec<SideEffectId<T>>>()
+        {
+            Self::delist_open_side_effect(local_ctx, side_effect_id);
+            <Self as Store>::InsuranceDeposits::remove(local_ctx.xtx_id, side_effect_id);
+            <Self as Store>::SideEffectBids::remove(local_ctx.xtx_id, side_effect_id);
+            <Self as Store>::LocalSideEffectToXtxIdLinks::remove(side_effect_id);
+        }
+
+        Self::close_challenge_windows(local_ctx);
+        <Self as Store>::XtxInsuranceLinks::remove(local_ctx.xtx_id);
+        <Self as Store>::FullSideEffects::remove(local_ctx.xtx_id);
+        <Self as Store>::LocalXtxStates::remove(local_ctx.xtx_id);
+        <Self as Store>::ActiveXExecSignalsTimingLinks::remove(local_ctx.xtx_id);
+        <Self as Store>::XExecSignals::remove(local_ctx.xtx_id);
+    }
+
     fn square_up(
         local_ctx: &mut LocalXtxCtx<T>,
         maybe_requester_charge: Option<<T as frame_system::Config>::AccountId>,
@@ -1401,10 +2220,8 @@ impl<T: Config> Pallet<T> {
             },
             // todo: make sure callable once
             // todo: distinct between RevertTimedOut to iterate over all steps vs single step for Revert
-            CircuitStatus::RevertTimedOut
-            | CircuitStatus::Reverted
-            | CircuitStatus::RevertMisbehaviour => {
-                Optimistic::<T>::try_slash(local_ctx);
+            CircuitStatus::RevertTimedOut | CircuitStatus::Reverted | CircuitStatus::RevertKill => {
+                Optimistic::<T>::try_slash(local_ctx)?;
                 for fsx in Self::get_current_step_fsx(local_ctx).iter() {
                     let charge_id = fsx.input.generate_id::<SystemHashing<T>>();
                     <T as Config>::AccountManager::try_finalize(
@@ -1415,6 +2232,22 @@ impl<T: Config> Pallet<T> {
                     );
                 }
             },
+            // The misbehaving executor is slashed once proven, others in the step are released
+            CircuitStatus::RevertMisbehaviour => {
+                // Nothing left to release if all of the steps have already finished
+                if local_ctx.xtx.steps_cnt.0 < local_ctx.xtx.steps_cnt.1 {
+                    Optimistic::<T>::try_release(local_ctx);
+                    for fsx in Self::get_current_step_fsx(local_ctx).iter() {
+                        let charge_id = fsx.input.generate_id::<SystemHashing<T>>();
+                        <T as Config>::AccountManager::try_finalize(
+                            charge_id,
+                            Outcome::Revert,
+                            None,
+                            None,
+                        );
+                    }
+                }
+            },
             CircuitStatus::Finished | CircuitStatus::FinishedAllSteps => {
                 Optimistic::<T>::try_unbond(local_ctx)?;
                 for fsx in Self::get_current_step_fsx(local_ctx).iter() {
@@ -1440,19 +2273,37 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
-    fn authorize(
-        origin: OriginFor<T>,
-        role: CircuitRole,
-    ) -> Result<T::AccountId, sp_runtime::traits::BadOrigin> {
+    fn authorize(origin: OriginFor<T>, role: CircuitRole) -> Result<T::AccountId, DispatchError> {
         match role {
-            CircuitRole::Requester | CircuitRole::ContractAuthor => ensure_signed(origin),
-            // ToDo: Handle active Relayer authorisation
-            CircuitRole::Relayer => ensure_signed(origin),
-            // ToDo: Handle bonded Executor authorisation
-            CircuitRole::Executor => ensure_signed(origin),
-            // ToDo: Handle other CircuitRoles
-            _ => unimplemented!(),
+            CircuitRole::Requester | CircuitRole::ContractAuthor => Ok(ensure_signed(origin)?),
+            CircuitRole::Relayer => {
+                let relayer = ensure_signed(origin)?;
+                ensure!(
+                    <Relayers<T>>::contains_key(&relayer),
+                    Error::<T>::UnauthorizedRelayer
+                );
+                Ok(relayer)
+            },
+            CircuitRole::Executor => {
+                let executor = ensure_signed(origin)?;
+                ensure!(
+                    Self::is_authorized_executor(&executor),
+                    Error::<T>::UnauthorizedExecutor
+                );
+                Ok(executor)
+            },
+            _ => Err(Error::<T>::UnsupportedRole.into()),
+        }
+    }
+
+    /// Executors either belong to the current active set or have bonded at least
+    /// the minimum candidate bond. Non-candidates report a zero collateral bond.
+    fn is_authorized_executor(executor: &T::AccountId) -> bool {
+        if T::Executors::active_set().contains(executor) {
+            return true
         }
+        let bond = T::Executors::collateral_bond(executor);
+        !bond.is_zero() && bond >= T::Executors::min_candidate_bond()
     }
 
     fn validate(
@@ -1463,7 +2314,8 @@ impl<T: Config> Pallet<T> {
         >],
         local_ctx: &mut LocalXtxCtx<T>,
         requester: &T::AccountId,
-        _sequential: bool,
+        sequential: bool,
+        steps: Option<Vec<u32>>,
     ) -> Result<(), &'static str> {
         // ToDo: Consuder burn_validation_fee
         // Fees::<T>::burn_validation_fee(requester, side_effects)?;
@@ -1474,6 +2326,14 @@ impl<T: Config> Pallet<T> {
 
         for side_effect in side_effects.iter() {
             let gateway_abi = <T as Config>::Xdns::get_abi(side_effect.target)?;
+
+            // Native ETH transfers emit no logs, so their execution can't be proven
+            if side_effect.encoded_action.starts_with(b"tran")
+                && <T as Config>::Xdns::get_gateway_vendor(&side_effect.target)?
+                    == GatewayVendor::Ethereum
+            {
+                return Err("Native transfers can't be confirmed on Ethereum gateways")
+            }
             let allowed_side_effects =
                 <T as Config>::Xdns::allowed_side_effects(&side_effect.target);
 
@@ -1560,7 +2420,24 @@ impl<T: Config> Pallet<T> {
             }
         }
 
-        // Circuit's automatic side effect ordering: execute escrowed asap, then line up optimistic ones
+        local_ctx.full_side_effects = match steps {
+            Some(steps) => Self::group_into_steps(full_side_effects, steps)?,
+            // Xtx always has at least one step, even if it's an empty one
+            None if sequential && !full_side_effects.is_empty() =>
+                full_side_effects.into_iter().map(|fse| vec![fse]).collect(),
+            None => Self::sort_into_steps(full_side_effects),
+        };
+
+        Ok(())
+    }
+
+    /// Circuit's automatic side effect ordering: execute escrowed asap, then line up optimistic ones
+    fn sort_into_steps(
+        mut full_side_effects: Vec<
+            FullSideEffect<T::AccountId, T::BlockNumber, EscrowedBalanceOf<T, T::Escrowed>>,
+        >,
+    ) -> Vec<Vec<FullSideEffect<T::AccountId, T::BlockNumber, EscrowedBalanceOf<T, T::Escrowed>>>>
+    {
         full_side_effects.sort_by(|a, b| b.security_lvl.partial_cmp(&a.security_lvl).unwrap());
 
         let mut full_side_effects_steps: Vec<
@@ -1583,14 +2460,62 @@ impl<T: Config> Pallet<T> {
             }
         }
 
-        local_ctx.full_side_effects = full_side_effects_steps.clone();
+        full_side_effects_steps
+    }
 
-        Ok(())
+    /// Puts each side effect into the step at its index in `steps`, keeping the submitted order
+    /// within the steps. Every step needs at least one side effect and, same as with the automatic
+    /// ordering, there only can be max 1 dirty side effect at each step.
+    fn group_into_steps(
+        full_side_effects: Vec<
+            FullSideEffect<T::AccountId, T::BlockNumber, EscrowedBalanceOf<T, T::Escrowed>>,
+        >,
+        steps: Vec<u32>,
+    ) -> Result<
+        Vec<Vec<FullSideEffect<T::AccountId, T::BlockNumber, EscrowedBalanceOf<T, T::Escrowed>>>>,
+        &'static str,
+    > {
+        if steps.len() != full_side_effects.len() {
+            return Err("Explicit steps must assign each side effect to a step")
+        }
+        // Counted in u64, since the last step + 1 may overflow usize on 32-bit targets
+        let steps_cnt = steps
+            .iter()
+            .max()
+            .map_or(0, |last_step| *last_step as u64 + 1);
+        if steps_cnt > steps.len() as u64 {
+            return Err("Explicit steps can't be left empty")
+        }
+        let steps_cnt = steps_cnt as usize;
+
+        let mut full_side_effects_steps: Vec<
+            Vec<FullSideEffect<T::AccountId, T::BlockNumber, EscrowedBalanceOf<T, T::Escrowed>>>,
+        > = vec![vec![]; steps_cnt.max(1)];
+
+        for (fse, step) in full_side_effects.into_iter().zip(steps) {
+            full_side_effects_steps[step as usize].push(fse);
+        }
+
+        for step in full_side_effects_steps.iter() {
+            if step.is_empty() && steps_cnt > 0 {
+                return Err("Explicit steps can't be left empty")
+            }
+            if step
+                .iter()
+                .filter(|fse| fse.security_lvl == SecurityLvl::Dirty)
+                .count()
+                > 1
+            {
+                return Err("Explicit steps can only have max 1 dirty side effect each")
+            }
+        }
+
+        Ok(full_side_effects_steps)
     }
 
     fn confirm(
         local_ctx: &mut LocalXtxCtx<T>,
-        _relayer: &T::AccountId,
+        relayer: &T::AccountId,
         side_effect: &SideEffect<
             <T as frame_system::Config>::AccountId,
             <T as frame_system::Config>::BlockNumber,
@@ -1601,7 +2526,8 @@ impl<T: Config> Pallet<T> {
             <T as frame_system::Config>::BlockNumber,
             EscrowedBalanceOf<T, <T as Config>::Escrowed>,
         >,
-    ) -> Result<(), &'static str> {
+        maybe_inclusion_proof: Option<(Vec<Vec<u8>>, Vec<u8>)>,
+    ) -> Result<Option<ConfirmationOutcome>, &'static str> {
         fn confirm_order<T: Config>(
             side_effect: &SideEffect<
                 <T as frame_system::Config>::AccountId,
@@ -1655,6 +2581,8 @@ impl<T: Config> Pallet<T> {
             }
         }
 
+        Self::ensure_enforced_executioner(side_effect, &confirmation.executioner)?;
+
         let mut side_effect_id: [u8; 4] = [0, 0, 0, 0];
         side_effect_id.copy_from_slice(&side_effect.encoded_action[0..4]);
 
@@ -1665,41 +2593,147 @@ impl<T: Config> Pallet<T> {
             &mut local_ctx.full_side_effects[local_ctx.xtx.steps_cnt.0 as usize],
         )?;
         log::debug!("Order confirmed!");
+        let is_execution_proven = maybe_inclusion_proof.is_some();
         // confirm the payload is included in the specified block, and return the SideEffect params as defined in XDNS.
         // this could be multiple events!
-        let (params, source) = <T as Config>::Portal::confirm_and_decode_payload_params(
-            side_effect.target,
-            fsx.submission_target_height,
-            confirmation.inclusion_data.clone(),
-            side_effect_id,
-        )
+        let (params, source) = match maybe_inclusion_proof {
+            Some((inclusion_proof, block_hash)) => <T as Config>::Portal::confirm_event_inclusion(
+                side_effect.target,
+                confirmation.inclusion_data.clone(),
+                fsx.submission_target_height.clone(),
+                inclusion_proof,
+                block_hash,
+                side_effect_id,
+            ),
+            // Escrowed side effects and XBI results are confirmed by the Circuit itself.
+            None if fsx.security_lvl == SecurityLvl::Escrowed || *relayer == Self::account_id() =>
+                <T as Config>::Portal::confirm_and_decode_payload_params(
+                    side_effect.target,
+                    fsx.submission_target_height.clone(),
+                    confirmation.inclusion_data.clone(),
+                    side_effect_id,
+                ),
+            None => return Err("SideEffect confirmation requires inclusion proof and block hash"),
+        }
         .map_err(|_| "SideEffect confirmation failed!")?;
-        // ToDo: handle misbehaviour
         log::debug!("SFX confirmation params: {:?}", params);
 
+        match Self::check_execution(
+            &local_ctx.local_state,
+            side_effect,
+            fsx.security_lvl,
+            &confirmation.executioner,
+            params,
+            source,
+        )? {
+            None => {
+                log::debug!("confirmation plug ok");
+                Ok(None)
+            },
+            // Only the proven execution of the bonded executor is a misbehaviour worth slashing
+            Some(misbehaviour)
+                if is_execution_proven
+                    && local_ctx.insurance_deposits.iter().any(|(id, deposit)| {
+                        *id == fsx.input.generate_id::<SystemHashing<T>>()
+                            && deposit.bonded_relayer.as_ref() == Some(&confirmation.executioner)
+                    }) =>
+            {
+                Self::mark_misbehaviour(
+                    local_ctx,
+                    fsx.input.generate_id::<SystemHashing<T>>(),
+                    misbehaviour.clone(),
+                );
+                Ok(Some(misbehaviour))
+            },
+            Some(_) => Err("Execution can't be confirmed."),
+        }
+    }
+
+    /// Checks the params of an execution proven on the target against the side effect.
+    ///
+    /// Returns the misbehaviour if the execution was made by the executioner but does not match
+    /// the side effect, and fails if it can't be attributed to the executioner at all.
+    fn check_execution(
+        local_state: &LocalState,
+        side_effect: &SideEffect<
+            <T as frame_system::Config>::AccountId,
+            <T as frame_system::Config>::BlockNumber,
+            EscrowedBalanceOf<T, T::Escrowed>,
+        >,
+        security_lvl: SecurityLvl,
+        executioner: &T::AccountId,
+        params: Vec<Vec<u8>>,
+        source: Vec<u8>,
+    ) -> Result<Option<ConfirmationOutcome>, &'static str> {
         let mut side_effect_id: [u8; 4] = [0, 0, 0, 0];
         side_effect_id.copy_from_slice(&side_effect.encoded_action[0..4]);
         let side_effect_interface =
-            <T as Config>::Xdns::fetch_side_effect_interface(side_effect_id);
+            <T as Config>::Xdns::fetch_side_effect_interface(side_effect_id)?;
 
         log::debug!("Found SFX interface!");
 
-        confirmation_plug::<T>(
-            &Box::new(side_effect_interface.unwrap()),
+        let received = params.encode();
+        let executed_by_executioner = params.first() == Some(&executioner.encode());
+
+        match confirmation_plug::<T>(
+            &Box::new(side_effect_interface),
             params,
             source,
-            &local_ctx.local_state,
+            local_state,
             Some(
                 side_effect
                     .generate_id::<SystemHashing<T>>()
                     .as_ref()
                     .to_vec(),
             ),
-            fsx.security_lvl,
+            security_lvl,
             <T as Config>::Xdns::get_gateway_security_coordinates(&side_effect.target)?,
-        )
-        .map_err(|_| "Execution can't be confirmed.")?;
-        log::debug!("confirmation plug ok");
+        ) {
+            Ok(_) => Ok(None),
+            Err(_) if executed_by_executioner =>
+                Ok(Some(ConfirmationOutcome::MisbehaviourMalformedValues {
+                    key: side_effect.encoded_action.clone(),
+                    expected: side_effect.encoded_args.encode(),
+                    received,
+                })),
+            Err(_) => Err("Execution can't be confirmed."),
+        }
+    }
+
+    /// Records the misbehaviour as the outcome of the side effect's confirmation.
+    fn mark_misbehaviour(
+        local_ctx: &mut LocalXtxCtx<T>,
+        sfx_id: SideEffectId<T>,
+        misbehaviour: ConfirmationOutcome,
+    ) {
+        if let Some(confirmed) = local_ctx
+            .full_side_effects
+            .iter_mut()
+            .flatten()
+            .find(|fsx| fsx.input.generate_id::<SystemHashing<T>>() == sfx_id)
+            .and_then(|fsx| fsx.confirmed.as_mut())
+        {
+            confirmed.err = Some(misbehaviour);
+        }
+    }
+
+    /// Slashes the executor who misbehaved while executing the side effect
+    /// and reverts the Xtx.
+    fn punish_misbehaviour(
+        local_ctx: &mut LocalXtxCtx<T>,
+        sfx_id: SideEffectId<T>,
+        reporter: &T::AccountId,
+    ) -> Result<(), Error<T>> {
+        let executor = Optimistic::<T>::slash_misbehaviour(local_ctx, sfx_id)?;
+
+        Self::kill(local_ctx, CircuitStatus::RevertMisbehaviour);
+
+        Self::deposit_event(Event::ExecutorMisbehaviourSlashed(
+            local_ctx.xtx_id,
+            sfx_id,
+            executor,
+            reporter.clone(),
+        ));
 
         Ok(())
     }
@@ -1798,6 +2832,207 @@ impl<T: Config> Pallet<T> {
         processed_weight
     }
 
+    /// Reverts the Xtx which are due by their timeout, as long as they fit into `max_weight` and
+    /// `DeletionQueueLimit`.
+    ///
+    /// The sweep runs every `XtxTimeoutCheckInterval` blocks, reading the Xtx timing out at each
+    /// block since the last sweep. Due Xtx left behind are picked up in the next block, and Xtx
+    /// which fail to revert stay active and are retried at the next interval.
+    pub(crate) fn process_xtx_timeouts(n: T::BlockNumber, max_weight: Weight) -> Weight {
+        let db_weight = T::DbWeight::get();
+        // Carried over flag
+        let mut processed_weight = db_weight.reads(1 as Weight);
+
+        if n % T::XtxTimeoutCheckInterval::get() != T::BlockNumber::from(0u8)
+            && !<XtxTimeoutsCarriedOver<T>>::get()
+        {
+            return processed_weight
+        }
+
+        // worst case from setup, slashing and square up, apply has 2 writes
+        let revert_weight = db_weight.reads_writes(9 as Weight, 9 as Weight);
+        let now = frame_system::Pallet::<T>::block_number();
+        let mut block = <NextXtxTimeoutsBlock<T>>::get();
+        let mut due: Vec<(T::BlockNumber, XExecSignalId<T>)> = vec![];
+        let mut carried_over = false;
+        processed_weight = processed_weight.saturating_add(db_weight.reads(1 as Weight));
+
+        // Collect first, since reverting removes the timing links
+        'blocks: while block <= now {
+            processed_weight = processed_weight.saturating_add(db_weight.reads(1 as Weight));
+            if processed_weight > max_weight {
+                carried_over = true;
+                break
+            }
+            for xtx_id in <XtxTimeoutsAt<T>>::iter_key_prefix(block) {
+                if due.len() as u32 >= T::DeletionQueueLimit::get()
+                    || processed_weight.saturating_add(revert_weight) > max_weight
+                {
+                    carried_over = true;
+                    break 'blocks
+                }
+                processed_weight = processed_weight.saturating_add(revert_weight);
+                due.push((block, xtx_id));
+            }
+            block = block.saturating_add(T::BlockNumber::from(1u8));
+        }
+
+        for (timeout_at, xtx_id) in due {
+            <XtxTimeoutsAt<T>>::remove(timeout_at, xtx_id);
+
+            // Finished in time
+            if !<ActiveXExecSignalsTimingLinks<T>>::contains_key(xtx_id) {
+                continue
+            }
+
+            match Self::setup(
+                CircuitStatus::RevertTimedOut,
+                &Self::account_id(),
+                Zero::zero(),
+                Some(xtx_id),
+            ) {
+                Ok(mut local_xtx_ctx) => {
+                    Self::kill(&mut local_xtx_ctx, CircuitStatus::RevertTimedOut);
+
+                    Self::emit(
+                        local_xtx_ctx.xtx_id,
+                        Some(local_xtx_ctx.xtx),
+                        &Self::account_id(),
+                        &vec![],
+                        None,
+                    );
+                },
+                Err(err) => {
+                    log::error!(
+                        target: "runtime::circuit",
+                        "could not revert timed out xtx {:?} -- {:?}",
+                        xtx_id,
+                        err
+                    );
+                    // Keep the Xtx active and retry it at the next interval
+                    <XtxTimeoutsAt<T>>::insert(
+                        now.saturating_add(T::XtxTimeoutCheckInterval::get()),
+                        xtx_id,
+                        (),
+                    );
+                },
+            }
+        }
+
+        <NextXtxTimeoutsBlock<T>>::put(block);
+        <XtxTimeoutsCarriedOver<T>>::put(carried_over);
+
+        processed_weight.saturating_add(db_weight.writes(2 as Weight))
+    }
+
+    /// Registers the bootstrap relayers, for chains started before relayers had to be registered.
+    pub(crate) fn register_bootstrap_relayers() -> Weight {
+        let relayers = T::BootstrapRelayers::get();
+        for relayer in &relayers {
+            <Relayers<T>>::insert(relayer, ());
+        }
+        T::DbWeight::get().writes(relayers.len() as Weight)
+    }
+
+    /// Indexes the active Xtx by the block they time out at, for Xtx created before timeouts were
+    /// indexed. The timeout sweep starts at the current block and reverts overdue Xtx first.
+    pub(crate) fn index_xtx_timeouts() -> Weight {
+        let now = frame_system::Pallet::<T>::block_number();
+        let mut indexed: Weight = 0;
+
+        for (xtx_id, timeout_at) in <ActiveXExecSignalsTimingLinks<T>>::iter() {
+            <XtxTimeoutsAt<T>>::insert(timeout_at.max(now), xtx_id, ());
+            indexed += 1;
+        }
+
+        <NextXtxTimeoutsBlock<T>>::put(now);
+
+        T::DbWeight::get().reads_writes(indexed, indexed.saturating_add(1))
+    }
+
+    /// Sets the Xtx deadline. Requested timeouts must stay within `XtxTimeoutMin` and `XtxTimeoutMax`,
+    /// otherwise the default one gives the slowest of the targets extra time to finalize.
+    fn set_timeout(
+        local_ctx: &mut LocalXtxCtx<T>,
+        side_effects: &[SideEffect<
+            T::AccountId,
+            T::BlockNumber,
+            EscrowedBalanceOf<T, T::Escrowed>,
+        >],
+        timeout: Option<T::BlockNumber>,
+    ) -> Result<(), Error<T>> {
+        let timeout = match timeout {
+            Some(timeout) => {
+                if timeout < T::XtxTimeoutMin::get() || timeout > T::XtxTimeoutMax::get() {
+                    return Err(Error::<T>::InvalidXtxTimeout)
+                }
+                timeout
+            },
+            None => Self::default_timeout(side_effects),
+        };
+
+        let timeouts_at = frame_system::Pallet::<T>::block_number() + timeout;
+        if timeouts_at != local_ctx.xtx.timeouts_at {
+            local_ctx.xtx.timeouts_at = timeouts_at;
+            // Xtx id commits to the deadline
+            local_ctx.xtx_id = local_ctx.xtx.generate_id::<T>();
+        }
+
+        Ok(())
+    }
+
+    /// `XtxTimeoutDefault` extended by the finality delay of the slowest target recorded in XDNS,
+    /// kept within `XtxTimeoutMin` and `XtxTimeoutMax`.
+    pub fn default_timeout(
+        side_effects: &[SideEffect<
+            T::AccountId,
+            T::BlockNumber,
+            EscrowedBalanceOf<T, T::Escrowed>,
+        >],
+    ) -> T::BlockNumber {
+        let slowest_finality_delay = side_effects
+            .iter()
+            .map(|side_effect| T::Xdns::get_gateway_finality_delay(&side_effect.target))
+            .max()
+            .unwrap_or_else(Zero::zero);
+
+        T::XtxTimeoutDefault::get()
+            .saturating_add(slowest_finality_delay)
+            .max(T::XtxTimeoutMin::get())
+            .min(T::XtxTimeoutMax::get())
+    }
+
+    /// Sets the earliest block each step can start at. The schedule needs an entry per step,
+    /// must not go backwards and has to open all of the steps before the Xtx times out.
+    fn schedule_steps(
+        local_ctx: &mut LocalXtxCtx<T>,
+        delay_steps_at: Option<Vec<T::BlockNumber>>,
+    ) -> Result<(), Error<T>> {
+        if let Some(delays) = &delay_steps_at {
+            if delays.len() != local_ctx.full_side_effects.len()
+                || delays.windows(2).any(|pair| pair[0] > pair[1])
+                || delays.iter().any(|at| *at >= local_ctx.xtx.timeouts_at)
+            {
+                return Err(Error::<T>::InvalidDelayedStepsSchedule)
+            }
+        }
+        local_ctx.xtx.delay_steps_at = delay_steps_at;
+
+        Ok(())
+    }
+
+    /// Scheduled steps stay closed for execution and confirmations until their block arrives.
+    fn ensure_current_step_open(local_ctx: &LocalXtxCtx<T>) -> Result<(), Error<T>> {
+        if local_ctx.xtx.is_step_open(
+            local_ctx.xtx.steps_cnt.0,
+            frame_system::Pallet::<T>::block_number(),
+        ) {
+            Ok(())
+        } else {
+            Err(Error::<T>::StepNotOpenYet)
+        }
+    }
+
     pub(self) fn get_current_step_fsx(
         local_ctx: &mut LocalXtxCtx<T>,
     ) -> &Vec<
@@ -2023,6 +3258,7 @@ impl<T: Config> Pallet<T> {
             &Self::account_id(),
             &fsx.input,
             &confirmation,
+            None,
         )
         .map_err(|_e| Error::<T>::XBIExitFailedOnSFXConfirmation)?;
         Ok(())
@@ -1,6 +1,8 @@
 use crate::{pallet::Error, *};
 
-use frame_support::traits::ReservableCurrency;
+use frame_support::traits::{
+    BalanceStatus, Currency, ExistenceRequirement::AllowDeath, ReservableCurrency,
+};
 use sp_std::marker::PhantomData;
 use t3rn_primitives::transfers::EscrowedBalanceOf;
 
@@ -17,21 +19,10 @@ impl<T: Config> Optimistic<T> {
         InsuranceDeposit<T::AccountId, T::BlockNumber, EscrowedBalanceOf<T, T::Escrowed>>,
         Error<T>,
     > {
-        let total_xtx_step_optimistic_rewards_of_others = crate::Pallet::<T>::get_fsx_total_rewards(
-            &crate::Pallet::<T>::get_current_step_fsx_by_security_lvl(
-                local_ctx,
-                SecurityLvl::Optimistic,
-            )
-            .into_iter()
-            .filter(|fsx| fsx.input.generate_id::<SystemHashing<T>>() != sfx_id)
-            .collect::<Vec<
-                FullSideEffect<
-                    <T as frame_system::Config>::AccountId,
-                    <T as frame_system::Config>::BlockNumber,
-                    EscrowedBalanceOf<T, <T as Config>::Escrowed>,
-                >,
-            >>(),
-        );
+        Self::ensure_enforced_executioner(local_ctx, sfx_id, executor)?;
+
+        let total_xtx_step_optimistic_rewards_of_others =
+            Self::get_step_rewards_of_others(local_ctx, sfx_id);
         let mut insurance_deposit = Self::get_insurance_deposit_mutable_ref(local_ctx, sfx_id)?;
 
         <<T as Config>::Escrowed as EscrowTrait<T>>::Currency::reserve(
@@ -54,6 +45,141 @@ impl<T: Config> Optimistic<T> {
         Ok(insurance_deposit.clone())
     }
 
+    /// Places the bid of the executor to execute the side effect for less than its prize.
+    ///
+    /// The lowest bid holds the bond slot of the insurance deposit until bidding closes,
+    /// with ties going to the executor with the higher collateral bond.
+    /// The insurance and bond of the outbid executor are released.
+    pub fn bid_4_sfx(
+        executor: &T::AccountId,
+        local_ctx: &mut LocalXtxCtx<T>,
+        sfx_id: SideEffectId<T>,
+        bid: EscrowedBalanceOf<T, T::Escrowed>,
+        maybe_best_bid: Option<EscrowedBalanceOf<T, T::Escrowed>>,
+    ) -> Result<
+        InsuranceDeposit<T::AccountId, T::BlockNumber, EscrowedBalanceOf<T, T::Escrowed>>,
+        Error<T>,
+    > {
+        Self::ensure_enforced_executioner(local_ctx, sfx_id, executor)?;
+
+        let total_xtx_step_optimistic_rewards_of_others =
+            Self::get_step_rewards_of_others(local_ctx, sfx_id);
+        let mut insurance_deposit = Self::get_insurance_deposit_mutable_ref(local_ctx, sfx_id)?;
+
+        if insurance_deposit.status == CircuitStatus::Bonded {
+            return Err(Error::<T>::InsuranceBondAlreadyDeposited)
+        }
+        if bid > insurance_deposit.reward {
+            return Err(Error::<T>::BidAboveMaxPrize)
+        }
+
+        let maybe_outbid_executor = insurance_deposit.bonded_relayer.clone();
+        if let Some(best_bidder) = &maybe_outbid_executor {
+            let best_bid = maybe_best_bid.unwrap_or(insurance_deposit.reward);
+            let outbids = bid < best_bid
+                || (bid == best_bid
+                    && <T as Config>::Executors::collateral_bond(executor)
+                        > <T as Config>::Executors::collateral_bond(best_bidder));
+            if !outbids {
+                return Err(Error::<T>::BidNotCompetitive)
+            }
+        }
+
+        <<T as Config>::Escrowed as EscrowTrait<T>>::Currency::reserve(
+            executor,
+            insurance_deposit.insurance,
+        )
+        .map_err(|_e| Error::<T>::InsuranceBondTooLow)?;
+
+        <T as Config>::Executors::reserve_bond(
+            executor,
+            total_xtx_step_optimistic_rewards_of_others,
+        )
+        .map_err(|_e| Error::<T>::InsuranceBondTooLow)?;
+
+        if let Some(outbid_executor) = &maybe_outbid_executor {
+            <<T as Config>::Escrowed as EscrowTrait<T>>::Currency::unreserve(
+                outbid_executor,
+                insurance_deposit.insurance,
+            );
+            <T as Config>::Executors::unreserve_bond(
+                outbid_executor,
+                insurance_deposit.reserved_bond,
+            );
+        }
+
+        insurance_deposit.bonded_relayer = Some(executor.clone());
+        insurance_deposit.reserved_bond = total_xtx_step_optimistic_rewards_of_others;
+
+        Ok(insurance_deposit.clone())
+    }
+
+    /// Bonds the insurance deposit to the winner of the bidding for the side effect,
+    /// who's rewarded with the winning bid instead of the prize. Returns the prize.
+    ///
+    /// Once the grace period is over any executor can take over the slot of a winner who never
+    /// bonded, at the winning bid. The insurance and bond of the winner are then released.
+    pub fn close_bidding_4_sfx(
+        executor: &T::AccountId,
+        local_ctx: &mut LocalXtxCtx<T>,
+        sfx_id: SideEffectId<T>,
+        winning_bid: EscrowedBalanceOf<T, T::Escrowed>,
+        grace_period_over: bool,
+    ) -> Result<
+        (
+            InsuranceDeposit<T::AccountId, T::BlockNumber, EscrowedBalanceOf<T, T::Escrowed>>,
+            EscrowedBalanceOf<T, T::Escrowed>,
+        ),
+        Error<T>,
+    > {
+        Self::ensure_enforced_executioner(local_ctx, sfx_id, executor)?;
+
+        let total_xtx_step_optimistic_rewards_of_others =
+            Self::get_step_rewards_of_others(local_ctx, sfx_id);
+        let mut insurance_deposit = Self::get_insurance_deposit_mutable_ref(local_ctx, sfx_id)?;
+
+        if insurance_deposit.status == CircuitStatus::Bonded {
+            return Err(Error::<T>::InsuranceBondAlreadyDeposited)
+        }
+        if insurance_deposit.bonded_relayer.as_ref() != Some(executor) {
+            if !grace_period_over {
+                return Err(Error::<T>::BidderNotWinning)
+            }
+
+            <<T as Config>::Escrowed as EscrowTrait<T>>::Currency::reserve(
+                executor,
+                insurance_deposit.insurance,
+            )
+            .map_err(|_e| Error::<T>::InsuranceBondTooLow)?;
+
+            <T as Config>::Executors::reserve_bond(
+                executor,
+                total_xtx_step_optimistic_rewards_of_others,
+            )
+            .map_err(|_e| Error::<T>::InsuranceBondTooLow)?;
+
+            if let Some(winning_bidder) = &insurance_deposit.bonded_relayer {
+                <<T as Config>::Escrowed as EscrowTrait<T>>::Currency::unreserve(
+                    winning_bidder,
+                    insurance_deposit.insurance,
+                );
+                <T as Config>::Executors::unreserve_bond(
+                    winning_bidder,
+                    insurance_deposit.reserved_bond,
+                );
+            }
+
+            insurance_deposit.bonded_relayer = Some(executor.clone());
+            insurance_deposit.reserved_bond = total_xtx_step_optimistic_rewards_of_others;
+        }
+
+        let prize = insurance_deposit.reward;
+        insurance_deposit.reward = winning_bid;
+        insurance_deposit.status = CircuitStatus::Bonded;
+
+        Ok((insurance_deposit.clone(), prize))
+    }
+
     pub fn try_unbond(local_ctx: &mut LocalXtxCtx<T>) -> Result<(), Error<T>> {
         let optimistic_fsx_in_step = crate::Pallet::<T>::get_current_step_fsx_by_security_lvl(
             local_ctx,
@@ -87,6 +213,39 @@ impl<T: Config> Optimistic<T> {
         Ok(())
     }
 
+    pub(self) fn ensure_enforced_executioner(
+        local_ctx: &LocalXtxCtx<T>,
+        sfx_id: SideEffectId<T>,
+        executor: &T::AccountId,
+    ) -> Result<(), Error<T>> {
+        match crate::Pallet::<T>::find_side_effect(local_ctx, sfx_id) {
+            Some(side_effect) =>
+                crate::Pallet::<T>::ensure_enforced_executioner(side_effect, executor),
+            None => Err(Error::<T>::InsuranceBondNotRequired),
+        }
+    }
+
+    pub(self) fn get_step_rewards_of_others(
+        local_ctx: &mut LocalXtxCtx<T>,
+        sfx_id: SideEffectId<T>,
+    ) -> EscrowedBalanceOf<T, T::Escrowed> {
+        crate::Pallet::<T>::get_fsx_total_rewards(
+            &crate::Pallet::<T>::get_current_step_fsx_by_security_lvl(
+                local_ctx,
+                SecurityLvl::Optimistic,
+            )
+            .into_iter()
+            .filter(|fsx| fsx.input.generate_id::<SystemHashing<T>>() != sfx_id)
+            .collect::<Vec<
+                FullSideEffect<
+                    <T as frame_system::Config>::AccountId,
+                    <T as frame_system::Config>::BlockNumber,
+                    EscrowedBalanceOf<T, <T as Config>::Escrowed>,
+                >,
+            >>(),
+        )
+    }
+
     pub(self) fn get_insurance_deposit_mutable_ref(
         local_ctx: &mut LocalXtxCtx<T>,
         sfx_id: SideEffectId<T>,
@@ -110,7 +269,7 @@ impl<T: Config> Optimistic<T> {
         }
     }
 
-    pub fn try_slash(local_ctx: &mut LocalXtxCtx<T>) {
+    pub fn try_slash(local_ctx: &mut LocalXtxCtx<T>) -> Result<(), Error<T>> {
         let optimistic_fsx_in_step = crate::Pallet::<T>::get_current_step_fsx_by_security_lvl(
             local_ctx,
             SecurityLvl::Optimistic,
@@ -120,7 +279,10 @@ impl<T: Config> Optimistic<T> {
             &T::AccountId,
             &InsuranceDeposit<T::AccountId, T::BlockNumber, EscrowedBalanceOf<T, T::Escrowed>>,
         )> = vec![];
-        let mut repatriated_executors: Vec<&T::AccountId> = vec![];
+        let mut repatriated_executors: Vec<(
+            &T::AccountId,
+            &InsuranceDeposit<T::AccountId, T::BlockNumber, EscrowedBalanceOf<T, T::Escrowed>>,
+        )> = vec![];
 
         for fsx in &optimistic_fsx_in_step {
             let side_effect_id = fsx.input.generate_id::<SystemHashing<T>>();
@@ -138,7 +300,7 @@ impl<T: Config> Optimistic<T> {
                             .err
                             .is_none()
                     {
-                        repatriated_executors.push(bonded_relayer)
+                        repatriated_executors.push((bonded_relayer, insurance_request))
                     } else {
                         slashed_executors.push((bonded_relayer, insurance_request))
                     }
@@ -147,24 +309,151 @@ impl<T: Config> Optimistic<T> {
         }
 
         for (slash_executor, slash_insurance_request) in slashed_executors.iter() {
-            <<T as Config>::Escrowed as EscrowTrait<T>>::Currency::slash_reserved(
+            <<T as Config>::Escrowed as EscrowTrait<T>>::Currency::repatriate_reserved(
                 slash_executor,
-                slash_insurance_request.insurance,
-            );
-            <<T as Config>::Escrowed as EscrowTrait<T>>::Currency::deposit_creating(
                 &slash_insurance_request.requester,
                 slash_insurance_request.insurance,
-            );
-            <T as Config>::Executors::slash_bond(
-                slash_executor,
-                slash_insurance_request.reserved_bond,
-            );
+                BalanceStatus::Free,
+            )
+            .map_err(|_e| Error::<T>::InsuranceSlashFailed)?;
+
+            // The slashed bond is split between the executors who delivered their side effects,
+            // or goes to the requester if none did.
+            let beneficiaries = repatriated_executors
+                .iter()
+                .map(|(repatriated_executor, _)| *repatriated_executor)
+                .filter(|repatriated_executor| repatriated_executor != slash_executor)
+                .collect::<Vec<_>>();
 
-            for repatriated_executor in &repatriated_executors {
-                <T as Config>::Executors::increase_bond(
-                    repatriated_executor,
+            if beneficiaries.is_empty() {
+                <T as Config>::Executors::slash_bond(
+                    slash_executor,
                     slash_insurance_request.reserved_bond,
+                    &slash_insurance_request.requester,
                 );
+                continue
+            }
+
+            let share = slash_insurance_request.reserved_bond
+                / EscrowedBalanceOf::<T, T::Escrowed>::from(beneficiaries.len() as u32);
+            let mut left = slash_insurance_request.reserved_bond;
+
+            for (i, beneficiary) in beneficiaries.iter().enumerate() {
+                // The last beneficiary also takes the rounding dust
+                let part = if i + 1 == beneficiaries.len() {
+                    left
+                } else {
+                    share
+                };
+                left = left.saturating_sub(part);
+
+                let slashed =
+                    <T as Config>::Executors::slash_bond(slash_executor, part, beneficiary);
+                <T as Config>::Executors::increase_bond(beneficiary, slashed);
+            }
+        }
+
+        // Executors who delivered their side effects get their insurance and bond back
+        for (repatriated_executor, repatriated_insurance_request) in repatriated_executors.iter() {
+            <<T as Config>::Escrowed as EscrowTrait<T>>::Currency::unreserve(
+                repatriated_executor,
+                repatriated_insurance_request.insurance,
+            );
+            <T as Config>::Executors::unreserve_bond(
+                repatriated_executor,
+                repatriated_insurance_request.reserved_bond,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Slashes the insurance and bond of the executor who misbehaved executing the side effect
+    /// to the requester. Returns the slashed executor.
+    ///
+    /// Both are released once the step of the side effect finishes, in which case as much of the
+    /// insurance as the executor's free balance covers is transferred instead.
+    pub fn slash_misbehaviour(
+        local_ctx: &mut LocalXtxCtx<T>,
+        sfx_id: SideEffectId<T>,
+    ) -> Result<T::AccountId, Error<T>> {
+        let is_step_open = local_ctx
+            .full_side_effects
+            .iter()
+            .position(|step| {
+                step.iter()
+                    .any(|fsx| fsx.input.generate_id::<SystemHashing<T>>() == sfx_id)
+            })
+            .map(|step| step >= local_ctx.xtx.steps_cnt.0 as usize)
+            .unwrap_or(false);
+
+        let insurance_deposit = Self::get_insurance_deposit_mutable_ref(local_ctx, sfx_id)?;
+        let executor = insurance_deposit
+            .bonded_relayer
+            .clone()
+            .ok_or(Error::<T>::InsuranceBondNotRequired)?;
+
+        if is_step_open {
+            <<T as Config>::Escrowed as EscrowTrait<T>>::Currency::repatriate_reserved(
+                &executor,
+                &insurance_deposit.requester,
+                insurance_deposit.insurance,
+                BalanceStatus::Free,
+            )
+            .map(|_| ())
+        } else {
+            <<T as Config>::Escrowed as EscrowTrait<T>>::Currency::transfer(
+                &executor,
+                &insurance_deposit.requester,
+                insurance_deposit.insurance.min(
+                    <<T as Config>::Escrowed as EscrowTrait<T>>::Currency::free_balance(&executor),
+                ),
+                AllowDeath,
+            )
+        }
+        .map_err(|_e| Error::<T>::InsuranceSlashFailed)?;
+
+        <T as Config>::Executors::slash_bond(
+            &executor,
+            insurance_deposit.reserved_bond,
+            &insurance_deposit.requester,
+        );
+
+        Ok(executor)
+    }
+
+    /// Releases the insurance and bond of executors in the current step who did not misbehave.
+    pub fn try_release(local_ctx: &mut LocalXtxCtx<T>) {
+        let optimistic_fsx_in_step = crate::Pallet::<T>::get_current_step_fsx_by_security_lvl(
+            local_ctx,
+            SecurityLvl::Optimistic,
+        );
+        for fsx in &optimistic_fsx_in_step {
+            let misbehaved = matches!(
+                fsx.confirmed
+                    .as_ref()
+                    .and_then(|confirmed| confirmed.err.as_ref()),
+                Some(ConfirmationOutcome::MisbehaviourMalformedValues { .. })
+            );
+            if misbehaved {
+                continue
+            }
+            let side_effect_id = fsx.input.generate_id::<SystemHashing<T>>();
+            if let Some((_id, insurance_request)) = local_ctx
+                .insurance_deposits
+                .iter()
+                .find(|(id, _)| *id == side_effect_id)
+            {
+                if let Some(bonded_relayer) = &insurance_request.bonded_relayer {
+                    <<T as Config>::Escrowed as EscrowTrait<T>>::Currency::unreserve(
+                        bonded_relayer,
+                        insurance_request.insurance,
+                    );
+                    <T as Config>::Executors::unreserve_bond(
+                        bonded_relayer,
+                        insurance_request.reserved_bond,
+                    );
+                }
             }
         }
     }
@@ -28,6 +28,7 @@ pub type XExecStepSideEffectId<T> = <T as frame_system::Config>::Hash;
 ///     Ready -> Committed: All of the side effects have been successfully confirmed
 ///     Ready -> Reverted: Some of the side effects failed and the Xtx was reverted
 #[derive(Clone, Eq, PartialEq, PartialOrd, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub enum CircuitStatus {
     Requested,
     PendingInsurance,
@@ -203,6 +204,7 @@ impl Default for CircuitStatus {
 }
 
 #[derive(Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct InsuranceDeposit<AccountId, BlockNumber, BalanceOf> {
     pub insurance: BalanceOf,
     pub reserved_bond: BalanceOf,
@@ -238,8 +240,29 @@ impl<
     }
 }
 
+/// Side effect open for executors to bond the insurance for, as listed in the open market
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpenSideEffect<AccountId, Hash, BlockNumber, BalanceOf> {
+    /// The Xtx the side effect belongs to
+    pub xtx_id: Hash,
+
+    /// Reward for the execution
+    pub prize: BalanceOf,
+
+    /// Insurance the executor needs to bond
+    pub insurance: BalanceOf,
+
+    /// The Xtx times out at this block
+    pub timeouts_at: BlockNumber,
+
+    /// The only executor allowed to bond, if the requester enforced one
+    pub reserved_for: Option<AccountId>,
+}
+
 /// A composable cross-chain (X) transaction that has already been verified to be valid and submittable
 #[derive(Clone, Eq, PartialEq, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct XExecSignal<AccountId, BlockNumber, BalanceOf> {
     /// The owner of the bid
     pub requester: AccountId,
@@ -288,6 +311,19 @@ impl<
         }
     }
 
+    /// Earliest block the step can start at, if the step has been scheduled
+    pub fn step_opens_at(&self, step: u32) -> Option<BlockNumber> {
+        self.delay_steps_at
+            .as_ref()
+            .and_then(|delays| delays.get(step as usize).copied())
+    }
+
+    /// Steps without a schedule are open right away
+    pub fn is_step_open(&self, step: u32, now: BlockNumber) -> bool {
+        self.step_opens_at(step)
+            .map_or(true, |opens_at| opens_at <= now)
+    }
+
     pub fn generate_id<T: frame_system::Config>(&self) -> XExecSignalId<T> {
         SystemHashing::<T>::hash(Encode::encode(self).as_ref())
     }
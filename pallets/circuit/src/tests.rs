@@ -17,7 +17,7 @@
 
 //! Runtime utilities
 use circuit_mock_runtime::*;
-use circuit_runtime_pallets::pallet_circuit::state::*;
+use circuit_runtime_pallets::pallet_circuit::{self, state::*, ActiveXExecSignalsTimingLinks};
 
 use t3rn_sdk_primitives::{
     signal::{ExecutionSignal, SignalKind},
@@ -25,7 +25,11 @@ use t3rn_sdk_primitives::{
 };
 
 use codec::{Decode, Encode};
-use frame_support::{assert_noop, assert_ok, dispatch::PostDispatchInfo, traits::Currency};
+use frame_support::{
+    assert_noop, assert_ok,
+    dispatch::PostDispatchInfo,
+    traits::{Currency, Get},
+};
 
 use frame_system::{pallet_prelude::OriginFor, EventRecord, Phase};
 
@@ -158,7 +162,7 @@ fn on_extrinsic_trigger(
     let sequential: bool =
         Decode::decode(&mut &*hex::decode(json["encoded_sequential"].as_str().unwrap()).unwrap())
             .unwrap();
-    Circuit::on_extrinsic_trigger(origin, side_effects, fee, sequential)
+    Circuit::on_extrinsic_trigger(origin, side_effects, fee, sequential, None, None, None)
 }
 
 fn confirm_side_effect(
@@ -171,19 +175,24 @@ fn confirm_side_effect(
     let side_effect: SideEffect<AccountId32, BlockNumber, BalanceOf> =
         Decode::decode(&mut &*hex::decode(json["encoded_side_effect"].as_str().unwrap()).unwrap())
             .unwrap();
-    let confirmed_side_effect: ConfirmedSideEffect<AccountId32, BlockNumber, BalanceOf> =
+    let mut confirmed_side_effect: ConfirmedSideEffect<AccountId32, BlockNumber, BalanceOf> =
         Decode::decode(
             &mut &*hex::decode(json["encoded_confirmed_side_effect"].as_str().unwrap()).unwrap(),
         )
         .unwrap();
 
+    // Exported inclusion data carries the event, its inclusion proof and the block hash in one
+    let (encoded_event, inclusion_proof, block_hash): (Vec<u8>, Vec<Vec<u8>>, sp_core::H256) =
+        Decode::decode(&mut &*confirmed_side_effect.inclusion_data).unwrap();
+    confirmed_side_effect.inclusion_data = encoded_event;
+
     Circuit::confirm_side_effect(
         origin,
         xtx_id,
         side_effect,
         confirmed_side_effect,
-        None,
-        None,
+        Some(inclusion_proof),
+        Some(block_hash.encode()),
     )
 }
 
@@ -302,6 +311,9 @@ fn on_extrinsic_trigger_works_with_empty_side_effects() {
             side_effects,
             fee,
             sequential,
+            None,
+            None,
+            None,
         ));
     });
 }
@@ -360,6 +372,9 @@ fn on_extrinsic_trigger_works_raw_insured_side_effect() {
                 side_effects,
                 fee,
                 sequential,
+                None,
+                None,
+                None,
             ));
         });
 }
@@ -402,6 +417,9 @@ fn on_extrinsic_trigger_works_with_single_transfer_not_insured() {
                 side_effects,
                 fee,
                 sequential,
+                None,
+                None,
+                None,
             ));
 
             // Assert Circuit::emit generates 5 correct events: 3 from charging and 2 Circuit-specific
@@ -543,6 +561,9 @@ fn on_extrinsic_trigger_validation_works_with_single_transfer_insured() {
                 side_effects,
                 fee,
                 sequential,
+                None,
+                None,
+                None,
             ));
         });
 }
@@ -586,6 +607,9 @@ fn on_extrinsic_trigger_emit_works_with_single_transfer_insured() {
                 side_effects,
                 fee,
                 sequential,
+                None,
+                None,
+                None,
             ));
 
             // Assert Circuit::emit generates 5 correct events: 3 for charging and 2 Circuit-specific
@@ -695,6 +719,9 @@ fn on_extrinsic_trigger_apply_works_with_single_transfer_insured() {
                 side_effects,
                 fee,
                 sequential,
+                None,
+                None,
+                None,
             ));
 
             let (xtx_id, side_effect_a_id) = set_ids(valid_transfer_side_effect.clone());
@@ -744,6 +771,71 @@ fn on_extrinsic_trigger_apply_works_with_single_transfer_insured() {
         });
 }
 
+#[test]
+fn circuit_lists_open_insurance_deposits_and_xtx_by_requester() {
+    let origin = Origin::signed(ALICE);
+
+    let transfer_protocol_box =
+        Box::new(t3rn_protocol::side_effects::standards::get_transfer_interface());
+
+    let mut local_state = LocalState::new();
+
+    let valid_transfer_side_effect = produce_and_validate_side_effect(
+        vec![
+            (Type::Address(32), ArgVariant::A),
+            (Type::Address(32), ArgVariant::B),
+            (Type::Uint(128), ArgVariant::A),
+            (Type::OptionalInsurance, ArgVariant::A), // insurance = 1, reward = 2
+        ],
+        &mut local_state,
+        transfer_protocol_box,
+    );
+
+    let side_effects = vec![valid_transfer_side_effect.clone()];
+    let fee = 1;
+    let sequential = true;
+
+    ExtBuilder::default()
+        .with_standard_side_effects()
+        .with_default_xdns_records()
+        .build()
+        .execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, 1 + 2); // Alice should have at least: fee (1) + insurance reward (2)(for VariantA)
+
+            System::set_block_number(1);
+            brute_seed_block_1([0, 0, 0, 0]);
+
+            assert_ok!(Circuit::on_extrinsic_trigger(
+                origin,
+                side_effects,
+                fee,
+                sequential,
+                None,
+                None,
+                None,
+            ));
+
+            let (xtx_id, side_effect_a_id) = set_ids(valid_transfer_side_effect);
+
+            assert_eq!(
+                Circuit::get_open_insurance_deposits(),
+                vec![(
+                    xtx_id,
+                    side_effect_a_id,
+                    Circuit::get_insurance_deposits(xtx_id, side_effect_a_id).unwrap()
+                )]
+            );
+
+            assert_eq!(
+                Circuit::get_xtx_by_requester(&ALICE, 0, 10),
+                vec![(xtx_id, Circuit::get_x_exec_signals(xtx_id).unwrap())]
+            );
+            assert_eq!(Circuit::get_xtx_by_requester(&ALICE, 1, 10), vec![]);
+            assert_eq!(Circuit::get_xtx_by_requester(&ALICE, 0, 0), vec![]);
+            assert_eq!(Circuit::get_xtx_by_requester(&CHARLIE, 0, 10), vec![]);
+        });
+}
+
 #[test]
 fn circuit_handles_insurance_deposit_for_transfers() {
     let origin = Origin::signed(ALICE); // Only sudo access to register new gateways for now
@@ -769,6 +861,7 @@ fn circuit_handles_insurance_deposit_for_transfers() {
     let sequential = true;
 
     ExtBuilder::default()
+        .with_default_executors_and_relayers()
         .with_standard_side_effects()
         .with_default_xdns_records()
         .build()
@@ -784,6 +877,9 @@ fn circuit_handles_insurance_deposit_for_transfers() {
                 side_effects,
                 fee,
                 sequential,
+                None,
+                None,
+                None,
             ));
 
             let (xtx_id, side_effect_a_id) = set_ids(valid_transfer_side_effect.clone());
@@ -831,6 +927,21 @@ fn circuit_handles_insurance_deposit_for_transfers() {
                 }]]
             );
 
+            // Side effect is listed in the open market until bonded
+            assert_eq!(
+                Circuit::get_open_side_effects_by_kind([0, 0, 0, 0], *b"tran"),
+                vec![(
+                    side_effect_a_id,
+                    OpenSideEffect {
+                        xtx_id,
+                        prize: 2,
+                        insurance: 1,
+                        timeouts_at: 401u32,
+                        reserved_for: None,
+                    }
+                )]
+            );
+
             let origin_relayer_bob = Origin::signed(BOB_RELAYER); // Only sudo access to register new gateways for now
 
             assert_ok!(Circuit::bond_insurance_deposit(
@@ -839,6 +950,11 @@ fn circuit_handles_insurance_deposit_for_transfers() {
                 side_effect_a_id,
             ));
 
+            assert_eq!(
+                Circuit::get_open_side_effects_by_kind([0, 0, 0, 0], *b"tran"),
+                vec![]
+            );
+
             let expected_bonded_insurance_deposit = InsuranceDeposit {
                 insurance: 1,
                 reward: 2,
@@ -873,38 +989,38 @@ fn circuit_handles_insurance_deposit_for_transfers() {
 }
 
 #[test]
-fn circuit_handles_dirty_swap_with_no_insurance() {
+fn circuit_handles_bidding_for_insured_transfers() {
     let origin = Origin::signed(ALICE); // Only sudo access to register new gateways for now
 
-    let swap_protocol_box = Box::new(t3rn_protocol::side_effects::standards::get_swap_interface());
+    let transfer_protocol_box =
+        Box::new(t3rn_protocol::side_effects::standards::get_transfer_interface());
 
     let mut local_state = LocalState::new();
 
-    let valid_swap_side_effect = produce_and_validate_side_effect(
+    let valid_transfer_side_effect = produce_and_validate_side_effect(
         vec![
-            (Type::Address(32), ArgVariant::A), // caller
-            (Type::Address(32), ArgVariant::B), // to
-            (Type::Uint(128), ArgVariant::A),   // amount_from
-            (Type::Uint(128), ArgVariant::B),   // amount_to
-            (Type::Bytes(4), ArgVariant::A),    // asset_from
-            (Type::Bytes(4), ArgVariant::B),    // asset_to
-            (Type::Bytes(0), ArgVariant::A),    // empty bytes instead of insurance
+            (Type::Address(32), ArgVariant::A),
+            (Type::Address(32), ArgVariant::B),
+            (Type::Uint(128), ArgVariant::A),
+            (Type::OptionalInsurance, ArgVariant::A), // insurance = 1, reward = 2
         ],
         &mut local_state,
-        swap_protocol_box,
+        transfer_protocol_box,
     );
 
-    let side_effects = vec![valid_swap_side_effect.clone()];
+    let side_effects = vec![valid_transfer_side_effect.clone()];
     let fee = 1;
     let sequential = true;
 
     ExtBuilder::default()
+        .with_default_executors_and_relayers()
         .with_standard_side_effects()
         .with_default_xdns_records()
         .build()
         .execute_with(|| {
             let _ = Balances::deposit_creating(&ALICE, 1 + 2); // Alice should have at least: fee (1) + insurance reward (2)(for VariantA)
             let _ = Balances::deposit_creating(&BOB_RELAYER, 1); // Bob should have at least: insurance deposit (1)(for VariantA)
+            let _ = Balances::deposit_creating(&EXECUTOR_DEFAULT, 1);
 
             System::set_block_number(1);
             brute_seed_block_1([0, 0, 0, 0]);
@@ -914,73 +1030,137 @@ fn circuit_handles_dirty_swap_with_no_insurance() {
                 side_effects,
                 fee,
                 sequential,
+                None,
+                None,
+                None,
             ));
 
-            let (xtx_id, side_effect_a_id) = set_ids(valid_swap_side_effect.clone());
+            let (xtx_id, side_effect_a_id) = set_ids(valid_transfer_side_effect.clone());
 
-            assert_eq!(
-                Circuit::get_x_exec_signals(xtx_id).unwrap(),
-                XExecSignal {
-                    requester: AccountId32::new(hex!(
-                        "0101010101010101010101010101010101010101010101010101010101010101"
-                    )),
-                    timeouts_at: 401u32,
-                    delay_steps_at: None,
-                    status: CircuitStatus::Ready,
-                    total_reward: Some(fee),
-                    steps_cnt: (0, 1),
-                }
+            assert_noop!(
+                Circuit::bid_side_effect(Origin::signed(BOB_RELAYER), xtx_id, side_effect_a_id, 3),
+                pallet_circuit::Error::<Runtime>::BidAboveMaxPrize
             );
 
+            assert_ok!(Circuit::bid_side_effect(
+                Origin::signed(BOB_RELAYER),
+                xtx_id,
+                side_effect_a_id,
+                2,
+            ));
+            assert_ok!(Circuit::bid_side_effect(
+                Origin::signed(EXECUTOR_DEFAULT),
+                xtx_id,
+                side_effect_a_id,
+                1,
+            ));
+
+            assert_noop!(
+                Circuit::bid_side_effect(Origin::signed(BOB_RELAYER), xtx_id, side_effect_a_id, 2),
+                pallet_circuit::Error::<Runtime>::BidNotCompetitive
+            );
+
+            // The outbid executor gets the insurance back
+            assert_eq!(Balances::reserved_balance(&BOB_RELAYER), 0);
+            assert_eq!(Balances::reserved_balance(&EXECUTOR_DEFAULT), 1);
             assert_eq!(
-                Circuit::get_full_side_effects(xtx_id).unwrap(),
-                vec![vec![FullSideEffect {
-                    input: valid_swap_side_effect.clone(),
-                    confirmed: None,
-                    security_lvl: SecurityLvl::Dirty,
-                    submission_target_height: vec![0],
-                }]]
+                Circuit::get_side_effect_bids(xtx_id, side_effect_a_id),
+                Some(1)
+            );
+
+            assert_noop!(
+                Circuit::bond_insurance_deposit(
+                    Origin::signed(EXECUTOR_DEFAULT),
+                    xtx_id,
+                    side_effect_a_id,
+                ),
+                pallet_circuit::Error::<Runtime>::BiddingStillOpen
+            );
+
+            System::set_block_number(5);
+
+            assert_noop!(
+                Circuit::bid_side_effect(Origin::signed(BOB_RELAYER), xtx_id, side_effect_a_id, 0),
+                pallet_circuit::Error::<Runtime>::BiddingClosed
+            );
+            assert_noop!(
+                Circuit::bond_insurance_deposit(
+                    Origin::signed(BOB_RELAYER),
+                    xtx_id,
+                    side_effect_a_id,
+                ),
+                pallet_circuit::Error::<Runtime>::BidderNotWinning
             );
 
+            let alice_reserved_before_bond = Balances::reserved_balance(&ALICE);
+
+            assert_ok!(Circuit::bond_insurance_deposit(
+                Origin::signed(EXECUTOR_DEFAULT),
+                xtx_id,
+                side_effect_a_id,
+            ));
+
+            // The requester is refunded the prize above the winning bid
             assert_eq!(
-                Circuit::get_insurance_deposits(xtx_id, side_effect_a_id),
+                Balances::reserved_balance(&ALICE),
+                alice_reserved_before_bond - 1
+            );
+            assert_eq!(
+                Circuit::get_side_effect_bids(xtx_id, side_effect_a_id),
                 None
             );
+            assert_eq!(
+                Circuit::get_insurance_deposits(xtx_id, side_effect_a_id).unwrap(),
+                InsuranceDeposit {
+                    insurance: 1,
+                    reward: 1,
+                    requester: ALICE,
+                    bonded_relayer: Some(EXECUTOR_DEFAULT),
+                    status: CircuitStatus::Bonded,
+                    requested_at: 1,
+                    reserved_bond: 0,
+                }
+            );
+            assert_eq!(
+                Circuit::get_x_exec_signals(xtx_id).unwrap().status,
+                CircuitStatus::Ready
+            );
         });
 }
 
 #[test]
-fn circuit_handles_swap_with_insurance() {
+fn circuit_lets_anyone_bond_at_the_winning_bid_once_the_grace_period_passes() {
     let origin = Origin::signed(ALICE); // Only sudo access to register new gateways for now
 
-    let ext = ExtBuilder::default();
+    let transfer_protocol_box =
+        Box::new(t3rn_protocol::side_effects::standards::get_transfer_interface());
 
     let mut local_state = LocalState::new();
-    let swap_protocol_box = Box::new(t3rn_protocol::side_effects::standards::get_swap_interface());
-    let valid_swap_side_effect = produce_and_validate_side_effect(
+
+    let valid_transfer_side_effect = produce_and_validate_side_effect(
         vec![
-            (Type::Address(32), ArgVariant::A),       // caller
-            (Type::Address(32), ArgVariant::B),       // to
-            (Type::Uint(128), ArgVariant::A),         // amount_from
-            (Type::Uint(128), ArgVariant::B),         // amount_to
-            (Type::Bytes(4), ArgVariant::A),          // asset_from
-            (Type::Bytes(4), ArgVariant::B),          // asset_to
-            (Type::OptionalInsurance, ArgVariant::A), // insurance
+            (Type::Address(32), ArgVariant::A),
+            (Type::Address(32), ArgVariant::B),
+            (Type::Uint(128), ArgVariant::A),
+            (Type::OptionalInsurance, ArgVariant::A), // insurance = 1, reward = 2
         ],
         &mut local_state,
-        swap_protocol_box,
+        transfer_protocol_box,
     );
 
-    let side_effects = vec![valid_swap_side_effect.clone()];
+    let side_effects = vec![valid_transfer_side_effect.clone()];
     let fee = 1;
     let sequential = true;
 
-    ext.with_default_xdns_records()
+    ExtBuilder::default()
+        .with_default_executors_and_relayers()
         .with_standard_side_effects()
+        .with_default_xdns_records()
         .build()
         .execute_with(|| {
             let _ = Balances::deposit_creating(&ALICE, 1 + 2); // Alice should have at least: fee (1) + insurance reward (2)(for VariantA)
             let _ = Balances::deposit_creating(&BOB_RELAYER, 1); // Bob should have at least: insurance deposit (1)(for VariantA)
+            let _ = Balances::deposit_creating(&EXECUTOR_DEFAULT, 1);
 
             System::set_block_number(1);
             brute_seed_block_1([0, 0, 0, 0]);
@@ -990,130 +1170,105 @@ fn circuit_handles_swap_with_insurance() {
                 side_effects,
                 fee,
                 sequential,
+                None,
+                None,
+                None,
             ));
 
-            let (xtx_id, side_effect_a_id) = set_ids(valid_swap_side_effect.clone());
-
-            // Runtime Apply State
-            // Returns valid insurance for that side effect
-            let valid_insurance_deposit = InsuranceDeposit {
-                insurance: 1,
-                reward: 2,
-                requester: AccountId32::new(hex!(
-                    "0101010101010101010101010101010101010101010101010101010101010101"
-                )),
-                bonded_relayer: None,
-                status: CircuitStatus::Requested,
-                requested_at: 1,
-                reserved_bond: 0,
-            };
-
-            assert_eq!(
-                Circuit::get_insurance_deposits(xtx_id, side_effect_a_id).unwrap(),
-                valid_insurance_deposit
-            );
+            let (xtx_id, side_effect_a_id) = set_ids(valid_transfer_side_effect.clone());
 
-            assert_eq!(
-                Circuit::get_x_exec_signals(xtx_id).unwrap(),
-                XExecSignal {
-                    requester: AccountId32::new(hex!(
-                        "0101010101010101010101010101010101010101010101010101010101010101"
-                    )),
-                    timeouts_at: 401u32,
-                    delay_steps_at: None,
-                    status: CircuitStatus::PendingInsurance,
-                    total_reward: Some(fee),
-                    steps_cnt: (0, 1),
-                }
-            );
+            assert_ok!(Circuit::bid_side_effect(
+                Origin::signed(EXECUTOR_DEFAULT),
+                xtx_id,
+                side_effect_a_id,
+                1,
+            ));
 
-            assert_eq!(
-                Circuit::get_full_side_effects(xtx_id).unwrap(),
-                vec![vec![FullSideEffect {
-                    input: valid_swap_side_effect.clone(),
-                    confirmed: None,
-                    security_lvl: SecurityLvl::Optimistic,
-                    submission_target_height: vec![0],
-                }]]
+            // Bidding closes at block 4, the winner bonds exclusively up until block 7
+            System::set_block_number(7);
+            assert_noop!(
+                Circuit::bond_insurance_deposit(
+                    Origin::signed(BOB_RELAYER),
+                    xtx_id,
+                    side_effect_a_id,
+                ),
+                pallet_circuit::Error::<Runtime>::BidderNotWinning
             );
 
-            let origin_relayer_bob = Origin::signed(BOB_RELAYER); // Only sudo access to register new gateways for now
+            System::set_block_number(8);
+            let alice_reserved_before_bond = Balances::reserved_balance(&ALICE);
 
             assert_ok!(Circuit::bond_insurance_deposit(
-                origin_relayer_bob,
+                Origin::signed(BOB_RELAYER),
                 xtx_id,
                 side_effect_a_id,
             ));
 
-            let expected_bonded_insurance_deposit = InsuranceDeposit {
-                insurance: 1,
-                reward: 2,
-                requester: AccountId32::new(hex!(
-                    "0101010101010101010101010101010101010101010101010101010101010101"
-                )),
-                bonded_relayer: Some(BOB_RELAYER),
-                status: CircuitStatus::Bonded,
-                requested_at: 1,
-                reserved_bond: 0,
-            };
-
+            // The winner who never bonded gets the insurance back
+            assert_eq!(Balances::reserved_balance(&EXECUTOR_DEFAULT), 0);
+            assert_eq!(Balances::reserved_balance(&BOB_RELAYER), 1);
+            // The requester is refunded the prize above the winning bid
             assert_eq!(
-                Circuit::get_insurance_deposits(xtx_id, side_effect_a_id).unwrap(),
-                expected_bonded_insurance_deposit
+                Balances::reserved_balance(&ALICE),
+                alice_reserved_before_bond - 1
             );
-
             assert_eq!(
-                Circuit::get_x_exec_signals(xtx_id).unwrap(),
-                XExecSignal {
-                    requester: AccountId32::new(hex!(
-                        "0101010101010101010101010101010101010101010101010101010101010101"
-                    )),
-                    timeouts_at: 401u32,
-                    delay_steps_at: None,
-                    status: CircuitStatus::Ready,
-                    total_reward: Some(fee),
-                    steps_cnt: (0, 1),
+                Circuit::get_side_effect_bids(xtx_id, side_effect_a_id),
+                None
+            );
+            assert_eq!(
+                Circuit::get_insurance_deposits(xtx_id, side_effect_a_id).unwrap(),
+                InsuranceDeposit {
+                    insurance: 1,
+                    reward: 1,
+                    requester: ALICE,
+                    bonded_relayer: Some(BOB_RELAYER),
+                    status: CircuitStatus::Bonded,
+                    requested_at: 1,
+                    reserved_bond: 0,
                 }
             );
+            assert_eq!(
+                Circuit::get_x_exec_signals(xtx_id).unwrap().status,
+                CircuitStatus::Ready
+            );
         });
 }
 
 #[test]
-fn circuit_handles_add_liquidity_without_insurance() {
-    let origin = Origin::signed(ALICE);
+fn circuit_reserves_insured_transfers_for_enforced_executioner() {
+    let origin = Origin::signed(ALICE); // Only sudo access to register new gateways for now
+
+    let transfer_protocol_box =
+        Box::new(t3rn_protocol::side_effects::standards::get_transfer_interface());
 
-    let ext = ExtBuilder::default();
     let mut local_state = LocalState::new();
 
-    let add_liquidity_protocol_box =
-        Box::new(t3rn_protocol::side_effects::standards::get_add_liquidity_interface());
-
-    let valid_add_liquidity_side_effect = produce_and_validate_side_effect(
+    let mut valid_transfer_side_effect = produce_and_validate_side_effect(
         vec![
-            (Type::Address(32), ArgVariant::A), // argument_0: caller
-            (Type::Address(32), ArgVariant::B), // argument_1: to
-            (Type::Bytes(4), ArgVariant::A),    // argument_2: asset_left
-            (Type::Bytes(4), ArgVariant::B),    // argument_3: asset_right
-            (Type::Bytes(4), ArgVariant::C),    // argument_4: liquidity_token
-            (Type::Uint(128), ArgVariant::A),   // argument_5: amount_left
-            (Type::Uint(128), ArgVariant::B),   // argument_6: amount_right
-            (Type::Uint(128), ArgVariant::A),   // argument_7: amount_liquidity_token
-            (Type::Bytes(0), ArgVariant::A),    // argument_8: no insurance, empty bytes
+            (Type::Address(32), ArgVariant::A),
+            (Type::Address(32), ArgVariant::B),
+            (Type::Uint(128), ArgVariant::A),
+            (Type::OptionalInsurance, ArgVariant::A), // insurance = 1, reward = 2
         ],
         &mut local_state,
-        add_liquidity_protocol_box,
+        transfer_protocol_box,
     );
+    valid_transfer_side_effect.enforce_executioner = Some(EXECUTOR_DEFAULT);
 
-    let side_effects = vec![valid_add_liquidity_side_effect.clone()];
+    let side_effects = vec![valid_transfer_side_effect.clone()];
     let fee = 1;
     let sequential = true;
 
-    ext.with_default_xdns_records()
+    ExtBuilder::default()
+        .with_default_executors_and_relayers()
         .with_standard_side_effects()
+        .with_default_xdns_records()
         .build()
         .execute_with(|| {
-            let _ = Balances::deposit_creating(&ALICE, 1 + 2);
-            let _ = Balances::deposit_creating(&BOB_RELAYER, 1);
+            let _ = Balances::deposit_creating(&ALICE, 1 + 2); // Alice should have at least: fee (1) + insurance reward (2)(for VariantA)
+            let _ = Balances::deposit_creating(&BOB_RELAYER, 1); // Bob should have at least: insurance deposit (1)(for VariantA)
+            let _ = Balances::deposit_creating(&EXECUTOR_DEFAULT, 1);
 
             System::set_block_number(1);
             brute_seed_block_1([0, 0, 0, 0]);
@@ -1123,49 +1278,82 @@ fn circuit_handles_add_liquidity_without_insurance() {
                 side_effects,
                 fee,
                 sequential,
+                None,
+                None,
+                None,
             ));
 
-            let (xtx_id, side_effect_a_id) = set_ids(valid_add_liquidity_side_effect.clone());
+            let (xtx_id, side_effect_a_id) = set_ids(valid_transfer_side_effect.clone());
 
             assert_eq!(
-                Circuit::get_insurance_deposits(xtx_id, side_effect_a_id),
-                None
+                Circuit::get_open_side_effects_by_kind([0, 0, 0, 0], *b"tran"),
+                vec![(
+                    side_effect_a_id,
+                    OpenSideEffect {
+                        xtx_id,
+                        prize: 2,
+                        insurance: 1,
+                        timeouts_at: 401u32,
+                        reserved_for: Some(EXECUTOR_DEFAULT),
+                    }
+                )]
+            );
+
+            assert_noop!(
+                Circuit::bid_side_effect(Origin::signed(BOB_RELAYER), xtx_id, side_effect_a_id, 1),
+                pallet_circuit::Error::<Runtime>::EnforcedExecutionerMismatch
+            );
+
+            assert_noop!(
+                Circuit::bond_insurance_deposit(
+                    Origin::signed(BOB_RELAYER),
+                    xtx_id,
+                    side_effect_a_id,
+                ),
+                pallet_circuit::Error::<Runtime>::EnforcedExecutionerMismatch
+            );
+
+            assert_ok!(Circuit::bond_insurance_deposit(
+                Origin::signed(EXECUTOR_DEFAULT),
+                xtx_id,
+                side_effect_a_id,
+            ));
+
+            assert_eq!(
+                Circuit::get_x_exec_signals(xtx_id).unwrap().status,
+                CircuitStatus::Ready
             );
         });
 }
 
 #[test]
-fn circuit_handles_add_liquidity_with_insurance() {
-    let origin = Origin::signed(ALICE);
+fn cancel_xtx_refunds_requester_and_drops_xtx() {
+    let origin = Origin::signed(ALICE); // Only sudo access to register new gateways for now
 
-    let ext = ExtBuilder::default();
-    let mut local_state = LocalState::new();
+    let transfer_protocol_box =
+        Box::new(t3rn_protocol::side_effects::standards::get_transfer_interface());
 
-    let add_liquidity_protocol_box =
-        Box::new(t3rn_protocol::side_effects::standards::get_add_liquidity_interface());
+    let mut local_state = LocalState::new();
 
-    let valid_add_liquidity_side_effect = produce_and_validate_side_effect(
+    let valid_transfer_side_effect = produce_and_validate_side_effect(
         vec![
-            (Type::Address(32), ArgVariant::A),       // argument_0: caller
-            (Type::Address(32), ArgVariant::B),       // argument_1: to
-            (Type::Bytes(4), ArgVariant::A),          // argument_2: asset_left
-            (Type::Bytes(4), ArgVariant::B),          // argument_3: asset_right
-            (Type::Bytes(4), ArgVariant::A),          // argument_4: liquidity_token
-            (Type::Uint(128), ArgVariant::A),         // argument_5: amount_left
-            (Type::Uint(128), ArgVariant::B),         // argument_6: amount_right
-            (Type::Uint(128), ArgVariant::A),         // argument_7: amount_liquidity_token
-            (Type::OptionalInsurance, ArgVariant::A), // argument_8: Variant A insurance = 1, reward = 2
+            (Type::Address(32), ArgVariant::A),
+            (Type::Address(32), ArgVariant::B),
+            (Type::Uint(128), ArgVariant::A),
+            (Type::OptionalInsurance, ArgVariant::A), // insurance = 1, reward = 2
         ],
         &mut local_state,
-        add_liquidity_protocol_box,
+        transfer_protocol_box,
     );
 
-    let side_effects = vec![valid_add_liquidity_side_effect.clone()];
+    let side_effects = vec![valid_transfer_side_effect.clone()];
     let fee = 1;
     let sequential = true;
 
-    ext.with_default_xdns_records()
+    ExtBuilder::default()
+        .with_default_executors_and_relayers()
         .with_standard_side_effects()
+        .with_default_xdns_records()
         .build()
         .execute_with(|| {
             let _ = Balances::deposit_creating(&ALICE, 1 + 2); // Alice should have at least: fee (1) + insurance reward (2)(for VariantA)
@@ -1179,76 +1367,160 @@ fn circuit_handles_add_liquidity_with_insurance() {
                 side_effects,
                 fee,
                 sequential,
+                None,
+                None,
+                None,
             ));
 
-            let (xtx_id, side_effect_a_id) = set_ids(valid_add_liquidity_side_effect.clone());
+            let (xtx_id, side_effect_a_id) = set_ids(valid_transfer_side_effect.clone());
 
-            // Runtime Apply State
-            // Returns valid insurance for that side effect
-            let valid_insurance_deposit = InsuranceDeposit {
-                insurance: 1,
-                reward: 2,
-                requester: AccountId32::new(hex!(
-                    "0101010101010101010101010101010101010101010101010101010101010101"
-                )),
-                bonded_relayer: None,
-                status: CircuitStatus::Requested,
-                requested_at: 1,
-                reserved_bond: 0,
-            };
+            assert_eq!(Balances::reserved_balance(&ALICE), 2);
+
+            assert_noop!(
+                Circuit::cancel_xtx(Origin::signed(BOB_RELAYER), xtx_id),
+                pallet_circuit::Error::<Runtime>::CancellationUnauthorized
+            );
+
+            assert_ok!(Circuit::cancel_xtx(Origin::signed(ALICE), xtx_id));
 
+            assert_eq!(Balances::reserved_balance(&ALICE), 0);
+            assert_eq!(Circuit::get_x_exec_signals(xtx_id), None);
+            assert_eq!(Circuit::get_full_side_effects(xtx_id), None);
             assert_eq!(
-                Circuit::get_insurance_deposits(xtx_id, side_effect_a_id).unwrap(),
-                valid_insurance_deposit
+                Circuit::get_insurance_deposits(xtx_id, side_effect_a_id),
+                None
             );
             assert_eq!(
-                Circuit::get_x_exec_signals(xtx_id).unwrap(),
-                XExecSignal {
-                    requester: AccountId32::new(hex!(
-                        "0101010101010101010101010101010101010101010101010101010101010101"
-                    )),
-                    timeouts_at: 401u32,
-                    delay_steps_at: None,
-                    status: CircuitStatus::PendingInsurance,
-                    total_reward: Some(fee),
-                    steps_cnt: (0, 1),
-                }
+                Circuit::get_open_side_effects_by_kind([0, 0, 0, 0], *b"tran"),
+                vec![]
             );
-
             assert_eq!(
-                Circuit::get_full_side_effects(xtx_id).unwrap(),
-                vec![vec![FullSideEffect {
-                    input: valid_add_liquidity_side_effect.clone(),
-                    confirmed: None,
-                    security_lvl: SecurityLvl::Optimistic,
-                    submission_target_height: vec![0],
-                }]]
+                System::events().last().map(|record| record.event.clone()),
+                Some(Event::Circuit(
+                    pallet_circuit::Event::<Runtime>::CancelledSideEffects(
+                        ALICE,
+                        xtx_id,
+                        vec![valid_transfer_side_effect.clone()],
+                    )
+                ))
             );
 
-            let origin_relayer_bob = Origin::signed(BOB_RELAYER); // Only sudo access to register new gateways for now
+            assert_noop!(
+                Circuit::cancel_xtx(Origin::signed(ALICE), xtx_id),
+                pallet_circuit::Error::<Runtime>::SetupFailedUnknownXtx
+            );
+        });
+}
+
+#[test]
+fn cancel_xtx_fails_once_side_effect_is_bonded() {
+    let origin = Origin::signed(ALICE); // Only sudo access to register new gateways for now
+
+    let transfer_protocol_box =
+        Box::new(t3rn_protocol::side_effects::standards::get_transfer_interface());
+
+    let mut local_state = LocalState::new();
+
+    let valid_transfer_side_effect = produce_and_validate_side_effect(
+        vec![
+            (Type::Address(32), ArgVariant::A),
+            (Type::Address(32), ArgVariant::B),
+            (Type::Uint(128), ArgVariant::A),
+            (Type::OptionalInsurance, ArgVariant::A), // insurance = 1, reward = 2
+        ],
+        &mut local_state,
+        transfer_protocol_box,
+    );
+
+    let side_effects = vec![valid_transfer_side_effect.clone()];
+    let fee = 1;
+    let sequential = true;
+
+    ExtBuilder::default()
+        .with_default_executors_and_relayers()
+        .with_standard_side_effects()
+        .with_default_xdns_records()
+        .build()
+        .execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, 1 + 2); // Alice should have at least: fee (1) + insurance reward (2)(for VariantA)
+            let _ = Balances::deposit_creating(&BOB_RELAYER, 1); // Bob should have at least: insurance deposit (1)(for VariantA)
+
+            System::set_block_number(1);
+            brute_seed_block_1([0, 0, 0, 0]);
+
+            assert_ok!(Circuit::on_extrinsic_trigger(
+                origin,
+                side_effects,
+                fee,
+                sequential,
+                None,
+                None,
+                None,
+            ));
+
+            let (xtx_id, side_effect_a_id) = set_ids(valid_transfer_side_effect.clone());
 
             assert_ok!(Circuit::bond_insurance_deposit(
-                origin_relayer_bob,
+                Origin::signed(BOB_RELAYER),
                 xtx_id,
                 side_effect_a_id,
             ));
 
-            let expected_bonded_insurance_deposit = InsuranceDeposit {
-                insurance: 1,
-                reward: 2,
-                requester: AccountId32::new(hex!(
-                    "0101010101010101010101010101010101010101010101010101010101010101"
-                )),
-                bonded_relayer: Some(BOB_RELAYER),
-                status: CircuitStatus::Bonded,
-                requested_at: 1,
-                reserved_bond: 0,
-            };
-
-            assert_eq!(
-                Circuit::get_insurance_deposits(xtx_id, side_effect_a_id).unwrap(),
-                expected_bonded_insurance_deposit
+            assert_noop!(
+                Circuit::cancel_xtx(Origin::signed(ALICE), xtx_id),
+                pallet_circuit::Error::<Runtime>::CancellationNotAllowed
             );
+        });
+}
+
+#[test]
+fn circuit_handles_dirty_swap_with_no_insurance() {
+    let origin = Origin::signed(ALICE); // Only sudo access to register new gateways for now
+
+    let swap_protocol_box = Box::new(t3rn_protocol::side_effects::standards::get_swap_interface());
+
+    let mut local_state = LocalState::new();
+
+    let valid_swap_side_effect = produce_and_validate_side_effect(
+        vec![
+            (Type::Address(32), ArgVariant::A), // caller
+            (Type::Address(32), ArgVariant::B), // to
+            (Type::Uint(128), ArgVariant::A),   // amount_from
+            (Type::Uint(128), ArgVariant::B),   // amount_to
+            (Type::Bytes(4), ArgVariant::A),    // asset_from
+            (Type::Bytes(4), ArgVariant::B),    // asset_to
+            (Type::Bytes(0), ArgVariant::A),    // empty bytes instead of insurance
+        ],
+        &mut local_state,
+        swap_protocol_box,
+    );
+
+    let side_effects = vec![valid_swap_side_effect.clone()];
+    let fee = 1;
+    let sequential = true;
+
+    ExtBuilder::default()
+        .with_standard_side_effects()
+        .with_default_xdns_records()
+        .build()
+        .execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, 1 + 2); // Alice should have at least: fee (1) + insurance reward (2)(for VariantA)
+            let _ = Balances::deposit_creating(&BOB_RELAYER, 1); // Bob should have at least: insurance deposit (1)(for VariantA)
+
+            System::set_block_number(1);
+            brute_seed_block_1([0, 0, 0, 0]);
+
+            assert_ok!(Circuit::on_extrinsic_trigger(
+                origin,
+                side_effects,
+                fee,
+                sequential,
+                None,
+                None,
+                None,
+            ));
+
+            let (xtx_id, side_effect_a_id) = set_ids(valid_swap_side_effect.clone());
 
             assert_eq!(
                 Circuit::get_x_exec_signals(xtx_id).unwrap(),
@@ -1263,10 +1535,351 @@ fn circuit_handles_add_liquidity_with_insurance() {
                     steps_cnt: (0, 1),
                 }
             );
+
+            assert_eq!(
+                Circuit::get_full_side_effects(xtx_id).unwrap(),
+                vec![vec![FullSideEffect {
+                    input: valid_swap_side_effect.clone(),
+                    confirmed: None,
+                    security_lvl: SecurityLvl::Dirty,
+                    submission_target_height: vec![0],
+                }]]
+            );
+
+            assert_eq!(
+                Circuit::get_insurance_deposits(xtx_id, side_effect_a_id),
+                None
+            );
         });
 }
 
-// fn successfully_confirm_optimistic(side_effect: SideEffect<AccountId32, BlockNumber, Balance>) {
+#[test]
+fn circuit_handles_swap_with_insurance() {
+    let origin = Origin::signed(ALICE); // Only sudo access to register new gateways for now
+
+    let ext = ExtBuilder::default().with_default_executors_and_relayers();
+
+    let mut local_state = LocalState::new();
+    let swap_protocol_box = Box::new(t3rn_protocol::side_effects::standards::get_swap_interface());
+    let valid_swap_side_effect = produce_and_validate_side_effect(
+        vec![
+            (Type::Address(32), ArgVariant::A),       // caller
+            (Type::Address(32), ArgVariant::B),       // to
+            (Type::Uint(128), ArgVariant::A),         // amount_from
+            (Type::Uint(128), ArgVariant::B),         // amount_to
+            (Type::Bytes(4), ArgVariant::A),          // asset_from
+            (Type::Bytes(4), ArgVariant::B),          // asset_to
+            (Type::OptionalInsurance, ArgVariant::A), // insurance
+        ],
+        &mut local_state,
+        swap_protocol_box,
+    );
+
+    let side_effects = vec![valid_swap_side_effect.clone()];
+    let fee = 1;
+    let sequential = true;
+
+    ext.with_default_xdns_records()
+        .with_standard_side_effects()
+        .build()
+        .execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, 1 + 2); // Alice should have at least: fee (1) + insurance reward (2)(for VariantA)
+            let _ = Balances::deposit_creating(&BOB_RELAYER, 1); // Bob should have at least: insurance deposit (1)(for VariantA)
+
+            System::set_block_number(1);
+            brute_seed_block_1([0, 0, 0, 0]);
+
+            assert_ok!(Circuit::on_extrinsic_trigger(
+                origin,
+                side_effects,
+                fee,
+                sequential,
+                None,
+                None,
+                None,
+            ));
+
+            let (xtx_id, side_effect_a_id) = set_ids(valid_swap_side_effect.clone());
+
+            // Runtime Apply State
+            // Returns valid insurance for that side effect
+            let valid_insurance_deposit = InsuranceDeposit {
+                insurance: 1,
+                reward: 2,
+                requester: AccountId32::new(hex!(
+                    "0101010101010101010101010101010101010101010101010101010101010101"
+                )),
+                bonded_relayer: None,
+                status: CircuitStatus::Requested,
+                requested_at: 1,
+                reserved_bond: 0,
+            };
+
+            assert_eq!(
+                Circuit::get_insurance_deposits(xtx_id, side_effect_a_id).unwrap(),
+                valid_insurance_deposit
+            );
+
+            assert_eq!(
+                Circuit::get_x_exec_signals(xtx_id).unwrap(),
+                XExecSignal {
+                    requester: AccountId32::new(hex!(
+                        "0101010101010101010101010101010101010101010101010101010101010101"
+                    )),
+                    timeouts_at: 401u32,
+                    delay_steps_at: None,
+                    status: CircuitStatus::PendingInsurance,
+                    total_reward: Some(fee),
+                    steps_cnt: (0, 1),
+                }
+            );
+
+            assert_eq!(
+                Circuit::get_full_side_effects(xtx_id).unwrap(),
+                vec![vec![FullSideEffect {
+                    input: valid_swap_side_effect.clone(),
+                    confirmed: None,
+                    security_lvl: SecurityLvl::Optimistic,
+                    submission_target_height: vec![0],
+                }]]
+            );
+
+            let origin_relayer_bob = Origin::signed(BOB_RELAYER); // Only sudo access to register new gateways for now
+
+            assert_ok!(Circuit::bond_insurance_deposit(
+                origin_relayer_bob,
+                xtx_id,
+                side_effect_a_id,
+            ));
+
+            let expected_bonded_insurance_deposit = InsuranceDeposit {
+                insurance: 1,
+                reward: 2,
+                requester: AccountId32::new(hex!(
+                    "0101010101010101010101010101010101010101010101010101010101010101"
+                )),
+                bonded_relayer: Some(BOB_RELAYER),
+                status: CircuitStatus::Bonded,
+                requested_at: 1,
+                reserved_bond: 0,
+            };
+
+            assert_eq!(
+                Circuit::get_insurance_deposits(xtx_id, side_effect_a_id).unwrap(),
+                expected_bonded_insurance_deposit
+            );
+
+            assert_eq!(
+                Circuit::get_x_exec_signals(xtx_id).unwrap(),
+                XExecSignal {
+                    requester: AccountId32::new(hex!(
+                        "0101010101010101010101010101010101010101010101010101010101010101"
+                    )),
+                    timeouts_at: 401u32,
+                    delay_steps_at: None,
+                    status: CircuitStatus::Ready,
+                    total_reward: Some(fee),
+                    steps_cnt: (0, 1),
+                }
+            );
+        });
+}
+
+#[test]
+fn circuit_handles_add_liquidity_without_insurance() {
+    let origin = Origin::signed(ALICE);
+
+    let ext = ExtBuilder::default();
+    let mut local_state = LocalState::new();
+
+    let add_liquidity_protocol_box =
+        Box::new(t3rn_protocol::side_effects::standards::get_add_liquidity_interface());
+
+    let valid_add_liquidity_side_effect = produce_and_validate_side_effect(
+        vec![
+            (Type::Address(32), ArgVariant::A), // argument_0: caller
+            (Type::Address(32), ArgVariant::B), // argument_1: to
+            (Type::Bytes(4), ArgVariant::A),    // argument_2: asset_left
+            (Type::Bytes(4), ArgVariant::B),    // argument_3: asset_right
+            (Type::Bytes(4), ArgVariant::C),    // argument_4: liquidity_token
+            (Type::Uint(128), ArgVariant::A),   // argument_5: amount_left
+            (Type::Uint(128), ArgVariant::B),   // argument_6: amount_right
+            (Type::Uint(128), ArgVariant::A),   // argument_7: amount_liquidity_token
+            (Type::Bytes(0), ArgVariant::A),    // argument_8: no insurance, empty bytes
+        ],
+        &mut local_state,
+        add_liquidity_protocol_box,
+    );
+
+    let side_effects = vec![valid_add_liquidity_side_effect.clone()];
+    let fee = 1;
+    let sequential = true;
+
+    ext.with_default_xdns_records()
+        .with_standard_side_effects()
+        .build()
+        .execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, 1 + 2);
+            let _ = Balances::deposit_creating(&BOB_RELAYER, 1);
+
+            System::set_block_number(1);
+            brute_seed_block_1([0, 0, 0, 0]);
+
+            assert_ok!(Circuit::on_extrinsic_trigger(
+                origin,
+                side_effects,
+                fee,
+                sequential,
+                None,
+                None,
+                None,
+            ));
+
+            let (xtx_id, side_effect_a_id) = set_ids(valid_add_liquidity_side_effect.clone());
+
+            assert_eq!(
+                Circuit::get_insurance_deposits(xtx_id, side_effect_a_id),
+                None
+            );
+        });
+}
+
+#[test]
+fn circuit_handles_add_liquidity_with_insurance() {
+    let origin = Origin::signed(ALICE);
+
+    let ext = ExtBuilder::default().with_default_executors_and_relayers();
+    let mut local_state = LocalState::new();
+
+    let add_liquidity_protocol_box =
+        Box::new(t3rn_protocol::side_effects::standards::get_add_liquidity_interface());
+
+    let valid_add_liquidity_side_effect = produce_and_validate_side_effect(
+        vec![
+            (Type::Address(32), ArgVariant::A),       // argument_0: caller
+            (Type::Address(32), ArgVariant::B),       // argument_1: to
+            (Type::Bytes(4), ArgVariant::A),          // argument_2: asset_left
+            (Type::Bytes(4), ArgVariant::B),          // argument_3: asset_right
+            (Type::Bytes(4), ArgVariant::A),          // argument_4: liquidity_token
+            (Type::Uint(128), ArgVariant::A),         // argument_5: amount_left
+            (Type::Uint(128), ArgVariant::B),         // argument_6: amount_right
+            (Type::Uint(128), ArgVariant::A),         // argument_7: amount_liquidity_token
+            (Type::OptionalInsurance, ArgVariant::A), // argument_8: Variant A insurance = 1, reward = 2
+        ],
+        &mut local_state,
+        add_liquidity_protocol_box,
+    );
+
+    let side_effects = vec![valid_add_liquidity_side_effect.clone()];
+    let fee = 1;
+    let sequential = true;
+
+    ext.with_default_xdns_records()
+        .with_standard_side_effects()
+        .build()
+        .execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, 1 + 2); // Alice should have at least: fee (1) + insurance reward (2)(for VariantA)
+            let _ = Balances::deposit_creating(&BOB_RELAYER, 1); // Bob should have at least: insurance deposit (1)(for VariantA)
+
+            System::set_block_number(1);
+            brute_seed_block_1([0, 0, 0, 0]);
+
+            assert_ok!(Circuit::on_extrinsic_trigger(
+                origin,
+                side_effects,
+                fee,
+                sequential,
+                None,
+                None,
+                None,
+            ));
+
+            let (xtx_id, side_effect_a_id) = set_ids(valid_add_liquidity_side_effect.clone());
+
+            // Runtime Apply State
+            // Returns valid insurance for that side effect
+            let valid_insurance_deposit = InsuranceDeposit {
+                insurance: 1,
+                reward: 2,
+                requester: AccountId32::new(hex!(
+                    "0101010101010101010101010101010101010101010101010101010101010101"
+                )),
+                bonded_relayer: None,
+                status: CircuitStatus::Requested,
+                requested_at: 1,
+                reserved_bond: 0,
+            };
+
+            assert_eq!(
+                Circuit::get_insurance_deposits(xtx_id, side_effect_a_id).unwrap(),
+                valid_insurance_deposit
+            );
+            assert_eq!(
+                Circuit::get_x_exec_signals(xtx_id).unwrap(),
+                XExecSignal {
+                    requester: AccountId32::new(hex!(
+                        "0101010101010101010101010101010101010101010101010101010101010101"
+                    )),
+                    timeouts_at: 401u32,
+                    delay_steps_at: None,
+                    status: CircuitStatus::PendingInsurance,
+                    total_reward: Some(fee),
+                    steps_cnt: (0, 1),
+                }
+            );
+
+            assert_eq!(
+                Circuit::get_full_side_effects(xtx_id).unwrap(),
+                vec![vec![FullSideEffect {
+                    input: valid_add_liquidity_side_effect.clone(),
+                    confirmed: None,
+                    security_lvl: SecurityLvl::Optimistic,
+                    submission_target_height: vec![0],
+                }]]
+            );
+
+            let origin_relayer_bob = Origin::signed(BOB_RELAYER); // Only sudo access to register new gateways for now
+
+            assert_ok!(Circuit::bond_insurance_deposit(
+                origin_relayer_bob,
+                xtx_id,
+                side_effect_a_id,
+            ));
+
+            let expected_bonded_insurance_deposit = InsuranceDeposit {
+                insurance: 1,
+                reward: 2,
+                requester: AccountId32::new(hex!(
+                    "0101010101010101010101010101010101010101010101010101010101010101"
+                )),
+                bonded_relayer: Some(BOB_RELAYER),
+                status: CircuitStatus::Bonded,
+                requested_at: 1,
+                reserved_bond: 0,
+            };
+
+            assert_eq!(
+                Circuit::get_insurance_deposits(xtx_id, side_effect_a_id).unwrap(),
+                expected_bonded_insurance_deposit
+            );
+
+            assert_eq!(
+                Circuit::get_x_exec_signals(xtx_id).unwrap(),
+                XExecSignal {
+                    requester: AccountId32::new(hex!(
+                        "0101010101010101010101010101010101010101010101010101010101010101"
+                    )),
+                    timeouts_at: 401u32,
+                    delay_steps_at: None,
+                    status: CircuitStatus::Ready,
+                    total_reward: Some(fee),
+                    steps_cnt: (0, 1),
+                }
+            );
+        });
+}
+
+// fn successfully_confirm_optimistic(side_effect: SideEffect<AccountId32, BlockNumber, Balance>) {
 //
 //     let from = side_effect.encoded_args[0].clone();
 //     let to = side_effect.encoded_args[1].clone();
@@ -1304,57 +1917,837 @@ fn circuit_handles_add_liquidity_with_insurance() {
 //
 // }
 
-fn successfully_bond_optimistic(
-    side_effect: SideEffect<AccountId32, BlockNumber, Balance>,
-    xtx_id: XtxId<Runtime>,
-    relayer: AccountId32,
-    submitter: AccountId32,
-) {
-    let optional_insurance = side_effect.encoded_args[3].clone();
+fn successfully_bond_optimistic(
+    side_effect: SideEffect<AccountId32, BlockNumber, Balance>,
+    xtx_id: XtxId<Runtime>,
+    relayer: AccountId32,
+    submitter: AccountId32,
+) {
+    let optional_insurance = side_effect.encoded_args[3].clone();
+
+    assert!(
+        optional_insurance.len() == 32,
+        "Wrong test value - optimistic transfer assumes optimistic arguments"
+    );
+
+    assert_ok!(Circuit::bond_insurance_deposit(
+        Origin::signed(relayer.clone()),
+        xtx_id,
+        side_effect
+            .generate_id::<circuit_runtime_pallets::pallet_circuit::SystemHashing<Runtime>>(),
+    ));
+
+    let [insurance, reward]: [u128; 2] = Decode::decode(&mut &optional_insurance[..]).unwrap();
+
+    let created_insurance_deposit = Circuit::get_insurance_deposits(
+        xtx_id,
+        side_effect
+            .generate_id::<circuit_runtime_pallets::pallet_circuit::SystemHashing<Runtime>>(),
+    )
+    .unwrap();
+
+    assert_eq!(created_insurance_deposit.insurance, insurance as u128);
+    assert_eq!(created_insurance_deposit.reward, reward as u128);
+    assert_eq!(
+        created_insurance_deposit.requester,
+        Decode::decode(&mut &submitter.encode()[..]).unwrap()
+    );
+    assert_eq!(created_insurance_deposit.bonded_relayer, Some(relayer));
+    assert_eq!(created_insurance_deposit.status, CircuitStatus::Bonded);
+    assert_eq!(created_insurance_deposit.requested_at, 1);
+}
+
+#[test]
+fn two_dirty_transfers_are_allocated_to_2_steps_and_can_be_submitted() {
+    let origin = Origin::signed(ALICE); // Only sudo access to register new gateways for now
+
+    let _origin_relayer_bob = Origin::signed(BOB_RELAYER); // Only sudo access to register new gateways for now
+
+    let transfer_protocol_box =
+        Box::new(t3rn_protocol::side_effects::standards::get_transfer_interface());
+
+    let mut local_state = LocalState::new();
+    let valid_transfer_side_effect_1 = produce_and_validate_side_effect(
+        vec![
+            (Type::Address(32), ArgVariant::A),
+            (Type::Address(32), ArgVariant::B),
+            (Type::Uint(128), ArgVariant::A),
+            (Type::Bytes(0), ArgVariant::A), // empty bytes instead of insurance
+        ],
+        &mut local_state,
+        transfer_protocol_box.clone(),
+    );
+
+    let valid_transfer_side_effect_2 = produce_and_validate_side_effect(
+        vec![
+            (Type::Address(32), ArgVariant::B),
+            (Type::Address(32), ArgVariant::A),
+            (Type::Uint(128), ArgVariant::A),
+            (Type::Bytes(0), ArgVariant::A), // empty bytes instead of insurance
+        ],
+        &mut local_state,
+        transfer_protocol_box,
+    );
+
+    let side_effects = vec![valid_transfer_side_effect_1, valid_transfer_side_effect_2];
+    let fee = 1;
+    let sequential = true;
+
+    ExtBuilder::default()
+        .with_standard_side_effects()
+        .with_default_xdns_records()
+        .build()
+        .execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, 10);
+
+            System::set_block_number(1);
+            brute_seed_block_1([0, 0, 0, 0]);
+
+            assert_ok!(Circuit::on_extrinsic_trigger(
+                origin,
+                side_effects,
+                fee,
+                sequential,
+                None,
+                None,
+                None,
+            ));
+
+            let events = System::events();
+            assert_eq!(events.len(), 5);
+        });
+}
+
+#[test]
+fn two_dirty_transfers_are_allocated_to_2_steps_and_can_be_confirmed() {
+    let origin = Origin::signed(ALICE); // Only sudo access to register new gateways for now
+
+    let _origin_relayer_bob = Origin::signed(BOB_RELAYER); // Only sudo access to register new gateways for now
+
+    let transfer_protocol_box =
+        Box::new(t3rn_protocol::side_effects::standards::get_transfer_interface());
+
+    let mut local_state = LocalState::new();
+    let valid_transfer_side_effect_1 = produce_and_validate_side_effect(
+        vec![
+            (Type::Address(32), ArgVariant::A),
+            (Type::Address(32), ArgVariant::B),
+            (Type::Uint(128), ArgVariant::A),
+            (Type::Bytes(0), ArgVariant::A), // empty bytes instead of insurance
+        ],
+        &mut local_state,
+        transfer_protocol_box.clone(),
+    );
+
+    let valid_transfer_side_effect_2 = produce_and_validate_side_effect(
+        vec![
+            (Type::Address(32), ArgVariant::B),
+            (Type::Address(32), ArgVariant::A),
+            (Type::Uint(128), ArgVariant::A),
+            (Type::Bytes(0), ArgVariant::A), // empty bytes instead of insurance
+        ],
+        &mut local_state,
+        transfer_protocol_box,
+    );
+
+    let side_effects = vec![valid_transfer_side_effect_1, valid_transfer_side_effect_2];
+    let fee = 1;
+    let sequential = true;
+
+    ExtBuilder::default()
+        .with_standard_side_effects()
+        .with_default_xdns_records()
+        .build()
+        .execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+
+            System::set_block_number(1);
+            brute_seed_block_1([0, 0, 0, 0]);
+
+            assert_ok!(Circuit::on_extrinsic_trigger(
+                origin,
+                side_effects,
+                fee,
+                sequential,
+                None,
+                None,
+                None,
+            ));
+        });
+}
+
+#[test]
+fn sequential_side_effects_are_allocated_to_steps_in_submitted_order() {
+    let origin = Origin::signed(ALICE);
+
+    let transfer_protocol_box =
+        Box::new(t3rn_protocol::side_effects::standards::get_transfer_interface());
+
+    let mut local_state = LocalState::new();
+    let dirty_transfer_side_effect = produce_and_validate_side_effect(
+        vec![
+            (Type::Address(32), ArgVariant::A),
+            (Type::Address(32), ArgVariant::B),
+            (Type::Uint(128), ArgVariant::A),
+            (Type::Bytes(0), ArgVariant::A), // empty bytes instead of insurance
+        ],
+        &mut local_state,
+        transfer_protocol_box.clone(),
+    );
+
+    let insured_transfer_side_effect = produce_and_validate_side_effect(
+        vec![
+            (Type::Address(32), ArgVariant::B),
+            (Type::Address(32), ArgVariant::A),
+            (Type::Uint(128), ArgVariant::A),
+            (Type::OptionalInsurance, ArgVariant::A),
+        ],
+        &mut local_state,
+        transfer_protocol_box,
+    );
+
+    // Automatic ordering would line up the optimistic transfer first
+    let side_effects = vec![
+        dirty_transfer_side_effect.clone(),
+        insured_transfer_side_effect.clone(),
+    ];
+    let fee = 1;
+    let sequential = true;
+
+    ExtBuilder::default()
+        .with_standard_side_effects()
+        .with_default_xdns_records()
+        .build()
+        .execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+
+            System::set_block_number(1);
+            brute_seed_block_1([0, 0, 0, 0]);
+
+            assert_ok!(Circuit::on_extrinsic_trigger(
+                origin,
+                side_effects,
+                fee,
+                sequential,
+                None,
+                None,
+                None,
+            ));
+
+            let (xtx_id, _timeouts_at) = ActiveXExecSignalsTimingLinks::<Runtime>::iter()
+                .next()
+                .unwrap();
+
+            let steps = Circuit::get_full_side_effects(xtx_id).unwrap();
+            assert_eq!(steps.len(), 2);
+            assert_eq!(steps[0].len(), 1);
+            assert_eq!(steps[0][0].input, dirty_transfer_side_effect);
+            assert_eq!(steps[1].len(), 1);
+            assert_eq!(steps[1][0].input, insured_transfer_side_effect);
+            assert_eq!(
+                Circuit::get_x_exec_signals(xtx_id).unwrap().steps_cnt,
+                (0, 2)
+            );
+        });
+}
+
+#[test]
+fn side_effects_are_allocated_to_explicit_steps() {
+    let origin = Origin::signed(ALICE);
+
+    let transfer_protocol_box =
+        Box::new(t3rn_protocol::side_effects::standards::get_transfer_interface());
+
+    let mut local_state = LocalState::new();
+    let valid_transfer_side_effect_1 = produce_and_validate_side_effect(
+        vec![
+            (Type::Address(32), ArgVariant::A),
+            (Type::Address(32), ArgVariant::B),
+            (Type::Uint(128), ArgVariant::A),
+            (Type::Bytes(0), ArgVariant::A), // empty bytes instead of insurance
+        ],
+        &mut local_state,
+        transfer_protocol_box.clone(),
+    );
+
+    let valid_transfer_side_effect_2 = produce_and_validate_side_effect(
+        vec![
+            (Type::Address(32), ArgVariant::B),
+            (Type::Address(32), ArgVariant::A),
+            (Type::Uint(128), ArgVariant::A),
+            (Type::Bytes(0), ArgVariant::A), // empty bytes instead of insurance
+        ],
+        &mut local_state,
+        transfer_protocol_box,
+    );
+
+    let side_effects = vec![
+        valid_transfer_side_effect_1.clone(),
+        valid_transfer_side_effect_2.clone(),
+    ];
+    let fee = 1;
+    let sequential = false;
+
+    ExtBuilder::default()
+        .with_standard_side_effects()
+        .with_default_xdns_records()
+        .build()
+        .execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+
+            System::set_block_number(1);
+            brute_seed_block_1([0, 0, 0, 0]);
+
+            // Every side effect needs a step, steps can't be skipped and take max 1 dirty side effect
+            for invalid_steps in [vec![0], vec![0, 2], vec![0, u32::MAX], vec![0, 0]] {
+                assert_noop!(
+                    Circuit::on_extrinsic_trigger(
+                        origin.clone(),
+                        side_effects.clone(),
+                        fee,
+                        sequential,
+                        Some(invalid_steps),
+                        None,
+                        None,
+                    ),
+                    pallet_circuit::Error::<Runtime>::SideEffectsValidationFailed
+                );
+            }
+
+            assert_ok!(Circuit::on_extrinsic_trigger(
+                origin,
+                side_effects,
+                fee,
+                sequential,
+                Some(vec![1, 0]),
+                None,
+                None,
+            ));
+
+            let (xtx_id, _timeouts_at) = ActiveXExecSignalsTimingLinks::<Runtime>::iter()
+                .next()
+                .unwrap();
+
+            let steps = Circuit::get_full_side_effects(xtx_id).unwrap();
+            assert_eq!(steps.len(), 2);
+            assert_eq!(steps[0][0].input, valid_transfer_side_effect_2);
+            assert_eq!(steps[1][0].input, valid_transfer_side_effect_1);
+        });
+}
+
+#[test]
+fn confirm_side_effect_rejects_dirty_transfer_without_inclusion_proof() {
+    let origin = Origin::signed(ALICE);
+
+    let transfer_protocol_box =
+        Box::new(t3rn_protocol::side_effects::standards::get_transfer_interface());
+
+    let mut local_state = LocalState::new();
+    let valid_transfer_side_effect = produce_and_validate_side_effect(
+        vec![
+            (Type::Address(32), ArgVariant::A),
+            (Type::Address(32), ArgVariant::B),
+            (Type::Uint(128), ArgVariant::A),
+            (Type::Bytes(0), ArgVariant::A), // empty bytes instead of insurance
+        ],
+        &mut local_state,
+        transfer_protocol_box,
+    );
+
+    let side_effects = vec![valid_transfer_side_effect.clone()];
+    let fee = 1;
+    let sequential = true;
+
+    ExtBuilder::default()
+        .with_default_executors_and_relayers()
+        .with_standard_side_effects()
+        .with_default_xdns_records()
+        .build()
+        .execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, 1 + 2);
+
+            System::set_block_number(1);
+            brute_seed_block_1([0, 0, 0, 0]);
+
+            assert_ok!(Circuit::on_extrinsic_trigger(
+                origin,
+                side_effects,
+                fee,
+                sequential,
+                None,
+                None,
+                None,
+            ));
+
+            let (xtx_id, _side_effect_id) = set_ids(valid_transfer_side_effect.clone());
 
-    assert!(
-        optional_insurance.len() == 32,
-        "Wrong test value - optimistic transfer assumes optimistic arguments"
+            let confirmation = ConfirmedSideEffect::<AccountId32, BlockNumber, BalanceOf> {
+                err: None,
+                output: None,
+                inclusion_data: vec![],
+                executioner: BOB_RELAYER,
+                received_at: 1,
+                cost: None,
+            };
+
+            assert_noop!(
+                Circuit::confirm_side_effect(
+                    Origin::signed(BOB_RELAYER),
+                    xtx_id,
+                    valid_transfer_side_effect,
+                    confirmation,
+                    None,
+                    None,
+                ),
+                "SideEffect confirmation requires inclusion proof and block hash"
+            );
+        });
+}
+
+#[test]
+fn bond_insurance_deposit_requires_active_or_bonded_executor() {
+    ExtBuilder::default()
+        .with_active_executors(vec![EXECUTOR_DEFAULT])
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Circuit::bond_insurance_deposit(
+                    Origin::signed(CHARLIE),
+                    sp_core::H256::zero(),
+                    sp_core::H256::zero(),
+                ),
+                pallet_circuit::Error::<Runtime>::UnauthorizedExecutor
+            );
+
+            // Authorized executors get through to the Xtx lookup
+            assert_noop!(
+                Circuit::bond_insurance_deposit(
+                    Origin::signed(EXECUTOR_DEFAULT),
+                    sp_core::H256::zero(),
+                    sp_core::H256::zero(),
+                ),
+                pallet_circuit::Error::<Runtime>::SetupFailedUnknownXtx
+            );
+        });
+}
+
+#[test]
+fn confirm_side_effect_requires_registered_relayer() {
+    let transfer_protocol_box =
+        Box::new(t3rn_protocol::side_effects::standards::get_transfer_interface());
+
+    let mut local_state = LocalState::new();
+    let valid_transfer_side_effect = produce_and_validate_side_effect(
+        vec![
+            (Type::Address(32), ArgVariant::A),
+            (Type::Address(32), ArgVariant::B),
+            (Type::Uint(128), ArgVariant::A),
+            (Type::Bytes(0), ArgVariant::A), // empty bytes instead of insurance
+        ],
+        &mut local_state,
+        transfer_protocol_box,
+    );
+
+    let confirmation = ConfirmedSideEffect::<AccountId32, BlockNumber, BalanceOf> {
+        err: None,
+        output: None,
+        inclusion_data: vec![],
+        executioner: CHARLIE,
+        received_at: 1,
+        cost: None,
+    };
+
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            Circuit::confirm_side_effect(
+                Origin::signed(CHARLIE),
+                sp_core::H256::zero(),
+                valid_transfer_side_effect.clone(),
+                confirmation.clone(),
+                None,
+                None,
+            ),
+            pallet_circuit::Error::<Runtime>::UnauthorizedRelayer
+        );
+
+        assert_ok!(Circuit::register_relayer(Origin::root(), CHARLIE));
+        assert_noop!(
+            Circuit::register_relayer(Origin::root(), CHARLIE),
+            pallet_circuit::Error::<Runtime>::RelayerAlreadyRegistered
+        );
+
+        // Registered relayers get through to the Xtx lookup
+        assert_noop!(
+            Circuit::confirm_side_effect(
+                Origin::signed(CHARLIE),
+                sp_core::H256::zero(),
+                valid_transfer_side_effect.clone(),
+                confirmation.clone(),
+                None,
+                None,
+            ),
+            pallet_circuit::Error::<Runtime>::SetupFailedUnknownXtx
+        );
+
+        assert_ok!(Circuit::deregister_relayer(Origin::root(), CHARLIE));
+        assert_noop!(
+            Circuit::confirm_side_effect(
+                Origin::signed(CHARLIE),
+                sp_core::H256::zero(),
+                valid_transfer_side_effect,
+                confirmation,
+                None,
+                None,
+            ),
+            pallet_circuit::Error::<Runtime>::UnauthorizedRelayer
+        );
+    });
+}
+
+#[test]
+fn on_extrinsic_trigger_rejects_delays_not_matching_steps() {
+    let origin = Origin::signed(ALICE);
+
+    let transfer_protocol_box =
+        Box::new(t3rn_protocol::side_effects::standards::get_transfer_interface());
+
+    let mut local_state = LocalState::new();
+    let valid_transfer_side_effect_1 = produce_and_validate_side_effect(
+        vec![
+            (Type::Address(32), ArgVariant::A),
+            (Type::Address(32), ArgVariant::B),
+            (Type::Uint(128), ArgVariant::A),
+            (Type::Bytes(0), ArgVariant::A), // empty bytes instead of insurance
+        ],
+        &mut local_state,
+        transfer_protocol_box.clone(),
+    );
+
+    let valid_transfer_side_effect_2 = produce_and_validate_side_effect(
+        vec![
+            (Type::Address(32), ArgVariant::B),
+            (Type::Address(32), ArgVariant::A),
+            (Type::Uint(128), ArgVariant::A),
+            (Type::Bytes(0), ArgVariant::A), // empty bytes instead of insurance
+        ],
+        &mut local_state,
+        transfer_protocol_box,
+    );
+
+    let side_effects = vec![valid_transfer_side_effect_1, valid_transfer_side_effect_2];
+    let fee = 1;
+    let sequential = true;
+
+    ExtBuilder::default()
+        .with_standard_side_effects()
+        .with_default_xdns_records()
+        .build()
+        .execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+
+            System::set_block_number(1);
+            brute_seed_block_1([0, 0, 0, 0]);
+
+            // Two dirty transfers make two steps
+            assert_noop!(
+                Circuit::on_extrinsic_trigger(
+                    origin.clone(),
+                    side_effects.clone(),
+                    fee,
+                    sequential,
+                    None,
+                    Some(vec![10]),
+                    None,
+                ),
+                pallet_circuit::Error::<Runtime>::InvalidDelayedStepsSchedule
+            );
+
+            // Steps can't be scheduled to open in reverse order
+            assert_noop!(
+                Circuit::on_extrinsic_trigger(
+                    origin.clone(),
+                    side_effects.clone(),
+                    fee,
+                    sequential,
+                    None,
+                    Some(vec![20, 10]),
+                    None,
+                ),
+                pallet_circuit::Error::<Runtime>::InvalidDelayedStepsSchedule
+            );
+
+            // Nor after the Xtx times out
+            assert_noop!(
+                Circuit::on_extrinsic_trigger(
+                    origin.clone(),
+                    side_effects.clone(),
+                    fee,
+                    sequential,
+                    None,
+                    Some(vec![10, 401]),
+                    None,
+                ),
+                pallet_circuit::Error::<Runtime>::InvalidDelayedStepsSchedule
+            );
+
+            assert_ok!(Circuit::on_extrinsic_trigger(
+                origin,
+                side_effects,
+                fee,
+                sequential,
+                None,
+                Some(vec![10, 20]),
+                None,
+            ));
+        });
+}
+
+#[test]
+fn on_extrinsic_trigger_rejects_native_transfers_on_ethereum_gateways() {
+    let origin = Origin::signed(ALICE);
+
+    let transfer_protocol_box =
+        Box::new(t3rn_protocol::side_effects::standards::get_transfer_interface());
+
+    let mut local_state = LocalState::new();
+    let mut valid_transfer_side_effect = produce_and_validate_side_effect(
+        vec![
+            (Type::Address(32), ArgVariant::A),
+            (Type::Address(32), ArgVariant::B),
+            (Type::Uint(128), ArgVariant::A),
+            (Type::Bytes(0), ArgVariant::A), // empty bytes instead of insurance
+        ],
+        &mut local_state,
+        transfer_protocol_box,
+    );
+    valid_transfer_side_effect.target = *b"eth2";
+
+    ExtBuilder::default()
+        .with_standard_side_effects()
+        .with_default_xdns_records()
+        .build()
+        .execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, 1);
+
+            assert_ok!(XDNS::add_new_xdns_record(
+                Origin::root(),
+                b"eth".to_vec(),
+                *b"eth2",
+                None,
+                Default::default(),
+                GatewayVendor::Ethereum,
+                GatewayType::TxOnly(0),
+                Default::default(),
+                GatewaySysProps {
+                    ss58_format: 0,
+                    token_symbol: b"ETH".to_vec(),
+                    token_decimals: 18,
+                },
+                vec![],
+                vec![*b"tran"],
+            ));
+
+            assert_noop!(
+                Circuit::on_extrinsic_trigger(
+                    origin,
+                    vec![valid_transfer_side_effect],
+                    1,
+                    true,
+                    None,
+                    None,
+                    None,
+                ),
+                "Native transfers can't be confirmed on Ethereum gateways"
+            );
+        });
+}
+
+#[test]
+fn on_extrinsic_trigger_sets_requested_timeout_within_bounds() {
+    let origin = Origin::signed(ALICE);
+
+    let transfer_protocol_box =
+        Box::new(t3rn_protocol::side_effects::standards::get_transfer_interface());
+
+    let mut local_state = LocalState::new();
+    let valid_transfer_side_effect = produce_and_validate_side_effect(
+        vec![
+            (Type::Address(32), ArgVariant::A),
+            (Type::Address(32), ArgVariant::B),
+            (Type::Uint(128), ArgVariant::A),
+            (Type::Bytes(0), ArgVariant::A), // empty bytes instead of insurance
+        ],
+        &mut local_state,
+        transfer_protocol_box,
+    );
+
+    let side_effects = vec![valid_transfer_side_effect];
+    let fee = 1;
+    let sequential = true;
+
+    ExtBuilder::default()
+        .with_standard_side_effects()
+        .with_default_xdns_records()
+        .build()
+        .execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, 1 + 2);
+
+            System::set_block_number(1);
+            brute_seed_block_1([0, 0, 0, 0]);
+
+            assert_noop!(
+                Circuit::on_extrinsic_trigger(
+                    origin.clone(),
+                    side_effects.clone(),
+                    fee,
+                    sequential,
+                    None,
+                    None,
+                    Some(5),
+                ),
+                pallet_circuit::Error::<Runtime>::InvalidXtxTimeout
+            );
+
+            assert_noop!(
+                Circuit::on_extrinsic_trigger(
+                    origin.clone(),
+                    side_effects.clone(),
+                    fee,
+                    sequential,
+                    None,
+                    None,
+                    Some(20_000),
+                ),
+                pallet_circuit::Error::<Runtime>::InvalidXtxTimeout
+            );
+
+            assert_ok!(Circuit::on_extrinsic_trigger(
+                origin,
+                side_effects,
+                fee,
+                sequential,
+                None,
+                None,
+                Some(100),
+            ));
+
+            let (xtx_id, timeouts_at) = ActiveXExecSignalsTimingLinks::<Runtime>::iter()
+                .next()
+                .unwrap();
+
+            assert_eq!(timeouts_at, 101);
+            assert_eq!(
+                Circuit::get_x_exec_signals(xtx_id).unwrap().timeouts_at,
+                101
+            );
+        });
+}
+
+#[test]
+fn on_extrinsic_trigger_rejects_more_side_effects_than_max_per_xtx() {
+    let origin = Origin::signed(ALICE);
+
+    let transfer_protocol_box =
+        Box::new(t3rn_protocol::side_effects::standards::get_transfer_interface());
+
+    let mut local_state = LocalState::new();
+    let valid_transfer_side_effect = produce_and_validate_side_effect(
+        vec![
+            (Type::Address(32), ArgVariant::A),
+            (Type::Address(32), ArgVariant::B),
+            (Type::Uint(128), ArgVariant::A),
+            (Type::Bytes(0), ArgVariant::A), // empty bytes instead of insurance
+        ],
+        &mut local_state,
+        transfer_protocol_box,
+    );
+
+    let max_side_effects =
+        <Runtime as pallet_circuit::Config>::MaxSideEffectsPerXtx::get() as usize;
+    let side_effects = vec![valid_transfer_side_effect; max_side_effects + 1];
+
+    ExtBuilder::default()
+        .with_standard_side_effects()
+        .with_default_xdns_records()
+        .build()
+        .execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, 1 + 2);
+
+            System::set_block_number(1);
+            brute_seed_block_1([0, 0, 0, 0]);
+
+            assert_noop!(
+                Circuit::on_extrinsic_trigger(origin, side_effects, 1, true, None, None, None,),
+                pallet_circuit::Error::<Runtime>::TooManySideEffects
+            );
+        });
+}
+
+#[test]
+fn on_extrinsic_trigger_default_timeout_waits_for_slowest_target_finality() {
+    let origin = Origin::signed(ALICE);
+
+    let transfer_protocol_box =
+        Box::new(t3rn_protocol::side_effects::standards::get_transfer_interface());
+
+    let mut local_state = LocalState::new();
+    let valid_transfer_side_effect = produce_and_validate_side_effect(
+        vec![
+            (Type::Address(32), ArgVariant::A),
+            (Type::Address(32), ArgVariant::B),
+            (Type::Uint(128), ArgVariant::A),
+            (Type::Bytes(0), ArgVariant::A), // empty bytes instead of insurance
+        ],
+        &mut local_state,
+        transfer_protocol_box,
     );
 
-    assert_ok!(Circuit::bond_insurance_deposit(
-        Origin::signed(relayer.clone()),
-        xtx_id,
-        side_effect
-            .generate_id::<circuit_runtime_pallets::pallet_circuit::SystemHashing<Runtime>>(),
-    ));
+    let side_effects = vec![valid_transfer_side_effect.clone()];
+    let fee = 1;
+    let sequential = true;
 
-    let [insurance, reward]: [u128; 2] = Decode::decode(&mut &optional_insurance[..]).unwrap();
+    ExtBuilder::default()
+        .with_standard_side_effects()
+        .with_default_xdns_records()
+        .build()
+        .execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, 1 + 2);
 
-    let created_insurance_deposit = Circuit::get_insurance_deposits(
-        xtx_id,
-        side_effect
-            .generate_id::<circuit_runtime_pallets::pallet_circuit::SystemHashing<Runtime>>(),
-    )
-    .unwrap();
+            System::set_block_number(1);
+            brute_seed_block_1([0, 0, 0, 0]);
 
-    assert_eq!(created_insurance_deposit.insurance, insurance as u128);
-    assert_eq!(created_insurance_deposit.reward, reward as u128);
-    assert_eq!(
-        created_insurance_deposit.requester,
-        Decode::decode(&mut &submitter.encode()[..]).unwrap()
-    );
-    assert_eq!(created_insurance_deposit.bonded_relayer, Some(relayer));
-    assert_eq!(created_insurance_deposit.status, CircuitStatus::Bonded);
-    assert_eq!(created_insurance_deposit.requested_at, 1);
-}
+            assert_ok!(XDNS::set_finality_delay(
+                Origin::root(),
+                valid_transfer_side_effect.target,
+                100
+            ));
+            assert_eq!(Circuit::default_timeout(&side_effects), 500);
 
-#[test]
-fn two_dirty_transfers_are_allocated_to_2_steps_and_can_be_submitted() {
-    let origin = Origin::signed(ALICE); // Only sudo access to register new gateways for now
+            assert_ok!(Circuit::on_extrinsic_trigger(
+                origin,
+                side_effects,
+                fee,
+                sequential,
+                None,
+                None,
+                None,
+            ));
 
-    let _origin_relayer_bob = Origin::signed(BOB_RELAYER); // Only sudo access to register new gateways for now
+            let (_xtx_id, timeouts_at) = ActiveXExecSignalsTimingLinks::<Runtime>::iter()
+                .next()
+                .unwrap();
+
+            assert_eq!(timeouts_at, 501);
+        });
+}
 
+#[test]
+fn challenge_side_effect_requires_open_challenge_window() {
     let transfer_protocol_box =
         Box::new(t3rn_protocol::side_effects::standards::get_transfer_interface());
 
     let mut local_state = LocalState::new();
-    let valid_transfer_side_effect_1 = produce_and_validate_side_effect(
+    let valid_transfer_side_effect = produce_and_validate_side_effect(
         vec![
             (Type::Address(32), ArgVariant::A),
             (Type::Address(32), ArgVariant::B),
@@ -1362,57 +2755,200 @@ fn two_dirty_transfers_are_allocated_to_2_steps_and_can_be_submitted() {
             (Type::Bytes(0), ArgVariant::A), // empty bytes instead of insurance
         ],
         &mut local_state,
-        transfer_protocol_box.clone(),
+        transfer_protocol_box,
     );
+    let sfx_id = valid_transfer_side_effect
+        .generate_id::<circuit_runtime_pallets::pallet_circuit::SystemHashing<Runtime>>();
 
-    let valid_transfer_side_effect_2 = produce_and_validate_side_effect(
+    ExtBuilder::default()
+        .with_default_executors_and_relayers()
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Circuit::challenge_side_effect(
+                    Origin::signed(CHARLIE),
+                    sp_core::H256::zero(),
+                    valid_transfer_side_effect.clone(),
+                    vec![],
+                ),
+                pallet_circuit::Error::<Runtime>::UnauthorizedExecutor
+            );
+
+            assert_noop!(
+                Circuit::challenge_side_effect(
+                    Origin::signed(EXECUTOR_SECOND),
+                    sp_core::H256::zero(),
+                    valid_transfer_side_effect.clone(),
+                    vec![],
+                ),
+                pallet_circuit::Error::<Runtime>::ConfirmationNotChallengeable
+            );
+
+            pallet_circuit::ChallengeableConfirmations::<Runtime>::insert(
+                sp_core::H256::zero(),
+                sfx_id,
+                (3, sp_core::H256::zero().encode()),
+            );
+            System::set_block_number(4);
+
+            assert_noop!(
+                Circuit::challenge_side_effect(
+                    Origin::signed(EXECUTOR_SECOND),
+                    sp_core::H256::zero(),
+                    valid_transfer_side_effect.clone(),
+                    vec![],
+                ),
+                pallet_circuit::Error::<Runtime>::ChallengeWindowClosed
+            );
+
+            // Within the window the challenge gets through to the Xtx lookup
+            System::set_block_number(3);
+            assert_noop!(
+                Circuit::challenge_side_effect(
+                    Origin::signed(EXECUTOR_SECOND),
+                    sp_core::H256::zero(),
+                    valid_transfer_side_effect,
+                    vec![],
+                ),
+                pallet_circuit::Error::<Runtime>::SetupFailedUnknownXtx
+            );
+        });
+}
+
+#[test]
+fn challenge_side_effect_slashes_executor_of_confirmed_execution_not_matching_side_effect() {
+    let origin = Origin::signed(ALICE);
+
+    let transfer_protocol_box =
+        Box::new(t3rn_protocol::side_effects::standards::get_transfer_interface());
+
+    let mut local_state = LocalState::new();
+    let valid_transfer_side_effect = produce_and_validate_side_effect(
         vec![
-            (Type::Address(32), ArgVariant::B),
             (Type::Address(32), ArgVariant::A),
+            (Type::Address(32), ArgVariant::B),
             (Type::Uint(128), ArgVariant::A),
-            (Type::Bytes(0), ArgVariant::A), // empty bytes instead of insurance
+            (Type::OptionalInsurance, ArgVariant::A), // insurance = 1, reward = 2
         ],
         &mut local_state,
         transfer_protocol_box,
     );
 
-    let side_effects = vec![valid_transfer_side_effect_1, valid_transfer_side_effect_2];
+    let side_effects = vec![valid_transfer_side_effect.clone()];
     let fee = 1;
     let sequential = true;
 
+    // Transfer of the bonded executor to someone else than requested - Balances::Transfer
+    let executed_transfer = (4u8, 2u8, BOB_RELAYER, CHARLIE, 2u64).encode();
+
     ExtBuilder::default()
+        .with_default_executors_and_relayers()
         .with_standard_side_effects()
         .with_default_xdns_records()
         .build()
         .execute_with(|| {
-            let _ = Balances::deposit_creating(&ALICE, 10);
+            let _ = Balances::deposit_creating(&ALICE, 1 + 2);
+            let _ = Balances::deposit_creating(&BOB_RELAYER, 1);
 
             System::set_block_number(1);
-            brute_seed_block_1([0, 0, 0, 0]);
+            pallet_grandpa_finality_verifier::mock::brute_seed_block_with_events::<Runtime, ()>(
+                [0, 0, 0, 0],
+                1,
+                vec![],
+            );
 
             assert_ok!(Circuit::on_extrinsic_trigger(
                 origin,
                 side_effects,
                 fee,
                 sequential,
+                None,
+                None,
+                None,
             ));
 
-            let events = System::events();
-            assert_eq!(events.len(), 5);
+            let (xtx_id, sfx_id) = set_ids(valid_transfer_side_effect.clone());
+
+            assert_ok!(Circuit::bond_insurance_deposit(
+                Origin::signed(BOB_RELAYER),
+                xtx_id,
+                sfx_id,
+            ));
+
+            let (block_hash, inclusion_proof) =
+                pallet_grandpa_finality_verifier::mock::brute_seed_block_with_events::<Runtime, ()>(
+                    [0, 0, 0, 0],
+                    2,
+                    executed_transfer.clone(),
+                );
+
+            // A confirmation which got through with the execution not matching the side effect
+            pallet_circuit::FullSideEffects::<Runtime>::mutate(xtx_id, |steps| {
+                steps.as_mut().unwrap()[0][0].confirmed =
+                    Some(ConfirmedSideEffect::<AccountId32, BlockNumber, BalanceOf> {
+                        err: None,
+                        output: None,
+                        inclusion_data: executed_transfer,
+                        executioner: BOB_RELAYER,
+                        received_at: 1,
+                        cost: None,
+                    });
+            });
+            pallet_circuit::ChallengeableConfirmations::<Runtime>::insert(
+                xtx_id,
+                sfx_id,
+                (3, block_hash.encode()),
+            );
+
+            // The executor can't challenge its own confirmation
+            assert_noop!(
+                Circuit::challenge_side_effect(
+                    Origin::signed(BOB_RELAYER),
+                    xtx_id,
+                    valid_transfer_side_effect.clone(),
+                    inclusion_proof.clone(),
+                ),
+                pallet_circuit::Error::<Runtime>::ChallengerIsExecutioner
+            );
+
+            assert_ok!(Circuit::challenge_side_effect(
+                Origin::signed(EXECUTOR_SECOND),
+                xtx_id,
+                valid_transfer_side_effect,
+                inclusion_proof,
+            ));
+
+            assert_eq!(
+                Circuit::get_x_exec_signals(xtx_id).unwrap().status,
+                CircuitStatus::RevertMisbehaviour
+            );
+            assert!(Circuit::get_challengeable_confirmations(xtx_id, sfx_id).is_none());
+
+            // The insurance of the executor is slashed
+            assert_eq!(Balances::free_balance(&BOB_RELAYER), 0);
+            assert_eq!(Balances::reserved_balance(&BOB_RELAYER), 0);
+
+            let slashed = pallet_circuit::Event::<Runtime>::ExecutorMisbehaviourSlashed(
+                xtx_id,
+                sfx_id,
+                BOB_RELAYER,
+                EXECUTOR_SECOND,
+            );
+            assert!(System::events()
+                .iter()
+                .any(|record| record.event == Event::Circuit(slashed.clone())));
         });
 }
 
 #[test]
-fn two_dirty_transfers_are_allocated_to_2_steps_and_can_be_confirmed() {
-    let origin = Origin::signed(ALICE); // Only sudo access to register new gateways for now
-
-    let _origin_relayer_bob = Origin::signed(BOB_RELAYER); // Only sudo access to register new gateways for now
+fn confirm_side_effect_rejects_step_scheduled_for_later_block() {
+    let origin = Origin::signed(ALICE);
 
     let transfer_protocol_box =
         Box::new(t3rn_protocol::side_effects::standards::get_transfer_interface());
 
     let mut local_state = LocalState::new();
-    let valid_transfer_side_effect_1 = produce_and_validate_side_effect(
+    let valid_transfer_side_effect = produce_and_validate_side_effect(
         vec![
             (Type::Address(32), ArgVariant::A),
             (Type::Address(32), ArgVariant::B),
@@ -1420,30 +2956,20 @@ fn two_dirty_transfers_are_allocated_to_2_steps_and_can_be_confirmed() {
             (Type::Bytes(0), ArgVariant::A), // empty bytes instead of insurance
         ],
         &mut local_state,
-        transfer_protocol_box.clone(),
-    );
-
-    let valid_transfer_side_effect_2 = produce_and_validate_side_effect(
-        vec![
-            (Type::Address(32), ArgVariant::B),
-            (Type::Address(32), ArgVariant::A),
-            (Type::Uint(128), ArgVariant::A),
-            (Type::Bytes(0), ArgVariant::A), // empty bytes instead of insurance
-        ],
-        &mut local_state,
         transfer_protocol_box,
     );
 
-    let side_effects = vec![valid_transfer_side_effect_1, valid_transfer_side_effect_2];
+    let side_effects = vec![valid_transfer_side_effect.clone()];
     let fee = 1;
     let sequential = true;
 
     ExtBuilder::default()
+        .with_default_executors_and_relayers()
         .with_standard_side_effects()
         .with_default_xdns_records()
         .build()
         .execute_with(|| {
-            let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+            let _ = Balances::deposit_creating(&ALICE, 1 + 2);
 
             System::set_block_number(1);
             brute_seed_block_1([0, 0, 0, 0]);
@@ -1453,7 +2979,53 @@ fn two_dirty_transfers_are_allocated_to_2_steps_and_can_be_confirmed() {
                 side_effects,
                 fee,
                 sequential,
+                None,
+                Some(vec![50]),
+                None,
             ));
+
+            let (xtx_id, _side_effect_id) = set_ids(valid_transfer_side_effect.clone());
+
+            assert_eq!(
+                Circuit::get_x_exec_signals(xtx_id).unwrap().delay_steps_at,
+                Some(vec![50])
+            );
+
+            let confirmation = ConfirmedSideEffect::<AccountId32, BlockNumber, BalanceOf> {
+                err: None,
+                output: None,
+                inclusion_data: vec![],
+                executioner: BOB_RELAYER,
+                received_at: 1,
+                cost: None,
+            };
+
+            assert_noop!(
+                Circuit::confirm_side_effect(
+                    Origin::signed(BOB_RELAYER),
+                    xtx_id,
+                    valid_transfer_side_effect.clone(),
+                    confirmation.clone(),
+                    None,
+                    None,
+                ),
+                pallet_circuit::Error::<Runtime>::StepNotOpenYet
+            );
+
+            // Once the step opens, the confirmation goes on to be verified
+            System::set_block_number(50);
+
+            assert_noop!(
+                Circuit::confirm_side_effect(
+                    Origin::signed(BOB_RELAYER),
+                    xtx_id,
+                    valid_transfer_side_effect,
+                    confirmation,
+                    None,
+                    None,
+                ),
+                "SideEffect confirmation requires inclusion proof and block hash"
+            );
         });
 }
 
@@ -1527,6 +3099,9 @@ fn circuit_handles_transfer_dirty_and_optimistic_and_swap() {
                 side_effects,
                 fee,
                 sequential,
+                None,
+                None,
+                None,
             ));
         });
 }
@@ -1572,6 +3147,9 @@ fn circuit_cancels_xtx_after_timeout() {
                 side_effects,
                 fee,
                 sequential,
+                None,
+                None,
+                None,
             ));
 
             let _events = System::events();
@@ -1642,6 +3220,149 @@ fn circuit_cancels_xtx_after_timeout() {
         });
 }
 
+#[test]
+fn circuit_cancels_all_timed_out_xtx_in_one_sweep_and_refunds_requester() {
+    let origin = Origin::signed(ALICE);
+
+    let sequential = false;
+
+    ExtBuilder::default()
+        .with_standard_side_effects()
+        .with_default_xdns_records()
+        .build()
+        .execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+
+            System::set_block_number(1);
+            brute_seed_block_1([0, 0, 0, 0]);
+
+            // Xtx ids are derived from the signal, so distinct fees give distinct Xtx
+            for (fee, prize) in [(1, 2), (2, 3), (3, 5)] {
+                let mut local_state = LocalState::new();
+                let mut transfer_side_effect = produce_and_validate_side_effect(
+                    vec![
+                        (Type::Address(32), ArgVariant::A),
+                        (Type::Address(32), ArgVariant::B),
+                        (Type::Uint(128), ArgVariant::A),
+                        (Type::Bytes(0), ArgVariant::A), // empty bytes instead of insurance
+                    ],
+                    &mut local_state,
+                    Box::new(t3rn_protocol::side_effects::standards::get_transfer_interface()),
+                );
+                transfer_side_effect.prize = prize;
+
+                assert_ok!(Circuit::on_extrinsic_trigger(
+                    origin.clone(),
+                    vec![transfer_side_effect],
+                    fee,
+                    sequential,
+                    None,
+                    None,
+                    None,
+                ));
+            }
+
+            let xtx_ids = circuit_runtime_pallets::pallet_circuit::ActiveXExecSignalsTimingLinks::<
+                Runtime,
+            >::iter_keys()
+            .collect::<Vec<_>>();
+            assert_eq!(xtx_ids.len(), 3);
+            assert_eq!(Balances::reserved_balance(&ALICE), 2 + 3 + 5);
+
+            System::set_block_number(410);
+
+            <Circuit as frame_support::traits::OnInitialize<BlockNumber>>::on_initialize(110);
+
+            for xtx_id in xtx_ids {
+                assert_eq!(
+                    Circuit::get_x_exec_signals(xtx_id).unwrap().status,
+                    CircuitStatus::RevertTimedOut
+                );
+                assert_eq!(Circuit::get_active_timing_links(xtx_id), None);
+            }
+            assert!(!Circuit::get_xtx_timeouts_carried_over());
+
+            // The offered rewards are returned to the requester
+            assert_eq!(Balances::reserved_balance(&ALICE), 0);
+            assert_eq!(Balances::free_balance(&ALICE), 1_000_000);
+        });
+}
+
+#[test]
+fn circuit_carries_timeouts_over_the_deletion_queue_limit_and_retries_failed_reverts() {
+    ExtBuilder::default().build().execute_with(|| {
+        let limit = <Runtime as pallet_circuit::Config>::DeletionQueueLimit::get();
+        let interval = <Runtime as pallet_circuit::Config>::XtxTimeoutCheckInterval::get();
+
+        // Linked Xtx without a signal can't be set up for the revert
+        for i in 0..=limit {
+            let xtx_id = sp_core::H256::from_low_u64_be(i as u64);
+            ActiveXExecSignalsTimingLinks::<Runtime>::insert(xtx_id, 5);
+            pallet_circuit::XtxTimeoutsAt::<Runtime>::insert(5, xtx_id, ());
+        }
+
+        System::set_block_number(interval);
+        <Circuit as frame_support::traits::OnInitialize<BlockNumber>>::on_initialize(interval);
+
+        assert!(Circuit::get_xtx_timeouts_carried_over());
+        assert_eq!(Circuit::get_next_xtx_timeouts_block(), 5);
+        assert_eq!(
+            pallet_circuit::XtxTimeoutsAt::<Runtime>::iter_prefix(5).count(),
+            1
+        );
+        // Xtx which fail to revert stay active and are retried at the next interval
+        assert_eq!(
+            pallet_circuit::XtxTimeoutsAt::<Runtime>::iter_prefix(2 * interval).count(),
+            limit as usize
+        );
+        assert_eq!(
+            ActiveXExecSignalsTimingLinks::<Runtime>::iter().count(),
+            limit as usize + 1
+        );
+
+        // Carried over sweeps continue off the interval
+        System::set_block_number(interval + 1);
+        <Circuit as frame_support::traits::OnInitialize<BlockNumber>>::on_initialize(interval + 1);
+
+        assert!(!Circuit::get_xtx_timeouts_carried_over());
+        assert_eq!(Circuit::get_next_xtx_timeouts_block(), interval + 2);
+        assert_eq!(
+            pallet_circuit::XtxTimeoutsAt::<Runtime>::iter_prefix(5).count(),
+            0
+        );
+        assert_eq!(
+            pallet_circuit::XtxTimeoutsAt::<Runtime>::iter_prefix(2 * interval + 1).count(),
+            1
+        );
+    });
+}
+
+#[test]
+fn circuit_runtime_upgrade_indexes_active_xtx_by_timeout() {
+    use frame_support::traits::{Hooks, StorageVersion};
+
+    ExtBuilder::default().build().execute_with(|| {
+        let overdue = sp_core::H256::repeat_byte(1);
+        let pending = sp_core::H256::repeat_byte(2);
+
+        ActiveXExecSignalsTimingLinks::<Runtime>::insert(overdue, 5);
+        ActiveXExecSignalsTimingLinks::<Runtime>::insert(pending, 50);
+        StorageVersion::new(0).put::<Circuit>();
+        System::set_block_number(20);
+
+        <Circuit as Hooks<BlockNumber>>::on_runtime_upgrade();
+
+        assert_eq!(Circuit::get_next_xtx_timeouts_block(), 20);
+        assert!(pallet_circuit::XtxTimeoutsAt::<Runtime>::contains_key(
+            20, overdue
+        ));
+        assert!(pallet_circuit::XtxTimeoutsAt::<Runtime>::contains_key(
+            50, pending
+        ));
+        assert_eq!(StorageVersion::get::<Circuit>(), StorageVersion::new(1));
+    });
+}
+
 #[test]
 fn load_local_state_can_generate_and_read_state() {
     let origin = Origin::signed(ALICE); // Only sudo access to register new gateways for now
@@ -1683,6 +3404,7 @@ fn uninsured_unrewarded_single_rococo_transfer() {
     // }
 
     ExtBuilder::default()
+        .with_default_executors_and_relayers()
         .with_standard_side_effects()
         .with_default_xdns_records()
         .build()
@@ -1762,6 +3484,7 @@ fn insured_unrewarded_single_rococo_transfer() {
     // await execute("submit-headers roco --export -o 5-headers-roco", 5);
 
     ExtBuilder::default()
+        .with_default_executors_and_relayers()
         .with_standard_side_effects()
         .with_default_xdns_records()
         .build()
@@ -1902,6 +3625,7 @@ fn insured_rewarded_single_rococo_transfer() {
     // await execute("submit-headers roco --export -o 5-headers-roco", 5);
 
     ExtBuilder::default()
+        .with_default_executors_and_relayers()
         .with_standard_side_effects()
         .with_default_xdns_records()
         .build()
@@ -2036,6 +3760,7 @@ fn insured_rewarded_multi_rococo_transfer() {
     // await execute("submit-headers roco --export -o 6-headers-roco", 5);
 
     ExtBuilder::default()
+        .with_default_executors_and_relayers()
         .with_standard_side_effects()
         .with_default_xdns_records()
         .build()
@@ -2183,6 +3908,7 @@ fn insured_unrewarded_multi_rococo_transfer() {
     // await execute("submit-headers roco --export -o 6-headers-roco", 5);
 
     ExtBuilder::default()
+        .with_default_executors_and_relayers()
         .with_standard_side_effects()
         .with_default_xdns_records()
         .build()
@@ -2349,6 +4075,7 @@ fn uninsured_unrewarded_multi_rococo_transfer() {
     // await execute("submit-headers roco --export -o 6-headers-roco", 15);
 
     ExtBuilder::default()
+        .with_default_executors_and_relayers()
         .with_standard_side_effects()
         .with_default_xdns_records()
         .build()
@@ -2549,6 +4276,7 @@ fn multi_mixed_rococo() {
     // await execute("submit-headers roco --export -o 22-headers-roco", 0);
 
     ExtBuilder::default()
+        .with_default_executors_and_relayers()
         .with_standard_side_effects()
         .with_default_xdns_records()
         .build()
@@ -2962,6 +4690,7 @@ fn insured_multi_rococo_multiple_executors() {
     // await execute("submit-headers bslk --export -o 14-headers-bslk", 10);
 
     ExtBuilder::default()
+        .with_default_executors_and_relayers()
         .with_standard_side_effects()
         .with_default_xdns_records()
         .build()
@@ -3209,6 +4938,7 @@ fn uninsured_unrewarded_parachain_transfer() {
     // await execute("submit-headers bslk --export -o 8-headers-bslk", 5);
 
     ExtBuilder::default()
+        .with_default_executors_and_relayers()
         .with_standard_side_effects()
         .with_default_xdns_records()
         .build()
@@ -3664,6 +5394,7 @@ fn execute_side_effects_with_xbi_works_for_transfers() {
     let sequential = true;
 
     ExtBuilder::default()
+        .with_active_executors(vec![ALICE])
         .with_standard_side_effects()
         .with_default_xdns_records()
         .build()
@@ -3686,6 +5417,9 @@ fn execute_side_effects_with_xbi_works_for_transfers() {
                 side_effects,
                 fee,
                 sequential,
+                None,
+                None,
+                None,
             ));
 
             assert_eq!(
@@ -3727,6 +5461,82 @@ fn execute_side_effects_with_xbi_works_for_transfers() {
         });
 }
 
+#[test]
+fn execute_side_effects_with_xbi_rejects_copies_of_side_effects_without_enforced_executioner() {
+    let origin = Origin::signed(ALICE); // Only sudo access to register new gateways for now
+
+    let transfer_protocol_box =
+        Box::new(t3rn_protocol::side_effects::standards::get_transfer_interface());
+
+    let mut local_state = LocalState::new();
+    let mut valid_transfer_side_effect = produce_and_validate_side_effect(
+        vec![
+            (Type::Address(32), ArgVariant::A),
+            (Type::Address(32), ArgVariant::B),
+            (Type::Uint(128), ArgVariant::A),
+            (Type::Bytes(0), ArgVariant::A), // empty bytes instead of insurance
+        ],
+        &mut local_state,
+        transfer_protocol_box,
+    );
+
+    valid_transfer_side_effect.target = [3, 3, 3, 3];
+    valid_transfer_side_effect.enforce_executioner = Some(EXECUTOR_DEFAULT);
+
+    let mut stripped_side_effect = valid_transfer_side_effect.clone();
+    stripped_side_effect.enforce_executioner = None;
+
+    let side_effects = vec![valid_transfer_side_effect.clone()];
+    let fee = 1;
+    let sequential = true;
+
+    ExtBuilder::default()
+        .with_active_executors(vec![ALICE])
+        .with_standard_side_effects()
+        .with_default_xdns_records()
+        .build()
+        .execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, INITIAL_BALANCE);
+
+            System::set_block_number(1);
+            brute_seed_block_1([3, 3, 3, 3]);
+
+            assert_ok!(Circuit::on_extrinsic_trigger(
+                origin.clone(),
+                side_effects,
+                fee,
+                sequential,
+                None,
+                None,
+                None,
+            ));
+
+            let (xtx_id, _) = set_ids(valid_transfer_side_effect.clone());
+
+            assert_noop!(
+                Circuit::execute_side_effects_with_xbi(
+                    origin.clone(),
+                    xtx_id,
+                    stripped_side_effect,
+                    MAX_EXECUTION_COST as u128,
+                    MAX_NOTIFICATION_COST as u128,
+                ),
+                pallet_circuit::Error::<Runtime>::SideEffectNotInCurrentStep
+            );
+
+            assert_noop!(
+                Circuit::execute_side_effects_with_xbi(
+                    origin,
+                    xtx_id,
+                    valid_transfer_side_effect,
+                    MAX_EXECUTION_COST as u128,
+                    MAX_NOTIFICATION_COST as u128,
+                ),
+                pallet_circuit::Error::<Runtime>::EnforcedExecutionerMismatch
+            );
+        });
+}
+
 #[test]
 fn execute_side_effects_with_xbi_works_for_call_evm() {
     let origin = Origin::signed(ALICE); // Only sudo access to register new gateways for now
@@ -3779,6 +5589,7 @@ fn execute_side_effects_with_xbi_works_for_call_evm() {
     let sequential = true;
 
     ExtBuilder::default()
+        .with_active_executors(vec![ALICE])
         .with_standard_side_effects()
         .with_default_xdns_records()
         .build()
@@ -3799,6 +5610,9 @@ fn execute_side_effects_with_xbi_works_for_call_evm() {
                 side_effects,
                 fee,
                 sequential,
+                None,
+                None,
+                None,
             ));
 
             assert_eq!(
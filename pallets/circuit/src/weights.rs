@@ -28,12 +28,20 @@ use frame_support::{
 use sp_std::marker::PhantomData;
 
 /// Weight functions needed for pallet_circuit_circuit_portal.
+///
+/// `s` stands for the number of side effects of the Xtx and `d` for the number of steps
+/// scheduled with `delay_steps_at`.
 pub trait WeightInfo {
     fn on_local_trigger() -> Weight;
-    fn on_extrinsic_trigger() -> Weight;
-    fn bond_insurance_deposit() -> Weight;
-    fn confirm_side_effect() -> Weight;
-    fn execute_side_effects_with_xbi() -> Weight;
+    fn on_extrinsic_trigger(s: u32, d: u32) -> Weight;
+    fn bond_insurance_deposit(s: u32) -> Weight;
+    fn confirm_side_effect(s: u32) -> Weight;
+    fn execute_side_effects_with_xbi(s: u32) -> Weight;
+    fn register_relayer() -> Weight;
+    fn deregister_relayer() -> Weight;
+    fn challenge_side_effect(s: u32) -> Weight;
+    fn bid_side_effect(s: u32) -> Weight;
+    fn cancel_xtx(s: u32) -> Weight;
 }
 
 /// Weights for pallet_circuit_circuit_portal using the Substrate node and recommended hardware.
@@ -43,20 +51,181 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
         6_984_000_u64
     }
 
-    fn on_extrinsic_trigger() -> Weight {
-        60_000_000_u64
+    // Storage: XDNS XDNSRegistry (r:1 w:0) per side effect
+    // Storage: XDNS FinalityDelays (r:1 w:0) per side effect
+    // Storage: Finality verifier BestFinalizedMap (r:1 w:0) per side effect
+    // Storage: Finality verifier MultiImportedHeaders (r:1 w:0) per side effect
+    // Storage: Clock CurrentRound (r:1 w:0) per side effect
+    // Storage: AccountManager PendingChargeRounds (r:1 w:1) per side effect
+    // Storage: AccountManager PendingRounds (r:0 w:1) per side effect
+    // Storage: AccountManager PendingChargesPerRound (r:0 w:1) per side effect
+    // Storage: System Account (r:1 w:1) per side effect
+    // Storage: Circuit InsuranceDeposits (r:0 w:1) per side effect
+    // Storage: Circuit OpenSideEffects (r:0 w:1) per side effect
+    // Storage: Circuit LocalSideEffectToXtxIdLinks (r:0 w:1) per side effect
+    // Storage: Circuit FullSideEffects (r:0 w:1)
+    // Storage: Circuit XtxInsuranceLinks (r:0 w:1)
+    // Storage: Circuit LocalXtxStates (r:0 w:1)
+    // Storage: Circuit ActiveXExecSignalsTimingLinks (r:0 w:1)
+    // Storage: Circuit XtxTimeoutsAt (r:0 w:1)
+    // Storage: Circuit XExecSignals (r:0 w:1)
+    fn on_extrinsic_trigger(s: u32, d: u32) -> Weight {
+        (40_000_000_u64)
+            .saturating_add((20_000_000_u64).saturating_mul(s as Weight))
+            .saturating_add((500_000_u64).saturating_mul(d as Weight))
+            .saturating_add(T::DbWeight::get().reads((7_u64).saturating_mul(s as Weight)))
+            .saturating_add(T::DbWeight::get().writes(6_u64))
+            .saturating_add(T::DbWeight::get().writes((7_u64).saturating_mul(s as Weight)))
     }
 
-    fn confirm_side_effect() -> Weight {
-        60_000_000_u64
+    // Storage: Circuit Relayers (r:1 w:0)
+    // Storage: Circuit XExecSignals (r:1 w:1)
+    // Storage: Circuit XtxInsuranceLinks (r:1 w:0)
+    // Storage: Circuit FullSideEffects (r:1 w:1)
+    // Storage: Circuit LocalXtxStates (r:1 w:0)
+    // Storage: XDNS XDNSRegistry (r:1 w:0)
+    // Storage: Finality verifier MultiImportedHeaders (r:2 w:0)
+    // Storage: System Account (r:2 w:2) slashing the misbehaving executor
+    // Storage: Circuit ActiveXExecSignalsTimingLinks (r:0 w:1)
+    // Storage: Circuit InsuranceDeposits (r:1 w:0) per side effect
+    // Storage: Circuit OpenSideEffects (r:0 w:1) per side effect
+    // Storage: Circuit SideEffectBids (r:0 w:1) per side effect
+    // Storage: Circuit ChallengeableConfirmations (r:0 w:1) per side effect
+    // Storage: AccountManager PendingChargeRounds (r:1 w:1) per side effect
+    // Storage: AccountManager PendingChargesPerRound (r:1 w:1) per side effect
+    // Storage: AccountManager SplitPolicies (r:1 w:0) per side effect
+    // Storage: System Account (r:2 w:2) per side effect
+    fn confirm_side_effect(s: u32) -> Weight {
+        (60_000_000_u64)
+            .saturating_add((10_000_000_u64).saturating_mul(s as Weight))
+            .saturating_add(T::DbWeight::get().reads(10_u64))
+            .saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(s as Weight)))
+            .saturating_add(T::DbWeight::get().writes(5_u64))
+            .saturating_add(T::DbWeight::get().writes((7_u64).saturating_mul(s as Weight)))
     }
 
-    fn bond_insurance_deposit() -> Weight {
-        60_000_000_u64
+    // Storage: Executors ActiveSet (r:1 w:0)
+    // Storage: Executors CandidateInfo (r:1 w:0)
+    // Storage: Circuit XExecSignals (r:1 w:1)
+    // Storage: Circuit XtxInsuranceLinks (r:1 w:0)
+    // Storage: Circuit FullSideEffects (r:1 w:0)
+    // Storage: Circuit LocalXtxStates (r:1 w:0)
+    // Storage: Circuit SideEffectBids (r:1 w:1)
+    // Storage: Circuit OpenSideEffects (r:0 w:1)
+    // Storage: AccountManager PendingChargeRounds (r:1 w:0)
+    // Storage: AccountManager PendingChargesPerRound (r:1 w:1)
+    // Storage: System Account (r:2 w:2)
+    // Storage: Circuit InsuranceDeposits (r:1 w:0) per side effect, written for the bonded one
+    fn bond_insurance_deposit(s: u32) -> Weight {
+        (40_000_000_u64)
+            .saturating_add((1_000_000_u64).saturating_mul(s as Weight))
+            .saturating_add(T::DbWeight::get().reads(11_u64))
+            .saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(s as Weight)))
+            .saturating_add(T::DbWeight::get().writes(7_u64))
     }
 
-    fn execute_side_effects_with_xbi() -> Weight {
-        60_000_000_u64
+    // Storage: XBIPortal check-ins (r:1 w:1)
+    // Storage: Executors ActiveSet (r:1 w:0)
+    // Storage: Executors CandidateInfo (r:1 w:0)
+    // Storage: Circuit XExecSignals (r:1 w:0)
+    // Storage: Circuit XtxInsuranceLinks (r:1 w:0)
+    // Storage: Circuit FullSideEffects (r:1 w:0)
+    // Storage: Circuit LocalXtxStates (r:1 w:0)
+    // Storage: XDNS XDNSRegistry (r:1 w:0)
+    // Storage: Clock CurrentRound (r:1 w:0)
+    // Storage: AccountManager PendingChargeRounds (r:1 w:1)
+    // Storage: AccountManager PendingRounds (r:0 w:1)
+    // Storage: AccountManager PendingChargesPerRound (r:0 w:1)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Circuit InsuranceDeposits (r:1 w:0) per side effect
+    fn execute_side_effects_with_xbi(s: u32) -> Weight {
+        (50_000_000_u64)
+            .saturating_add((1_000_000_u64).saturating_mul(s as Weight))
+            .saturating_add(T::DbWeight::get().reads(11_u64))
+            .saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(s as Weight)))
+            .saturating_add(T::DbWeight::get().writes(5_u64))
+    }
+
+    // Storage: Circuit Relayers (r:1 w:1)
+    fn register_relayer() -> Weight {
+        (15_000_000_u64)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Storage: Circuit Relayers (r:1 w:1)
+    fn deregister_relayer() -> Weight {
+        (15_000_000_u64)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Storage: Executors ActiveSet (r:1 w:0)
+    // Storage: Executors CandidateInfo (r:1 w:0)
+    // Storage: Circuit ChallengeableConfirmations (r:1 w:1)
+    // Storage: Circuit XExecSignals (r:1 w:1)
+    // Storage: Circuit XtxInsuranceLinks (r:1 w:0)
+    // Storage: Circuit FullSideEffects (r:1 w:1)
+    // Storage: Circuit LocalXtxStates (r:1 w:0)
+    // Storage: XDNS XDNSRegistry (r:1 w:0)
+    // Storage: Finality verifier MultiImportedHeaders (r:2 w:0)
+    // Storage: System Account (r:2 w:2)
+    // Storage: Circuit ActiveXExecSignalsTimingLinks (r:0 w:1)
+    // Storage: Circuit InsuranceDeposits (r:1 w:0) per side effect
+    // Storage: Circuit OpenSideEffects (r:0 w:1) per side effect
+    // Storage: Circuit SideEffectBids (r:0 w:1) per side effect
+    // Storage: Circuit ChallengeableConfirmations (r:0 w:1) per side effect
+    // Storage: AccountManager PendingChargeRounds (r:1 w:1) per side effect
+    // Storage: AccountManager PendingChargesPerRound (r:1 w:1) per side effect
+    // Storage: AccountManager SplitPolicies (r:1 w:0) per side effect
+    // Storage: System Account (r:2 w:2) per side effect
+    fn challenge_side_effect(s: u32) -> Weight {
+        (70_000_000_u64)
+            .saturating_add((10_000_000_u64).saturating_mul(s as Weight))
+            .saturating_add(T::DbWeight::get().reads(12_u64))
+            .saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(s as Weight)))
+            .saturating_add(T::DbWeight::get().writes(6_u64))
+            .saturating_add(T::DbWeight::get().writes((7_u64).saturating_mul(s as Weight)))
+    }
+
+    // Storage: Executors ActiveSet (r:1 w:0)
+    // Storage: Executors CandidateInfo (r:1 w:0)
+    // Storage: Circuit XExecSignals (r:1 w:1)
+    // Storage: Circuit XtxInsuranceLinks (r:1 w:0)
+    // Storage: Circuit FullSideEffects (r:1 w:0)
+    // Storage: Circuit LocalXtxStates (r:1 w:0)
+    // Storage: Circuit SideEffectBids (r:1 w:1)
+    // Storage: System Account (r:2 w:2) reserving the bid, unreserving the outbid one
+    // Storage: Circuit InsuranceDeposits (r:1 w:0) per side effect, written for the bid one
+    fn bid_side_effect(s: u32) -> Weight {
+        (40_000_000_u64)
+            .saturating_add((1_000_000_u64).saturating_mul(s as Weight))
+            .saturating_add(T::DbWeight::get().reads(9_u64))
+            .saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(s as Weight)))
+            .saturating_add(T::DbWeight::get().writes(5_u64))
+    }
+
+    // Storage: Circuit XExecSignals (r:1 w:1)
+    // Storage: Circuit XtxInsuranceLinks (r:1 w:1)
+    // Storage: Circuit FullSideEffects (r:1 w:1)
+    // Storage: Circuit LocalXtxStates (r:1 w:1)
+    // Storage: Circuit ActiveXExecSignalsTimingLinks (r:0 w:1)
+    // Storage: Circuit InsuranceDeposits (r:1 w:1) per side effect
+    // Storage: Circuit OpenSideEffects (r:0 w:1) per side effect
+    // Storage: Circuit SideEffectBids (r:0 w:1) per side effect
+    // Storage: Circuit LocalSideEffectToXtxIdLinks (r:0 w:1) per side effect
+    // Storage: Circuit ChallengeableConfirmations (r:0 w:1) per side effect
+    // Storage: AccountManager PendingChargeRounds (r:1 w:1) per side effect
+    // Storage: AccountManager PendingChargesPerRound (r:1 w:1) per side effect
+    // Storage: AccountManager SplitPolicies (r:1 w:0) per side effect
+    // Storage: System Account (r:1 w:1) per side effect
+    fn cancel_xtx(s: u32) -> Weight {
+        (30_000_000_u64)
+            .saturating_add((15_000_000_u64).saturating_mul(s as Weight))
+            .saturating_add(T::DbWeight::get().reads(4_u64))
+            .saturating_add(T::DbWeight::get().reads((5_u64).saturating_mul(s as Weight)))
+            .saturating_add(T::DbWeight::get().writes(5_u64))
+            .saturating_add(T::DbWeight::get().writes((8_u64).saturating_mul(s as Weight)))
     }
 }
 
@@ -66,19 +235,75 @@ impl WeightInfo for () {
         6_984_000_u64
     }
 
-    fn on_extrinsic_trigger() -> Weight {
-        60_000_000_u64
+    fn on_extrinsic_trigger(s: u32, d: u32) -> Weight {
+        (40_000_000_u64)
+            .saturating_add((20_000_000_u64).saturating_mul(s as Weight))
+            .saturating_add((500_000_u64).saturating_mul(d as Weight))
+            .saturating_add(RocksDbWeight::get().reads((7_u64).saturating_mul(s as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(6_u64))
+            .saturating_add(RocksDbWeight::get().writes((7_u64).saturating_mul(s as Weight)))
+    }
+
+    fn confirm_side_effect(s: u32) -> Weight {
+        (60_000_000_u64)
+            .saturating_add((10_000_000_u64).saturating_mul(s as Weight))
+            .saturating_add(RocksDbWeight::get().reads(10_u64))
+            .saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(s as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(5_u64))
+            .saturating_add(RocksDbWeight::get().writes((7_u64).saturating_mul(s as Weight)))
+    }
+
+    fn bond_insurance_deposit(s: u32) -> Weight {
+        (40_000_000_u64)
+            .saturating_add((1_000_000_u64).saturating_mul(s as Weight))
+            .saturating_add(RocksDbWeight::get().reads(11_u64))
+            .saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(s as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(7_u64))
+    }
+
+    fn execute_side_effects_with_xbi(s: u32) -> Weight {
+        (50_000_000_u64)
+            .saturating_add((1_000_000_u64).saturating_mul(s as Weight))
+            .saturating_add(RocksDbWeight::get().reads(11_u64))
+            .saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(s as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(5_u64))
+    }
+
+    fn register_relayer() -> Weight {
+        (15_000_000_u64)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn deregister_relayer() -> Weight {
+        (15_000_000_u64)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
     }
 
-    fn confirm_side_effect() -> Weight {
-        60_000_000_u64
+    fn challenge_side_effect(s: u32) -> Weight {
+        (70_000_000_u64)
+            .saturating_add((10_000_000_u64).saturating_mul(s as Weight))
+            .saturating_add(RocksDbWeight::get().reads(12_u64))
+            .saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(s as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(6_u64))
+            .saturating_add(RocksDbWeight::get().writes((7_u64).saturating_mul(s as Weight)))
     }
 
-    fn bond_insurance_deposit() -> Weight {
-        60_000_000_u64
+    fn bid_side_effect(s: u32) -> Weight {
+        (40_000_000_u64)
+            .saturating_add((1_000_000_u64).saturating_mul(s as Weight))
+            .saturating_add(RocksDbWeight::get().reads(9_u64))
+            .saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(s as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(5_u64))
     }
 
-    fn execute_side_effects_with_xbi() -> Weight {
-        60_000_000_u64
+    fn cancel_xtx(s: u32) -> Weight {
+        (30_000_000_u64)
+            .saturating_add((15_000_000_u64).saturating_mul(s as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4_u64))
+            .saturating_add(RocksDbWeight::get().reads((5_u64).saturating_mul(s as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(5_u64))
+            .saturating_add(RocksDbWeight::get().writes((8_u64).saturating_mul(s as Weight)))
     }
 }
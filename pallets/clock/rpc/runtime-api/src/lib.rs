@@ -0,0 +1,21 @@
+//! Runtime API definition for querying claims of pallet Clock.
+//!
+//! This API should be imported and implemented by the runtime,
+//! of a node that wants to show beneficiaries what they can claim.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+pub use t3rn_primitives::claimable::{BenefitSource, CircuitRole};
+
+sp_api::decl_runtime_apis! {
+    /// The API to query pending claims of pallet Clock
+    pub trait ClockRuntimeApi<AccountId, Balance> where
+        AccountId: Codec,
+        Balance: Codec,
+    {
+        /// Returns the amounts the beneficiary can claim per role and benefit source
+        fn get_pending_claims(beneficiary: AccountId) -> Vec<(CircuitRole, BenefitSource, Balance)>;
+    }
+}
@@ -9,12 +9,15 @@
 #![allow(clippy::too_many_arguments)]
 
 pub use crate::pallet::*;
-use frame_support::{pallet_prelude::Weight, traits::Get};
+use frame_support::{
+    pallet_prelude::Weight,
+    traits::{Currency, ExistenceRequirement, Get},
+};
 
 pub use t3rn_primitives::{
     abi::{GatewayABIConfig, Type},
     account_manager::AccountManager,
-    claimable::ClaimableArtifacts,
+    claimable::{BenefitSource, CircuitRole, ClaimableArtifacts, PendingClaim},
     clock::Clock,
     common::RoundInfo,
     executors::Executors,
@@ -32,7 +35,9 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
-mod weights;
+pub mod weights;
+
+pub use weights::WeightInfo;
 
 // Definition of the pallet logic, to be aggregated at runtime definition through
 // `construct_runtime`.
@@ -44,7 +49,7 @@ pub mod pallet {
     use frame_system::pallet_prelude::*;
     use sp_std::{prelude::*, vec};
 
-    use sp_runtime::traits::Zero;
+    use sp_runtime::traits::{Saturating, Zero};
 
     #[pallet::config]
     pub trait Config: frame_system::Config + pallet_account_manager::Config {
@@ -65,6 +70,9 @@ pub mod pallet {
             Self::Hash,
             Self::BlockNumber,
         >;
+
+        /// Weight information for extrinsics in this pallet.
+        type WeightInfo: weights::WeightInfo;
     }
 
     // Simple declaration of the `Pallet` type. It is placeholder we use to implement traits and
@@ -86,7 +94,143 @@ pub mod pallet {
         Vec<ClaimableArtifacts<T::AccountId, BalanceOf<T>>>,
     >;
 
+    /// Running balance of what beneficiaries can claim in each role, accrued per round
+    /// and benefit source from the round's claimable artifacts.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_claims)]
+    pub type PendingClaims<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        CircuitRole,
+        Vec<PendingClaim<BalanceOf<T>>>,
+        ValueQuery,
+    >;
+
+    #[pallet::call]
     impl<T: Config> Pallet<T> {
+        /// Claims what the sender accrued in the role over at most the given number of rounds,
+        /// starting with the oldest. Paid out from the escrow account.
+        ///
+        /// `pending_claims_count` must be at least the number of pending claims the sender has
+        /// in the role, since they are all read and rewritten.
+        #[pallet::weight(<T as Config>::WeightInfo::claim(*rounds, *pending_claims_count))]
+        pub fn claim(
+            origin: OriginFor<T>,
+            role: CircuitRole,
+            rounds: u32,
+            pending_claims_count: u32,
+        ) -> DispatchResultWithPostInfo {
+            let beneficiary = ensure_signed(origin)?;
+
+            let pending_claims = PendingClaims::<T>::get(&beneficiary, &role);
+            let pending_claims_len = pending_claims.len() as u32;
+            ensure!(
+                pending_claims_len <= pending_claims_count,
+                Error::<T>::TooLowPendingClaimsCount
+            );
+            let mut claimed_rounds: Vec<u32> = pending_claims
+                .iter()
+                .map(|pending_claim| pending_claim.round)
+                .collect();
+            claimed_rounds.sort_unstable();
+            claimed_rounds.dedup();
+            claimed_rounds.truncate(rounds as usize);
+
+            let (claimed, left): (Vec<_>, Vec<_>) = pending_claims
+                .into_iter()
+                .partition(|pending_claim| claimed_rounds.contains(&pending_claim.round));
+
+            let amount = claimed
+                .iter()
+                .fold(Zero::zero(), |total: BalanceOf<T>, pending_claim| {
+                    total.saturating_add(pending_claim.amount)
+                });
+            ensure!(amount > Zero::zero(), Error::<T>::NothingToClaim);
+
+            <T as pallet_account_manager::Config>::Currency::transfer(
+                &<T as pallet_account_manager::Config>::EscrowAccount::get(),
+                &beneficiary,
+                amount,
+                ExistenceRequirement::KeepAlive,
+            )
+            .map_err(|_e| Error::<T>::ClaimTransferFailed)?;
+
+            if left.is_empty() {
+                PendingClaims::<T>::remove(&beneficiary, &role);
+            } else {
+                PendingClaims::<T>::insert(&beneficiary, &role, left);
+            }
+
+            Self::deposit_event(Event::Claimed {
+                beneficiary,
+                role,
+                amount,
+            });
+
+            Ok(Some(<T as Config>::WeightInfo::claim(
+                claimed_rounds.len() as u32,
+                pending_claims_len,
+            ))
+            .into())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Returns the amounts the beneficiary can claim per role and benefit source.
+        pub fn get_pending_claims_by_beneficiary(
+            beneficiary: &T::AccountId,
+        ) -> Vec<(CircuitRole, BenefitSource, BalanceOf<T>)> {
+            let mut pending: Vec<(CircuitRole, BenefitSource, BalanceOf<T>)> = vec![];
+            for (role, pending_claims) in PendingClaims::<T>::iter_prefix(beneficiary) {
+                for pending_claim in pending_claims {
+                    match pending.iter_mut().find(|(pending_role, source, _)| {
+                        *pending_role == role && *source == pending_claim.benefit_source
+                    }) {
+                        Some((_, _, amount)) =>
+                            *amount = amount.saturating_add(pending_claim.amount),
+                        None => pending.push((
+                            role.clone(),
+                            pending_claim.benefit_source,
+                            pending_claim.amount,
+                        )),
+                    }
+                }
+            }
+            pending
+        }
+
+        /// Adds the claimable artifacts of the round to the running balances of beneficiaries.
+        fn accrue_pending_claims(
+            r: RoundInfo<T::BlockNumber>,
+            claimable_artifacts: &[ClaimableArtifacts<T::AccountId, BalanceOf<T>>],
+        ) {
+            for artifact in claimable_artifacts
+                .iter()
+                .filter(|artifact| artifact.total_round_claim > Zero::zero())
+            {
+                PendingClaims::<T>::mutate(
+                    &artifact.beneficiary,
+                    &artifact.role,
+                    |pending_claims| match pending_claims.iter_mut().find(|pending_claim| {
+                        pending_claim.round == r.index
+                            && pending_claim.benefit_source == artifact.benefit_source
+                    }) {
+                        Some(pending_claim) =>
+                            pending_claim.amount = pending_claim
+                                .amount
+                                .saturating_add(artifact.total_round_claim),
+                        None => pending_claims.push(PendingClaim {
+                            round: r.index,
+                            benefit_source: artifact.benefit_source.clone(),
+                            amount: artifact.total_round_claim,
+                        }),
+                    },
+                );
+            }
+        }
+
         fn calculate_claimable_for_round(n: T::BlockNumber) -> DispatchResult {
             // fixme: move current_round from treasury to circuit-clock
             let r = T::Treasury::current_round();
@@ -98,6 +242,7 @@ pub mod pallet {
             // claimable_artifacts.push(T::LiquidityPools::on_collect_claimable(n, r)?);
 
             ClaimableArtifactsPerRound::<T>::insert(r, claimable_artifacts.clone());
+            Self::accrue_pending_claims(r, &claimable_artifacts);
             // todo: aggregated claimable_artifacts to TotalClaimablePerRound
             Ok(())
         }
@@ -142,10 +287,21 @@ pub mod pallet {
 
     #[pallet::event]
     #[pallet::generate_deposit(pub (super) fn deposit_event)]
-    pub enum Event<T: Config> {}
+    pub enum Event<T: Config> {
+        /// The beneficiary claimed what they accrued in the role
+        Claimed {
+            beneficiary: T::AccountId,
+            role: CircuitRole,
+            amount: BalanceOf<T>,
+        },
+    }
 
     #[pallet::error]
-    pub enum Error<T> {}
+    pub enum Error<T> {
+        NothingToClaim,
+        ClaimTransferFailed,
+        TooLowPendingClaimsCount,
+    }
 
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
@@ -1 +1,186 @@
+use circuit_mock_runtime::*;
 
+use frame_support::{
+    assert_err, assert_ok,
+    traits::{Currency, Get, Hooks},
+};
+use sp_core::H256;
+use t3rn_primitives::{
+    account_manager::{AccountManager as AccountManagerExt, Outcome},
+    claimable::{BenefitSource, CircuitRole, PendingClaim},
+    common::RoundInfo,
+};
+
+const DEFAULT_BALANCE: Balance = 1_000_000;
+
+fn escrow_account() -> AccountId {
+    <Runtime as pallet_account_manager::Config>::EscrowAccount::get()
+}
+
+fn settle(execution_id: H256, recipient: AccountId, charge_amt: Balance, role: CircuitRole) {
+    assert_ok!(<AccountManager as AccountManagerExt<
+        AccountId,
+        Balance,
+        Hash,
+        BlockNumber,
+    >>::deposit(
+        execution_id,
+        &ALICE,
+        charge_amt,
+        0,
+        BenefitSource::TrafficRewards,
+        role,
+        Some(recipient),
+    ));
+    assert_ok!(<AccountManager as AccountManagerExt<
+        AccountId,
+        Balance,
+        Hash,
+        BlockNumber,
+    >>::finalize(execution_id, Outcome::Commit, None, None));
+}
+
+#[test]
+fn clock_accrues_settlements_of_all_roles_at_the_end_of_round() {
+    ExtBuilder::default()
+        .with_active_executors(vec![EXECUTOR_DEFAULT])
+        .build()
+        .execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, DEFAULT_BALANCE);
+
+            settle(H256::repeat_byte(1), BOB_RELAYER, 100, CircuitRole::Relayer);
+            settle(
+                H256::repeat_byte(2),
+                CHARLIE,
+                200,
+                CircuitRole::ContractAuthor,
+            );
+            settle(
+                H256::repeat_byte(3),
+                EXECUTOR_DEFAULT,
+                100,
+                CircuitRole::Executor,
+            );
+            settle(
+                H256::repeat_byte(4),
+                EXECUTOR_DEFAULT,
+                200,
+                CircuitRole::Executor,
+            );
+
+            <Clock as Hooks<BlockNumber>>::on_finalize(499);
+            assert!(Clock::pending_claims(&BOB_RELAYER, &CircuitRole::Relayer).is_empty());
+
+            <Clock as Hooks<BlockNumber>>::on_finalize(500);
+
+            assert_eq!(
+                Clock::pending_claims(&BOB_RELAYER, &CircuitRole::Relayer),
+                vec![PendingClaim {
+                    round: 0,
+                    benefit_source: BenefitSource::TrafficRewards,
+                    amount: 99,
+                }]
+            );
+            assert_eq!(
+                Clock::pending_claims(&CHARLIE, &CircuitRole::ContractAuthor),
+                vec![PendingClaim {
+                    round: 0,
+                    benefit_source: BenefitSource::TrafficRewards,
+                    amount: 198,
+                }]
+            );
+            // Executor without any stake keeps the whole aggregated claim
+            assert_eq!(
+                Clock::pending_claims(&EXECUTOR_DEFAULT, &CircuitRole::Executor),
+                vec![PendingClaim {
+                    round: 0,
+                    benefit_source: BenefitSource::TrafficRewards,
+                    amount: 297,
+                }]
+            );
+            assert_eq!(
+                Clock::get_pending_claims_by_beneficiary(&BOB_RELAYER),
+                vec![(CircuitRole::Relayer, BenefitSource::TrafficRewards, 99)]
+            );
+        });
+}
+
+#[test]
+fn claim_pays_out_accrued_claims_from_escrow() {
+    ExtBuilder::default().build().execute_with(|| {
+        let _ = Balances::deposit_creating(&ALICE, DEFAULT_BALANCE);
+
+        settle(H256::repeat_byte(1), BOB_RELAYER, 100, CircuitRole::Relayer);
+        <Clock as Hooks<BlockNumber>>::on_finalize(500);
+
+        let escrow_balance = Balances::free_balance(&escrow_account());
+        let relayer_balance = Balances::free_balance(&BOB_RELAYER);
+
+        assert_ok!(Clock::claim(
+            Origin::signed(BOB_RELAYER),
+            CircuitRole::Relayer,
+            1,
+            1
+        ));
+
+        assert_eq!(Balances::free_balance(&BOB_RELAYER), relayer_balance + 99);
+        assert_eq!(
+            Balances::free_balance(&escrow_account()),
+            escrow_balance - 99
+        );
+        assert!(Clock::pending_claims(&BOB_RELAYER, &CircuitRole::Relayer).is_empty());
+
+        assert_err!(
+            Clock::claim(Origin::signed(BOB_RELAYER), CircuitRole::Relayer, 1, 1),
+            pallet_clock::Error::<Runtime>::NothingToClaim
+        );
+    });
+}
+
+#[test]
+fn claim_pays_out_only_the_given_number_of_oldest_rounds() {
+    ExtBuilder::default().build().execute_with(|| {
+        let _ = Balances::deposit_creating(&ALICE, DEFAULT_BALANCE);
+
+        settle(H256::repeat_byte(1), BOB_RELAYER, 100, CircuitRole::Relayer);
+        <Clock as Hooks<BlockNumber>>::on_finalize(500);
+
+        pallet_treasury::CurrentRound::<Runtime>::put(RoundInfo::new(1, 500, 500));
+        settle(H256::repeat_byte(2), BOB_RELAYER, 200, CircuitRole::Relayer);
+        <Clock as Hooks<BlockNumber>>::on_finalize(1000);
+
+        let relayer_balance = Balances::free_balance(&BOB_RELAYER);
+
+        assert_ok!(Clock::claim(
+            Origin::signed(BOB_RELAYER),
+            CircuitRole::Relayer,
+            1,
+            2
+        ));
+
+        assert_eq!(Balances::free_balance(&BOB_RELAYER), relayer_balance + 99);
+        assert_eq!(
+            Clock::pending_claims(&BOB_RELAYER, &CircuitRole::Relayer),
+            vec![PendingClaim {
+                round: 1,
+                benefit_source: BenefitSource::TrafficRewards,
+                amount: 198,
+            }]
+        );
+    });
+}
+
+#[test]
+fn claim_fails_for_too_low_pending_claims_count() {
+    ExtBuilder::default().build().execute_with(|| {
+        let _ = Balances::deposit_creating(&ALICE, DEFAULT_BALANCE);
+
+        settle(H256::repeat_byte(1), BOB_RELAYER, 100, CircuitRole::Relayer);
+        <Clock as Hooks<BlockNumber>>::on_finalize(500);
+
+        assert_err!(
+            Clock::claim(Origin::signed(BOB_RELAYER), CircuitRole::Relayer, 1, 0),
+            pallet_clock::Error::<Runtime>::TooLowPendingClaimsCount
+        );
+    });
+}
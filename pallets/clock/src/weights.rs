@@ -9,12 +9,30 @@ use frame_support::{
 };
 use sp_std::marker::PhantomData;
 
-/// Weight functions needed for pallet_xdns.
-pub trait WeightInfo {}
+/// Weight functions needed for pallet_clock.
+pub trait WeightInfo {
+    fn claim(rounds: u32, pending_claims: u32) -> Weight;
+}
 
-/// Weights for pallet_xdns using the Substrate node and recommended hardware.
+/// Weights for pallet_clock using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
-impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {}
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    fn claim(rounds: u32, pending_claims: u32) -> Weight {
+        (30_000_000_u64)
+            .saturating_add((1_000_000_u64).saturating_mul(rounds as Weight))
+            .saturating_add((500_000_u64).saturating_mul(pending_claims as Weight))
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+    }
+}
 
 // For backwards compatibility and tests
-impl WeightInfo for () {}
+impl WeightInfo for () {
+    fn claim(rounds: u32, pending_claims: u32) -> Weight {
+        (30_000_000_u64)
+            .saturating_add((1_000_000_u64).saturating_mul(rounds as Weight))
+            .saturating_add((500_000_u64).saturating_mul(pending_claims as Weight))
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+    }
+}
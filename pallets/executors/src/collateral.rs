@@ -0,0 +1,435 @@
+use crate::pallet::{
+    ActiveSet, BalanceOf, CandidateInfo, CandidatePool, Config, Error, Event, ExecutorConfig,
+    Fixtures, Pallet, ReservedBonds, StakerInfo, TopStakes, Total,
+};
+use frame_support::{
+    ensure,
+    storage::with_storage_layer,
+    traits::{tokens::WithdrawReasons, Currency, Imbalance, LockableCurrency},
+};
+use sp_runtime::{
+    traits::{Saturating, Zero},
+    DispatchError, Perbill, Percent,
+};
+use sp_std::{vec, vec::Vec};
+use t3rn_primitives::{
+    executors::{Bond, Executors, NominatedStake, EXECUTOR_LOCK_ID},
+    treasury::StakeSource,
+};
+
+impl<T: Config> Pallet<T> {
+    /// Splits `amount` between the executor's own bond and its top stakers.
+    /// The executor's unreserved own bond is used first, the remainder is spread
+    /// across stakers pro rata, each exposed to at most the executor's `risk` share of its stake.
+    /// The split covers less than `amount` if the collateral falls short.
+    pub(crate) fn split_collateral(
+        executor: &T::AccountId,
+        amount: BalanceOf<T>,
+    ) -> Vec<(T::AccountId, BalanceOf<T>)> {
+        let info = match <CandidateInfo<T>>::get(executor) {
+            Some(info) => info,
+            None => return vec![],
+        };
+
+        let own_available = info
+            .bond
+            .saturating_sub(<ReservedBonds<T>>::get(executor, executor));
+        let from_own = own_available.min(amount);
+        let mut split = vec![];
+
+        if !from_own.is_zero() {
+            split.push((executor.clone(), from_own));
+        }
+
+        let left = amount.saturating_sub(from_own);
+
+        if left.is_zero() {
+            return split
+        }
+
+        let risk = <ExecutorConfig<T>>::get(executor)
+            .map(|config| config.risk)
+            .unwrap_or_else(Percent::zero);
+
+        let capacities = <TopStakes<T>>::get(executor)
+            .map(|top| top.stakes)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|bond| {
+                let capacity = (risk * bond.amount)
+                    .saturating_sub(<ReservedBonds<T>>::get(executor, &bond.owner));
+                (bond.owner, capacity)
+            })
+            .collect::<Vec<_>>();
+
+        split.extend(Self::spread_pro_rata(left, capacities));
+
+        split
+    }
+
+    /// Releases up to `amount` of the collateral reserved for an executor.
+    /// The executor's own bond is released first, then stakes pro rata to what each has reserved.
+    /// Returns the released parts per contributor.
+    pub(crate) fn release_collateral(
+        executor: &T::AccountId,
+        amount: BalanceOf<T>,
+    ) -> Vec<(T::AccountId, BalanceOf<T>)> {
+        let from_own = <ReservedBonds<T>>::get(executor, executor).min(amount);
+        let mut released = vec![];
+
+        if !from_own.is_zero() {
+            released.push((executor.clone(), from_own));
+        }
+
+        let left = amount.saturating_sub(from_own);
+
+        if !left.is_zero() {
+            let reserved_by_stakers = <ReservedBonds<T>>::iter_prefix(executor)
+                .filter(|(staker, _)| staker != executor)
+                .collect::<Vec<_>>();
+
+            released.extend(Self::spread_pro_rata(left, reserved_by_stakers));
+        }
+
+        for (account, part) in released.iter() {
+            let reserved = <ReservedBonds<T>>::get(executor, account).saturating_sub(*part);
+            if reserved.is_zero() {
+                <ReservedBonds<T>>::remove(executor, account);
+            } else {
+                <ReservedBonds<T>>::insert(executor, account, reserved);
+            }
+        }
+
+        released
+    }
+
+    /// Spreads `amount` across accounts pro rata to their caps, never exceeding a cap.
+    /// Rounding dust is handed out in order to accounts with spare cap.
+    fn spread_pro_rata(
+        amount: BalanceOf<T>,
+        caps: Vec<(T::AccountId, BalanceOf<T>)>,
+    ) -> Vec<(T::AccountId, BalanceOf<T>)> {
+        let total_cap = caps.iter().fold(BalanceOf::<T>::zero(), |acc, (_, cap)| {
+            acc.saturating_add(*cap)
+        });
+
+        if total_cap.is_zero() {
+            return vec![]
+        }
+
+        let to_spread = amount.min(total_cap);
+        let mut left = to_spread;
+        let mut shares = caps
+            .iter()
+            .map(|(account, cap)| {
+                let share = (Perbill::from_rational(*cap, total_cap) * to_spread).min(*cap);
+                left = left.saturating_sub(share);
+                (account.clone(), share)
+            })
+            .collect::<Vec<_>>();
+
+        for ((_, share), (_, cap)) in shares.iter_mut().zip(caps.iter()) {
+            if left.is_zero() {
+                break
+            }
+            let extra = cap.saturating_sub(*share).min(left);
+            *share = share.saturating_add(extra);
+            left = left.saturating_sub(extra);
+        }
+
+        shares.retain(|(_, share)| !share.is_zero());
+
+        shares
+    }
+
+    /// Slashes an executor's own bond, moving the slashed funds to the beneficiary.
+    /// An executor whose bond falls below the minimum candidate bond is taken offline.
+    pub(crate) fn slash_own_bond(
+        executor: &T::AccountId,
+        amount: BalanceOf<T>,
+        beneficiary: &T::AccountId,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        let mut info = <CandidateInfo<T>>::get(executor).ok_or(Error::<T>::NoSuchCandidate)?;
+
+        let (imbalance, _) = T::Currency::slash(executor, amount.min(info.bond));
+        let slashed = imbalance.peek();
+        T::Currency::resolve_creating(beneficiary, imbalance);
+
+        info.bond = info.bond.saturating_sub(slashed);
+        info.total_counted = info.total_counted.saturating_sub(slashed);
+
+        T::Currency::set_lock(
+            EXECUTOR_LOCK_ID,
+            executor,
+            info.bond,
+            WithdrawReasons::all(),
+        );
+
+        <Total<T>>::mutate(|total| *total = total.saturating_sub(slashed));
+
+        if info.is_active() && info.bond < <Fixtures<T>>::get().min_candidate_bond {
+            info.go_offline();
+
+            let mut candidates = <CandidatePool<T>>::get();
+            if candidates.remove(&Bond::from_owner(executor.clone())) {
+                <CandidatePool<T>>::put(candidates);
+            }
+
+            <ActiveSet<T>>::mutate(|active_set| active_set.retain(|active| active != executor));
+
+            Self::deposit_event(Event::CandidateWentOffline {
+                candidate: executor.clone(),
+            });
+        } else if info.is_active() {
+            Self::update_active(executor.clone(), info.total_counted);
+        }
+
+        <CandidateInfo<T>>::insert(executor, info);
+
+        Ok(slashed)
+    }
+
+    /// Slashes a staker's stake towards an executor, moving the slashed funds to the beneficiary.
+    pub(crate) fn slash_stake(
+        executor: &T::AccountId,
+        staker: &T::AccountId,
+        amount: BalanceOf<T>,
+        beneficiary: &T::AccountId,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        let mut state = <StakerInfo<T>>::get(staker).ok_or(Error::<T>::NoSuchStaker)?;
+        let mut info = <CandidateInfo<T>>::get(executor).ok_or(Error::<T>::NoSuchCandidate)?;
+
+        let bond = state
+            .stakes
+            .0
+            .iter_mut()
+            .find(|bond| &bond.owner == executor)
+            .ok_or(Error::<T>::NoSuchStake)?;
+        let before = bond.amount;
+        let less = amount.min(before);
+
+        ensure!(!less.is_zero(), Error::<T>::NoSuchStake);
+
+        info.decrease_stake::<T>(executor, staker.clone(), before, less)?;
+        bond.amount = before.saturating_sub(less);
+
+        let (imbalance, _) = T::Currency::slash(staker, less);
+        let slashed = imbalance.peek();
+        T::Currency::resolve_creating(beneficiary, imbalance);
+
+        state.total_sub::<T>(less)?;
+
+        <Total<T>>::mutate(|total| *total = total.saturating_sub(less));
+
+        if info.is_active() {
+            Self::update_active(executor.clone(), info.total_counted);
+        }
+
+        <CandidateInfo<T>>::insert(executor, info);
+        <StakerInfo<T>>::insert(staker, state);
+
+        Ok(slashed)
+    }
+
+    /// Increases an executor's own bond by up to `amount` of its free, not yet bonded balance.
+    pub(crate) fn bond_free_balance(
+        executor: &T::AccountId,
+        amount: BalanceOf<T>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        let mut info = <CandidateInfo<T>>::get(executor).ok_or(Error::<T>::NoSuchCandidate)?;
+
+        let rewarded = T::Currency::free_balance(executor)
+            .saturating_sub(info.bond)
+            .min(amount);
+
+        ensure!(!rewarded.is_zero(), Error::<T>::InsufficientBalance);
+
+        info.bond = info.bond.saturating_add(rewarded);
+        info.total_counted = info.total_counted.saturating_add(rewarded);
+
+        T::Currency::set_lock(
+            EXECUTOR_LOCK_ID,
+            executor,
+            info.bond,
+            WithdrawReasons::all(),
+        );
+
+        <Total<T>>::mutate(|total| *total = total.saturating_add(rewarded));
+
+        if info.is_active() {
+            Self::update_active(executor.clone(), info.total_counted);
+        }
+
+        Self::deposit_event(Event::CandidateBondedMore {
+            candidate: executor.clone(),
+            amount: rewarded,
+            total_bond: info.bond,
+        });
+
+        <CandidateInfo<T>>::insert(executor, info);
+
+        Ok(rewarded)
+    }
+}
+
+impl<T: Config> Executors<T, BalanceOf<T>> for Pallet<T> {
+    fn active_set() -> Vec<T::AccountId> {
+        <ActiveSet<T>>::get()
+    }
+
+    /// Reserves collateral of an executor, taken from its own bond first and then from its stakers.
+    fn reserve_bond(
+        executor: &T::AccountId,
+        amount: BalanceOf<T>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        if amount.is_zero() {
+            return Ok(Zero::zero())
+        }
+
+        ensure!(Self::is_candidate(executor), Error::<T>::NoSuchCandidate);
+
+        let split = Self::split_collateral(executor, amount);
+        let covered = split.iter().fold(BalanceOf::<T>::zero(), |acc, (_, part)| {
+            acc.saturating_add(*part)
+        });
+
+        ensure!(covered == amount, Error::<T>::InsufficientCollateral);
+
+        for (account, part) in split {
+            <ReservedBonds<T>>::mutate(executor, account, |reserved| {
+                *reserved = reserved.saturating_add(part)
+            });
+        }
+
+        Ok(amount)
+    }
+
+    fn unreserve_bond(executor: &T::AccountId, amount: BalanceOf<T>) -> BalanceOf<T> {
+        Self::release_collateral(executor, amount)
+            .into_iter()
+            .fold(Zero::zero(), |acc: BalanceOf<T>, (_, part)| {
+                acc.saturating_add(part)
+            })
+    }
+
+    /// Slashes the collateral reserved for an executor first.
+    /// Any shortfall is then slashed from unreserved collateral, own bond first.
+    /// The slashed funds are moved to the beneficiary.
+    fn slash_bond(
+        executor: &T::AccountId,
+        amount: BalanceOf<T>,
+        beneficiary: &T::AccountId,
+    ) -> BalanceOf<T> {
+        let mut penalties = Self::release_collateral(executor, amount);
+        let released = penalties
+            .iter()
+            .fold(BalanceOf::<T>::zero(), |acc, (_, part)| {
+                acc.saturating_add(*part)
+            });
+
+        if released < amount {
+            penalties.extend(Self::split_collateral(
+                executor,
+                amount.saturating_sub(released),
+            ));
+        }
+
+        let mut total_slashed = BalanceOf::<T>::zero();
+
+        for (account, part) in penalties {
+            let slashed: Result<BalanceOf<T>, DispatchError> = with_storage_layer(|| {
+                if &account == executor {
+                    Self::slash_own_bond(executor, part, beneficiary)
+                } else {
+                    Self::slash_stake(executor, &account, part, beneficiary)
+                }
+            });
+
+            match slashed {
+                Ok(slashed) => {
+                    total_slashed = total_slashed.saturating_add(slashed);
+                    Self::deposit_event(Event::CollateralSlashed {
+                        executor: executor.clone(),
+                        account,
+                        amount: slashed,
+                    });
+                },
+                Err(err) => log::warn!(
+                    "failed to slash {:?} of {:?} backing executor {:?} -- {:?}",
+                    part,
+                    account,
+                    executor,
+                    err
+                ),
+            }
+        }
+
+        total_slashed
+    }
+
+    /// Bonds funds the executor already holds, e.g. collateral slashed to it; nothing is minted.
+    fn increase_bond(executor: &T::AccountId, amount: BalanceOf<T>) -> BalanceOf<T> {
+        if amount.is_zero() {
+            return Zero::zero()
+        }
+
+        let increased: Result<BalanceOf<T>, DispatchError> =
+            with_storage_layer(|| Self::bond_free_balance(executor, amount));
+
+        increased.unwrap_or_else(|_| Zero::zero())
+    }
+
+    fn collateral_bond(executor: &T::AccountId) -> BalanceOf<T> {
+        <CandidateInfo<T>>::get(executor)
+            .map(|info| info.bond)
+            .unwrap_or_else(Zero::zero)
+    }
+
+    fn min_candidate_bond() -> BalanceOf<T> {
+        <Fixtures<T>>::get().min_candidate_bond
+    }
+
+    fn total_nominated_stake(executor: &T::AccountId) -> BalanceOf<T> {
+        <CandidateInfo<T>>::get(executor)
+            .map(|info| info.total_counted.saturating_sub(info.bond))
+            .unwrap_or_else(Zero::zero)
+    }
+
+    fn stakes(staker: &T::AccountId) -> Vec<NominatedStake<T::AccountId, BalanceOf<T>>> {
+        <StakerInfo<T>>::get(staker)
+            .map(|state| state.stakes.0)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|bond| NominatedStake {
+                executor: bond.owner,
+                staker: staker.clone(),
+                nominated_stake: bond.amount,
+            })
+            .collect()
+    }
+
+    fn stakes_per_executor(
+        executor: &T::AccountId,
+    ) -> Vec<NominatedStake<T::AccountId, BalanceOf<T>>> {
+        <TopStakes<T>>::get(executor)
+            .map(|top| top.stakes)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|bond| NominatedStake {
+                executor: executor.clone(),
+                staker: bond.owner,
+                nominated_stake: bond.amount,
+            })
+            .collect()
+    }
+
+    /// The active set is reselected at every treasury round transition in `on_initialize`.
+    fn recalculate_executors_stakes() {}
+}
+
+impl<T: Config> StakeSource<BalanceOf<T>> for Pallet<T> {
+    /// Total capital locked by executors and their stakers.
+    fn total_stake() -> BalanceOf<T> {
+        <Total<T>>::get()
+    }
+}
@@ -14,6 +14,7 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod collateral;
 pub mod stakes;
 pub mod staking_actions;
 pub mod subject_metadata;
@@ -29,18 +30,21 @@ pub mod pallet {
     use core::ops::Mul;
     use frame_support::{
         pallet_prelude::*,
-        traits::{tokens::WithdrawReasons, Currency, LockableCurrency, ReservableCurrency},
+        traits::{
+            tokens::WithdrawReasons, Currency, ExistenceRequirement, LockableCurrency,
+            ReservableCurrency,
+        },
     };
     use frame_system::{ensure_root, pallet_prelude::*};
     use sp_runtime::{
         traits::{One, Saturating, Zero},
-        Percent,
+        Perbill, Percent,
     };
     use sp_std::collections::btree_map::BTreeMap;
     use t3rn_primitives::{
         common::{OrderedSet, Range, RoundIndex},
         executors::{
-            Bond, CancelledScheduledStakingRequest, ExecutorInfo, ExecutorSnapshot,
+            Bond, CancelledScheduledStakingRequest, DelayedPayout, ExecutorInfo, ExecutorSnapshot,
             Fixtures as StakingFixtures, ScheduledConfigurationRequest, ScheduledStakingRequest,
             StakeAdjust, StakerAdded, StakingAction, EXECUTOR_LOCK_ID, STAKER_LOCK_ID,
         },
@@ -128,6 +132,18 @@ pub mod pallet {
         /// Treasury round proveider.
         type Treasury: TTreasury<Self>;
 
+        /// Number of rounds after which a round's staking rewards are paid out.
+        #[pallet::constant]
+        type RewardPaymentDelay: Get<RoundIndex>;
+
+        /// Share of a round's total active stake paid out as staking rewards.
+        #[pallet::constant]
+        type StakingRewardRate: Get<Perbill>;
+
+        /// Treasury account holding the executor allocation staking rewards are paid out from.
+        #[pallet::constant]
+        type RewardsAccount: Get<Self::AccountId>;
+
         type WeightInfo: weights::WeightInfo;
     }
 
@@ -240,6 +256,31 @@ pub mod pallet {
     #[pallet::getter(fn staked)]
     pub type Staked<T: Config> = StorageMap<_, Twox64Concat, RoundIndex, BalanceOf<T>, ValueQuery>;
 
+    /// Latest round for which the active set has been selected.
+    #[pallet::storage]
+    #[pallet::getter(fn last_round)]
+    pub type LastRound<T: Config> = StorageValue<_, RoundIndex, ValueQuery>;
+
+    /// Staking rewards pending payout by round.
+    #[pallet::storage]
+    #[pallet::getter(fn delayed_payouts)]
+    pub type DelayedPayouts<T: Config> =
+        StorageMap<_, Twox64Concat, RoundIndex, DelayedPayout<BalanceOf<T>>, OptionQuery>;
+
+    /// Collateral reserved for side effect bonds by executor and contributor.
+    /// The contributor is either the executor itself (own bond) or one of its stakers.
+    #[pallet::storage]
+    #[pallet::getter(fn reserved_bonds)]
+    pub type ReservedBonds<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        Twox64Concat,
+        T::AccountId,
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Sets all protocol enforced staking fixtures.
@@ -406,6 +447,11 @@ pub mod pallet {
             let mut state =
                 <CandidateInfo<T>>::get(&executor).ok_or(Error::<T>::NoSuchCandidate)?;
 
+            ensure!(
+                state.bond.saturating_sub(amount) >= <ReservedBonds<T>>::get(&executor, &executor),
+                Error::<T>::CollateralStillReserved
+            );
+
             let when = state.schedule_bond_less::<T>(amount)?;
 
             <CandidateInfo<T>>::insert(&executor, state);
@@ -431,6 +477,14 @@ pub mod pallet {
             let mut state =
                 <CandidateInfo<T>>::get(&candidate).ok_or(Error::<T>::NoSuchCandidate)?;
 
+            if let Some(request) = state.request {
+                ensure!(
+                    state.bond.saturating_sub(request.amount)
+                        >= <ReservedBonds<T>>::get(&candidate, &candidate),
+                    Error::<T>::CollateralStillReserved
+                );
+            }
+
             state.execute_bond_less::<T>(candidate.clone())?;
 
             <CandidateInfo<T>>::insert(&candidate, state);
@@ -582,6 +636,11 @@ pub mod pallet {
 
             state.can_leave::<T>()?;
 
+            ensure!(
+                <ReservedBonds<T>>::iter_prefix(&candidate).next().is_none(),
+                Error::<T>::CollateralStillReserved
+            );
+
             let return_stake = |bond: Bond<T::AccountId, BalanceOf<T>>| -> DispatchResult {
                 // remove stake from staker state
                 let mut state = StakerInfo::<T>::get(&bond.owner).expect(
@@ -741,6 +800,11 @@ pub mod pallet {
 
             ensure!(!state.is_leaving(), Error::<T>::CannotGoOnlineIfLeaving);
 
+            ensure!(
+                state.bond >= <Fixtures<T>>::get().min_candidate_bond,
+                Error::<T>::CandidateBondBelowMin
+            );
+
             state.go_online();
 
             let mut candidates = <CandidatePool<T>>::get();
@@ -983,10 +1047,26 @@ pub mod pallet {
         //
         // This function must return the weight consumed by `on_initialize` and `on_finalize`.
         fn on_initialize(_n: T::BlockNumber) -> Weight {
-            //TODO listen2round updates then reselect active set + prep payouts
-            //TODO//TODO//TODO//TODO//TODO//TODO//TODO//TODO//TODO//TODO//TODO
+            let mut weight = T::WeightInfo::base_on_initialize();
+            let round = T::Treasury::current_round();
 
-            419
+            if round.index > <LastRound<T>>::get() {
+                <LastRound<T>>::put(round.index);
+
+                // snapshot the active set and its stakes for the new round
+                let (executor_count, stake_count, total_staked) =
+                    Self::select_active_set(round.index);
+                <Staked<T>>::insert(round.index, total_staked);
+
+                Self::prepare_staking_payouts(round.index);
+
+                weight = weight.saturating_add(T::WeightInfo::round_transition_on_initialize(
+                    executor_count,
+                    stake_count,
+                ));
+            }
+
+            weight.saturating_add(Self::handle_delayed_payouts(round.index))
         }
     }
 
@@ -1160,6 +1240,12 @@ pub mod pallet {
             candidate_bond_less_delay: u32,
             revoke_stake_delay: u32,
         },
+        /// Collateral backing an executor, either its own bond or a stake, has been slashed.
+        CollateralSlashed {
+            executor: T::AccountId,
+            account: T::AccountId,
+            amount: BalanceOf<T>,
+        },
     }
 
     #[pallet::error]
@@ -1204,6 +1290,8 @@ pub mod pallet {
         InsufficientBalance,
         MaxStakesExceeded,
         AlreadyStakedCandidate,
+        InsufficientCollateral,
+        CollateralStillReserved,
     }
 
     #[pallet::genesis_config]
@@ -1328,154 +1416,157 @@ pub mod pallet {
             Ok(())
         }
 
-        // fn prepare_staking_payouts(now: RoundIndex) {
-        // 	// payout is now - delay rounds ago => now - delay > 0 else return early
-        // 	let delay = T::RewardPaymentDelay::get();
-        // 	if now <= delay {
-        // 		return;
-        // 	}
-        // 	let round_to_payout = now.saturating_sub(delay);
-        // 	let total_points = <Points<T>>::get(round_to_payout);
-        // 	if total_points.is_zero() {
-        // 		return;
-        // 	}
-        // 	let total_staked = <Staked<T>>::take(round_to_payout);
-        // 	let total_issuance = Self::compute_issuance(total_staked);
-        // 	let mut left_issuance = total_issuance;
-        // 	// reserve portion of issuance for parachain bond account
-        // 	let bond_config = <ParachainBondInfo<T>>::get();
-        // 	let parachain_bond_reserve = bond_config.percent * total_issuance;
-        // 	if let Ok(imb) =
-        // 		T::Currency::deposit_into_existing(&bond_config.account, parachain_bond_reserve)
-        // 	{
-        // 		// update round issuance iff transfer succeeds
-        // 		left_issuance = left_issuance.saturating_sub(imb.peek());
-        // 		Self::deposit_event(Event::ReservedForParachainBond {
-        // 			account: bond_config.account,
-        // 			value: imb.peek(),
-        // 		});
-        // 	}
-
-        // 	let payout = DelayedPayout {
-        // 		round_issuance: total_issuance,
-        // 		total_staking_reward: left_issuance,
-        // 		executor_commission: <Commissions<T>>::get(),
-        // 	};
-
-        // 	<DelayedPayouts<T>>::insert(round_to_payout, payout);
-        // }
-
-        // /// Wrapper around pay_one_executor_reward which handles the following logic:
-        // /// * whether or not a payout needs to be made
-        // /// * cleaning up when payouts are done
-        // /// * returns the weight consumed by pay_one_executor_reward if applicable
-        // fn handle_delayed_payouts(now: RoundIndex) -> Weight {
-        // 	let delay = T::RewardPaymentDelay::get();
-
-        // 	// don't underflow uint
-        // 	if now < delay {
-        // 		return 0u64.into();
-        // 	}
-
-        // 	let paid_for_round = now.saturating_sub(delay);
-
-        // 	if let Some(payout_info) = <DelayedPayouts<T>>::get(paid_for_round) {
-        // 		let result = Self::pay_one_executor_reward(paid_for_round, payout_info);
-        // 		if result.0.is_none() {
-        // 			// result.0 indicates whether or not a payout was made
-        // 			// clean up storage items that we no longer need
-        // 			<DelayedPayouts<T>>::remove(paid_for_round);
-        // 			<Points<T>>::remove(paid_for_round);
-        // 		}
-        // 		result.1 // weight consumed by pay_one_executor_reward
-        // 	} else {
-        // 		0u64.into()
-        // 	}
-        // }
-
-        // /// Payout a single executor from the given round.
-        // ///
-        // /// Returns an optional tuple of (Executor's AccountId, total paid)
-        // /// or None if there were no more payouts to be made for the round.
-        // pub(crate) fn pay_one_executor_reward(
-        // 	paid_for_round: RoundIndex,
-        // 	payout_info: DelayedPayout<BalanceOf<T>>,
-        // ) -> (Option<(T::AccountId, BalanceOf<T>)>, Weight) {
-        // 	// TODO: it would probably be optimal to roll Points into the DelayedPayouts storage
-        // 	// item so that we do fewer reads each block
-        // 	let total_points = <Points<T>>::get(paid_for_round);
-        // 	if total_points.is_zero() {
-        // 		// TODO: this case is obnoxious... it's a value query, so it could mean one of two
-        // 		// different logic errors:
-        // 		// 1. we removed it before we should have
-        // 		// 2. we called pay_one_executor_reward when we were actually done with deferred
-        // 		//    payouts
-        // 		log::warn!("pay_one_executor_reward called with no <Points<T>> for the round!");
-        // 		return (None, 0u64.into());
-        // 	}
-
-        // 	let mint = |amt: BalanceOf<T>, to: T::AccountId| {
-        // 		if let Ok(amount_transferred) = T::Currency::deposit_into_existing(&to, amt) {
-        // 			Self::deposit_event(Event::Rewarded {
-        // 				account: to.clone(),
-        // 				rewards: amount_transferred.peek(),
-        // 			});
-        // 		}
-        // 	};
-
-        // 	let executor_fee = payout_info.executor_commission;
-        // 	let executor_issuance = executor_fee * payout_info.round_issuance;
-
-        // 	if let Some((executor, pts)) =
-        // 		<AwardedPts<T>>::iter_prefix(paid_for_round).drain().next()
-        // 	{
-        // 		let mut extra_weight = 0;
-        // 		let pct_due = Perbill::from_rational(pts, total_points);
-        // 		let total_paid = pct_due * payout_info.total_staking_reward;
-        // 		let mut amt_due = total_paid;
-        // 		// Take the snapshot of block author and stakes
-        // 		let state = <AtStake<T>>::take(paid_for_round, &executor);
-        // 		let num_stakers = state.stakes.len();
-        // 		if state.stakes.is_empty() {
-        // 			// solo executor with no stakers
-        // 			mint(amt_due, executor.clone());
-        // 			extra_weight += T::OnCollatorPayout::on_executor_payout(
-        // 				paid_for_round,
-        // 				executor.clone(),
-        // 				amt_due,
-        // 			);
-        // 		} else {
-        // 			// pay executor first; commission + due_portion
-        // 			let executor_pct = Perbill::from_rational(state.bond, state.total);
-        // 			let commission = pct_due * executor_issuance;
-        // 			amt_due = amt_due.saturating_sub(commission);
-        // 			let executor_reward = (executor_pct * amt_due).saturating_add(commission);
-        // 			mint(executor_reward, executor.clone());
-        // 			extra_weight += T::OnCollatorPayout::on_executor_payout(
-        // 				paid_for_round,
-        // 				executor.clone(),
-        // 				executor_reward,
-        // 			);
-        // 			// pay stakers due portion
-        // 			for Bond { owner, amount } in state.stakes {
-        // 				let percent = Perbill::from_rational(amount, state.total);
-        // 				let due = percent * amt_due;
-        // 				if !due.is_zero() {
-        // 					mint(due, owner.clone());
-        // 				}
-        // 			}
-        // 		}
-
-        // 		(
-        // 			Some((executor, total_paid)),
-        // 			T::WeightInfo::pay_one_executor_reward(num_stakers as u32) + extra_weight,
-        // 		)
-        // 	} else {
-        // 		// Note that we don't clean up storage here; it is cleaned up in
-        // 		// handle_delayed_payouts()
-        // 		(None, 0u64.into())
-        // 	}
-        // }
+        /// Registers the delayed payout for the round rewarded at round `now`.
+        /// Round issuance is the staking reward rate applied to the round's total stake,
+        /// capped by what's left of the executor allocation in the rewards account
+        /// once the rewards still due for earlier rounds are set aside.
+        pub(crate) fn prepare_staking_payouts(now: RoundIndex) {
+            let delay = T::RewardPaymentDelay::get();
+
+            if now <= delay {
+                return
+            }
+
+            let round_to_payout = now.saturating_sub(delay);
+            let total_staked = <Staked<T>>::take(round_to_payout);
+
+            if total_staked.is_zero() {
+                return
+            }
+
+            let rewards_account = T::RewardsAccount::get();
+            let outstanding = <DelayedPayouts<T>>::iter_values().fold(
+                BalanceOf::<T>::zero(),
+                |outstanding, payout| {
+                    outstanding.saturating_add(payout.round_issuance.saturating_sub(payout.paid))
+                },
+            );
+            let round_issuance = (T::StakingRewardRate::get() * total_staked).min(
+                T::Currency::free_balance(&rewards_account)
+                    .saturating_sub(T::Currency::minimum_balance())
+                    .saturating_sub(outstanding),
+            );
+
+            <DelayedPayouts<T>>::insert(
+                round_to_payout,
+                DelayedPayout {
+                    round_issuance,
+                    total_staked,
+                    paid: Zero::zero(),
+                },
+            );
+        }
+
+        /// Wrapper around pay_one_executor_reward which handles the following logic:
+        /// * whether or not a payout needs to be made
+        /// * paying out rounds left unfinished before the current one
+        /// * cleaning up when payouts are done
+        /// * returns the weight consumed by pay_one_executor_reward if applicable
+        pub(crate) fn handle_delayed_payouts(now: RoundIndex) -> Weight {
+            let delay = T::RewardPaymentDelay::get();
+
+            // don't underflow uint
+            if now < delay {
+                return 0
+            }
+
+            // the oldest round first, so no round is left unpaid with its rewards set aside
+            let paid_for_round = match <DelayedPayouts<T>>::iter_keys().min() {
+                Some(round) if round <= now.saturating_sub(delay) => round,
+                _ => return 0,
+            };
+
+            if let Some(payout_info) = <DelayedPayouts<T>>::get(paid_for_round) {
+                let (paid, weight) = Self::pay_one_executor_reward(paid_for_round, payout_info);
+                if paid.is_none() {
+                    // all executors of the round have been paid
+                    <DelayedPayouts<T>>::remove(paid_for_round);
+                }
+                weight
+            } else {
+                0
+            }
+        }
+
+        /// Payout a single executor and its stakers from the given round.
+        /// The executor's commission is deducted first, the remainder is split
+        /// between the executor's own bond and its stakes pro rata.
+        /// Rewards are transferred from the rewards account, nothing is minted.
+        /// What was actually transferred is added to the round's paid rewards.
+        ///
+        /// Returns an optional tuple of (Executor's AccountId, total paid)
+        /// or None if there were no more payouts to be made for the round.
+        pub(crate) fn pay_one_executor_reward(
+            paid_for_round: RoundIndex,
+            payout_info: DelayedPayout<BalanceOf<T>>,
+        ) -> (Option<(T::AccountId, BalanceOf<T>)>, Weight) {
+            let rewards_account = T::RewardsAccount::get();
+            let pay = |amount: BalanceOf<T>, to: T::AccountId| match T::Currency::transfer(
+                &rewards_account,
+                &to,
+                amount,
+                ExistenceRequirement::KeepAlive,
+            ) {
+                Ok(()) => {
+                    <DelayedPayouts<T>>::mutate(paid_for_round, |maybe_payout| {
+                        if let Some(payout) = maybe_payout {
+                            payout.paid = payout.paid.saturating_add(amount);
+                        }
+                    });
+                    Self::deposit_event(Event::Rewarded {
+                        account: to,
+                        rewards: amount,
+                    })
+                },
+                Err(err) => log::warn!(
+                    "failed to pay staking reward of {:?} to '{:?}' -- {:?}",
+                    amount,
+                    to,
+                    err
+                ),
+            };
+
+            if let Some((executor, state)) =
+                <AtStake<T>>::iter_prefix(paid_for_round).drain().next()
+            {
+                let num_stakers = state.stakes.len() as u32;
+                let total_paid = Perbill::from_rational(state.total, payout_info.total_staked)
+                    * payout_info.round_issuance;
+
+                if state.stakes.is_empty() {
+                    // solo executor with no stakers
+                    pay(total_paid, executor.clone());
+                } else {
+                    let commission = <ExecutorConfig<T>>::get(&executor)
+                        .map(|config| config.commission)
+                        .unwrap_or_else(Percent::zero)
+                        * total_paid;
+                    let amount_due = total_paid.saturating_sub(commission);
+                    let executor_reward = (Perbill::from_rational(state.bond, state.total)
+                        * amount_due)
+                        .saturating_add(commission);
+
+                    pay(executor_reward, executor.clone());
+
+                    // pay stakers due portion
+                    for Bond { owner, amount } in state.stakes {
+                        let due = Perbill::from_rational(amount, state.total) * amount_due;
+                        if !due.is_zero() {
+                            pay(due, owner);
+                        }
+                    }
+                }
+
+                (
+                    Some((executor, total_paid)),
+                    T::WeightInfo::pay_one_executor_reward(num_stakers),
+                )
+            } else {
+                // Note that we don't clean up storage here; it is cleaned up in
+                // handle_delayed_payouts()
+                (None, 0)
+            }
+        }
 
         /// Selects executors into the active set.
         /// Best as in most cumulatively supported in terms of stake.
@@ -1518,15 +1609,13 @@ pub mod pallet {
                 // `ActiveSet` remains unchanged from last round
                 // emit ExecutorChosen event for tools that use this event
                 for candidate in <ActiveSet<T>>::get() {
-                    let snapshot_total = total_per_candidate
-                        .get(&candidate)
-                        .expect("all selected candidates have snapshots");
-
-                    Self::deposit_event(Event::ExecutorChosen {
-                        round: current_round,
-                        executor: candidate,
-                        total_counted: *snapshot_total,
-                    })
+                    if let Some(snapshot_total) = total_per_candidate.get(&candidate) {
+                        Self::deposit_event(Event::ExecutorChosen {
+                            round: current_round,
+                            executor: candidate,
+                            total_counted: *snapshot_total,
+                        })
+                    }
                 }
                 return (executor_count, stake_count, total)
             }
@@ -145,13 +145,21 @@ impl pallet_treasury::Config for Test {
     type InflationRegressionMonths = InflationRegressionMonths;
     type MinRoundTerm = MinRoundTerm;
     type ReserveAccount = ReserveAccount;
+    type StakeSource = Executors;
     type TreasuryAccount = TreasuryAccount;
     type WeightInfo = ();
 }
 
+parameter_types! {
+    pub const StakingRewardRate: Perbill = Perbill::from_percent(1);
+}
+
 impl pallet_executors::Config for Test {
     type Currency = Balances;
     type Event = Event;
+    type RewardPaymentDelay = frame_support::traits::ConstU32<2>;
+    type RewardsAccount = TreasuryAccount;
+    type StakingRewardRate = StakingRewardRate;
     type Treasury = Treasury;
     type WeightInfo = ();
 }
@@ -1,12 +1,12 @@
 use crate::{
     pallet::{
-        BalanceOf, CandidateInfo, Config, Error, Event, Fixtures, Pallet, ScheduledStakingRequests,
-        StakerInfo, Total,
+        BalanceOf, CandidateInfo, Config, Error, Event, Fixtures, Pallet, ReservedBonds,
+        ScheduledStakingRequests, StakerInfo, Total,
     },
     subject_metadata::StakerMetadata,
 };
 use frame_support::{dispatch::DispatchResultWithPostInfo, ensure, traits::ReservableCurrency};
-use sp_runtime::traits::Saturating;
+use sp_runtime::traits::{Saturating, Zero};
 use sp_std::{vec, vec::Vec};
 use t3rn_primitives::{
     executors::{ScheduledStakingRequest, StakingAction},
@@ -183,8 +183,12 @@ impl<T: Config> Pallet<T> {
             <Error<T>>::PendingStakeRequestNotDueYet
         );
 
+        let reserved = <ReservedBonds<T>>::get(&executor, &staker);
+
         match request.action {
             StakingAction::Revoke(amount) => {
+                ensure!(reserved.is_zero(), <Error<T>>::CollateralStillReserved);
+
                 // revoking last stake => leaving set of stakers
                 let leaving = if state.stakes.0.len() == 1usize {
                     true
@@ -235,6 +239,11 @@ impl<T: Config> Pallet<T> {
                 for bond in &mut state.stakes.0 {
                     if bond.owner == executor {
                         return if bond.amount > amount {
+                            ensure!(
+                                bond.amount.saturating_sub(amount) >= reserved,
+                                <Error<T>>::CollateralStillReserved
+                            );
+
                             let amount_before: BalanceOf<T> = bond.amount;
                             bond.amount = bond.amount.saturating_sub(amount);
 
@@ -426,6 +435,11 @@ impl<T: Config> Pallet<T> {
                 <Error<T>>::StakerCannotLeaveYet
             );
 
+            ensure!(
+                <ReservedBonds<T>>::get(&bond.owner, &staker).is_zero(),
+                <Error<T>>::CollateralStillReserved
+            );
+
             validated_scheduled_requests.push((bond.clone(), scheduled_requests, request_idx))
         }
 
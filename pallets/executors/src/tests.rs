@@ -1,25 +1,28 @@
 use crate::{
     assert_last_event, assert_last_n_events,
     mock::{
-        fast_forward_to, new_test_ext, Balance, Event as MockEvent, Executors, Origin, System,
-        Test, Treasury,
+        fast_forward_to, new_test_ext, Balance, Balances, Event as MockEvent, Executors, Origin,
+        System, Test, Treasury, TreasuryAccount,
     },
     pallet::{
-        BottomStakes, CandidateInfo, CandidatePool, Config, Error, Event, ExecutorConfig,
-        ScheduledConfigurationRequests, StakerInfo, TopStakes, Total,
+        BottomStakes, CandidateInfo, CandidatePool, Config, DelayedPayouts, Error, Event,
+        ExecutorConfig, ScheduledConfigurationRequests, Staked, StakerInfo, TopStakes, Total,
     },
     stakes::Stakes,
     subject_metadata::{CandidateMetadata, StakerMetadata},
 };
 
-use frame_support::{assert_noop, assert_ok, traits::Currency};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{Currency, Get},
+};
 use sp_runtime::Percent;
 use t3rn_primitives::{
     common::{OrderedSet, Range, DEFAULT_ROUND_TERM},
     executors::{
-        Bond, ExecutorInfo, ExecutorStatus, Fixtures as StakingFixtures,
-        ScheduledConfigurationRequest, ScheduledStakingRequest, StakerAdded, StakerStatus,
-        StakingAction,
+        Bond, DelayedPayout, ExecutorInfo, ExecutorSnapshot, ExecutorStatus,
+        Executors as ExecutorsExt, Fixtures as StakingFixtures, ScheduledConfigurationRequest,
+        ScheduledStakingRequest, StakerAdded, StakerStatus, StakingAction,
     },
     monetary::DECIMALS,
 };
@@ -2819,3 +2822,354 @@ fn cancel_leave_stakers_successfully() {
         assert_last_event!(MockEvent::Executors(Event::StakerExitCancelled { staker }));
     });
 }
+
+#[test]
+fn reserve_bond_takes_own_bond_first_then_stakers_up_to_risk() {
+    new_test_ext().execute_with(|| {
+        let executor = 14;
+        let stakers = [15, 16];
+        let bond = 10_000;
+
+        drop(<Test as Config>::Currency::deposit_creating(
+            &executor, bond,
+        ));
+
+        assert_ok!(Executors::join_candidates(
+            Origin::signed(executor),
+            bond,
+            0
+        ));
+
+        assert_ok!(Executors::schedule_configure_executor(
+            Origin::signed(executor),
+            Percent::from_percent(10),
+            Percent::from_percent(50),
+        ));
+
+        for (i, staker) in stakers.iter().enumerate() {
+            drop(<Test as Config>::Currency::deposit_creating(staker, bond));
+
+            assert_ok!(Executors::stake(
+                Origin::signed(*staker),
+                executor,
+                bond,
+                i as u32,
+                0
+            ));
+        }
+
+        assert_ok!(<Executors as ExecutorsExt<Test, Balance>>::reserve_bond(
+            &executor, 14_000
+        ));
+
+        assert_eq!(Executors::reserved_bonds(executor, executor), 10_000);
+        assert_eq!(Executors::reserved_bonds(executor, 15), 2_000);
+        assert_eq!(Executors::reserved_bonds(executor, 16), 2_000);
+
+        // stakers are exposed to at most half of their stake
+        assert_noop!(
+            <Executors as ExecutorsExt<Test, Balance>>::reserve_bond(&executor, 7_000),
+            <Error<Test>>::InsufficientCollateral,
+        );
+
+        assert_eq!(
+            <Executors as ExecutorsExt<Test, Balance>>::unreserve_bond(&executor, 12_000),
+            12_000
+        );
+
+        assert_eq!(Executors::reserved_bonds(executor, executor), 0);
+        assert_eq!(Executors::reserved_bonds(executor, 15), 1_000);
+        assert_eq!(Executors::reserved_bonds(executor, 16), 1_000);
+    });
+}
+
+#[test]
+fn reserve_bond_fails_for_non_candidates() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            <Executors as ExecutorsExt<Test, Balance>>::reserve_bond(&14, 1),
+            <Error<Test>>::NoSuchCandidate,
+        );
+
+        assert_ok!(
+            <Executors as ExecutorsExt<Test, Balance>>::reserve_bond(&14, 0),
+            0
+        );
+    });
+}
+
+#[test]
+fn slash_bond_slashes_own_bond_first_then_stakes() {
+    new_test_ext().execute_with(|| {
+        let executor = 14;
+        let staker = 15;
+        let beneficiary = 16;
+        let bond = 10_000;
+
+        drop(<Test as Config>::Currency::deposit_creating(
+            &executor,
+            2 * bond,
+        ));
+        drop(<Test as Config>::Currency::deposit_creating(
+            &staker,
+            2 * bond,
+        ));
+
+        assert_ok!(Executors::join_candidates(
+            Origin::signed(executor),
+            bond,
+            0
+        ));
+
+        assert_ok!(Executors::schedule_configure_executor(
+            Origin::signed(executor),
+            Percent::from_percent(10),
+            Percent::from_percent(50),
+        ));
+
+        assert_ok!(Executors::stake(
+            Origin::signed(staker),
+            executor,
+            bond,
+            0,
+            0
+        ));
+
+        assert_ok!(<Executors as ExecutorsExt<Test, Balance>>::reserve_bond(
+            &executor, 12_000
+        ));
+
+        let issuance = Balances::total_issuance();
+
+        assert_eq!(
+            <Executors as ExecutorsExt<Test, Balance>>::slash_bond(&executor, 12_000, &beneficiary),
+            12_000
+        );
+
+        assert_eq!(Executors::reserved_bonds(executor, executor), 0);
+        assert_eq!(Executors::reserved_bonds(executor, staker), 0);
+
+        let info = Executors::candidate_info(executor).unwrap();
+        assert_eq!(info.bond, 0);
+        assert_eq!(info.total_counted, 8_000);
+        assert_eq!(Executors::staker_info(staker).unwrap().total, 8_000);
+        assert_eq!(Executors::total_value_locked(), 8_000);
+
+        assert_eq!(Balances::free_balance(executor), bond);
+        assert_eq!(Balances::free_balance(staker), 18_000);
+        assert_eq!(Balances::free_balance(beneficiary), 12_000);
+        assert_eq!(Balances::total_issuance(), issuance);
+
+        // slashed below the minimum candidate bond
+        assert!(!info.is_active());
+        assert_eq!(<CandidatePool<Test>>::get().0.len(), 0);
+        assert_noop!(
+            Executors::go_online(Origin::signed(executor)),
+            <Error<Test>>::CandidateBondBelowMin
+        );
+
+        assert_last_event!(MockEvent::Executors(Event::CollateralSlashed {
+            executor,
+            account: staker,
+            amount: 2_000,
+        }));
+
+        assert_eq!(
+            <Executors as ExecutorsExt<Test, Balance>>::increase_bond(&executor, 2_000),
+            2_000
+        );
+        assert_eq!(Executors::candidate_info(executor).unwrap().bond, 2_000);
+        assert_eq!(Balances::total_issuance(), issuance);
+        assert_eq!(
+            <Executors as ExecutorsExt<Test, Balance>>::increase_bond(&staker, 2_000),
+            0
+        );
+    });
+}
+
+#[test]
+fn reserved_collateral_cannot_be_unbonded() {
+    new_test_ext().execute_with(|| {
+        let executor = 14;
+        let staker = 15;
+        let bond = 10_000;
+
+        drop(<Test as Config>::Currency::deposit_creating(
+            &executor, bond,
+        ));
+        drop(<Test as Config>::Currency::deposit_creating(&staker, bond));
+
+        assert_ok!(Executors::join_candidates(
+            Origin::signed(executor),
+            bond,
+            0
+        ));
+
+        assert_ok!(Executors::schedule_configure_executor(
+            Origin::signed(executor),
+            Percent::from_percent(10),
+            Percent::from_percent(50),
+        ));
+
+        assert_ok!(Executors::stake(
+            Origin::signed(staker),
+            executor,
+            bond,
+            0,
+            0
+        ));
+
+        assert_ok!(<Executors as ExecutorsExt<Test, Balance>>::reserve_bond(
+            &executor, 12_000
+        ));
+
+        assert_noop!(
+            Executors::schedule_candidate_bond_less(Origin::signed(executor), 1_000),
+            <Error<Test>>::CollateralStillReserved
+        );
+
+        assert_ok!(Executors::schedule_leave_candidates(
+            Origin::signed(executor),
+            1
+        ));
+        assert_ok!(Executors::schedule_leave_stakers(Origin::signed(staker)));
+
+        fast_forward_to((Executors::fixtures().leave_candidates_delay * DEFAULT_ROUND_TERM).into());
+
+        assert_noop!(
+            Executors::execute_leave_stakers(Origin::signed(staker), staker, 1),
+            <Error<Test>>::CollateralStillReserved
+        );
+        assert_noop!(
+            Executors::execute_leave_candidates(Origin::signed(executor), executor, 1),
+            <Error<Test>>::CollateralStillReserved
+        );
+
+        assert_eq!(
+            <Executors as ExecutorsExt<Test, Balance>>::unreserve_bond(&executor, 12_000),
+            12_000
+        );
+
+        assert_ok!(Executors::execute_leave_stakers(
+            Origin::signed(staker),
+            staker,
+            1
+        ));
+        assert_ok!(Executors::execute_leave_candidates(
+            Origin::signed(executor),
+            executor,
+            1
+        ));
+    });
+}
+
+#[test]
+fn round_transition_selects_active_set_and_pays_delayed_rewards_by_commission() {
+    new_test_ext().execute_with(|| {
+        let executor = 14;
+        let staker = 15;
+        let bond = 10_000;
+
+        drop(<Test as Config>::Currency::deposit_creating(
+            &executor, bond,
+        ));
+        drop(<Test as Config>::Currency::deposit_creating(&staker, bond));
+        drop(<Test as Config>::Currency::deposit_creating(
+            &TreasuryAccount::get(),
+            bond,
+        ));
+
+        assert_ok!(Executors::join_candidates(
+            Origin::signed(executor),
+            bond,
+            0
+        ));
+
+        assert_ok!(Executors::schedule_configure_executor(
+            Origin::signed(executor),
+            Percent::from_percent(10),
+            Percent::from_percent(10),
+        ));
+
+        assert_ok!(Executors::stake(
+            Origin::signed(staker),
+            executor,
+            bond,
+            0,
+            0
+        ));
+
+        fast_forward_to(2);
+
+        assert_eq!(Executors::last_round(), 1);
+        assert_eq!(Executors::active_set(), vec![executor]);
+        assert_eq!(Executors::staked(1), 2 * bond);
+        assert_eq!(
+            Executors::at_stake(1, executor),
+            ExecutorSnapshot {
+                bond,
+                stakes: vec![Bond {
+                    owner: staker,
+                    amount: bond,
+                }],
+                total: 2 * bond,
+            }
+        );
+
+        let total_issuance = Balances::total_issuance();
+
+        // round 1 rewards are due two rounds later, the treasury rounds last 20 blocks
+        fast_forward_to(42);
+
+        assert_eq!(Executors::last_round(), 3);
+        assert!(Executors::delayed_payouts(1).is_some());
+
+        // 1% of 20_000 staked: 10% commission, remainder split by bond and stake
+        assert_eq!(Balances::free_balance(executor), bond + 110);
+        assert_eq!(Balances::free_balance(staker), bond + 90);
+        // paid out from the treasury, not minted
+        assert_eq!(Balances::free_balance(TreasuryAccount::get()), bond - 200);
+
+        assert_last_event!(MockEvent::Executors(Event::Rewarded {
+            account: staker,
+            rewards: 90,
+        }));
+
+        fast_forward_to(43);
+
+        assert_eq!(Executors::delayed_payouts(1), None);
+        assert_eq!(Balances::free_balance(executor), bond + 110);
+        assert_eq!(Balances::total_issuance(), total_issuance);
+    });
+}
+
+#[test]
+fn staking_payouts_set_aside_the_rewards_still_due_for_earlier_rounds() {
+    new_test_ext().execute_with(|| {
+        drop(<Test as Config>::Currency::deposit_creating(
+            &TreasuryAccount::get(),
+            1_000,
+        ));
+        <DelayedPayouts<Test>>::insert(
+            1,
+            DelayedPayout {
+                round_issuance: 700,
+                total_staked: 70_000,
+                paid: 200,
+            },
+        );
+        <Staked<Test>>::insert(2, 100_000);
+
+        Executors::prepare_staking_payouts(4);
+
+        // 1% of the stake is 1_000, but 500 are still due for round 1 and 1 is the ED
+        assert_eq!(
+            Executors::delayed_payouts(2),
+            Some(DelayedPayout {
+                round_issuance: 499,
+                total_staked: 100_000,
+                paid: 0,
+            })
+        );
+    });
+}
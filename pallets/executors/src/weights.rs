@@ -1,4 +1,4 @@
-use frame_support::weights::Weight;
+use frame_support::weights::{constants::RocksDbWeight, Weight};
 use sp_std::marker::PhantomData;
 
 pub trait WeightInfo {
@@ -7,6 +7,9 @@ pub trait WeightInfo {
     #[rustfmt::skip]
     fn round_transition_on_initialize(x: u32, y: u32, ) -> Weight;
 
+    #[rustfmt::skip]
+    fn pay_one_executor_reward(y: u32, ) -> Weight;
+
     // #[rustfmt::skip]
     // fn set_fixtures() -> Weight;
     // #[rustfmt::skip]
@@ -58,8 +61,6 @@ pub trait WeightInfo {
     // #[rustfmt::skip]
     // fn cancel_staker_bond_less() -> Weight;
     // #[rustfmt::skip]
-    // fn pay_one_executor_reward(y: u32, ) -> Weight;
-    // #[rustfmt::skip]
     // fn cancel_stake_request() -> Weight;
     // #[rustfmt::skip]
     // fn execute_stake_request() -> Weight;
@@ -67,28 +68,69 @@ pub trait WeightInfo {
 
 pub struct TreasuryWeight<T>(PhantomData<T>);
 
-// TODO
 impl<T: frame_system::Config> WeightInfo for TreasuryWeight<T> {
-    #[rustfmt::skip]
+    // Storage: Executors LastRound (r:1 w:0)
+    // Storage: Treasury CurrentRound (r:1 w:0)
+    // Storage: Executors DelayedPayouts (r:1 w:0) per pending round, usually just one
     fn base_on_initialize() -> Weight {
-		419 as Weight //TODO
-	}
+        (5_000_000_u64).saturating_add(T::DbWeight::get().reads(3_u64))
+    }
 
-    #[rustfmt::skip]
-    fn round_transition_on_initialize(_x: u32, _y: u32, ) -> Weight {
-		419 as Weight //TODO
+    // Storage: Executors Fixtures (r:1 w:0)
+    // Storage: Executors CandidatePool (r:1 w:0)
+    // Storage: Executors CandidateInfo (r:1 w:0) per executor
+    // Storage: Executors TopStakes (r:1 w:0) per executor
+    // Storage: Executors ScheduledStakingRequests (r:1 w:0) per executor
+    // Storage: Executors AtStake (r:0 w:1) per executor
+    // Storage: Executors ActiveSet (r:0 w:1)
+    // Storage: Executors LastRound (r:0 w:1)
+    // Storage: Executors Staked (r:1 w:2)
+    // Storage: Executors DelayedPayouts (r:1 w:1) per pending round, usually just one
+    fn round_transition_on_initialize(x: u32, y: u32) -> Weight {
+        (40_000_000_u64)
+            .saturating_add((15_000_000_u64).saturating_mul(x as Weight))
+            .saturating_add((1_000_000_u64).saturating_mul(y as Weight))
+            .saturating_add(T::DbWeight::get().reads(4_u64))
+            .saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(x as Weight)))
+            .saturating_add(T::DbWeight::get().writes(5_u64))
+            .saturating_add(T::DbWeight::get().writes(x as Weight))
+    }
+
+    // Storage: Executors AtStake (r:1 w:1)
+    // Storage: Executors ExecutorConfig (r:1 w:0)
+    // Storage: System Account (r:1 w:1) per payee and the rewards account
+    // Storage: Executors DelayedPayouts (r:1 w:1) per payee
+    fn pay_one_executor_reward(y: u32) -> Weight {
+        (30_000_000_u64)
+            .saturating_add((20_000_000_u64).saturating_mul(y as Weight))
+            .saturating_add(T::DbWeight::get().reads(5_u64))
+            .saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(y as Weight)))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
+            .saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(y as Weight)))
     }
 }
 
-// TODO
 impl WeightInfo for () {
-    #[rustfmt::skip]
     fn base_on_initialize() -> Weight {
-		419 as Weight //TODO
-	}
+        (5_000_000_u64).saturating_add(RocksDbWeight::get().reads(3_u64))
+    }
 
-    #[rustfmt::skip]
-    fn round_transition_on_initialize(_x: u32, _y: u32, ) -> Weight {
-		419 as Weight //TODO
+    fn round_transition_on_initialize(x: u32, y: u32) -> Weight {
+        (40_000_000_u64)
+            .saturating_add((15_000_000_u64).saturating_mul(x as Weight))
+            .saturating_add((1_000_000_u64).saturating_mul(y as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4_u64))
+            .saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(x as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(5_u64))
+            .saturating_add(RocksDbWeight::get().writes(x as Weight))
+    }
+
+    fn pay_one_executor_reward(y: u32) -> Weight {
+        (30_000_000_u64)
+            .saturating_add((20_000_000_u64).saturating_mul(y as Weight))
+            .saturating_add(RocksDbWeight::get().reads(5_u64))
+            .saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(y as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(4_u64))
+            .saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(y as Weight)))
     }
 }
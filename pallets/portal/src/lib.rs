@@ -1,6 +1,8 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::sp_runtime::DispatchError;
+use frame_support::{
+    sp_runtime::DispatchError, storage::with_storage_layer, traits::Get, weights::Weight,
+};
 pub use pallet::*;
 
 #[cfg(test)]
@@ -8,7 +10,7 @@ mod tests;
 
 use sp_std::vec::Vec;
 use t3rn_primitives::{
-    portal::{Portal, RococoBridge},
+    portal::{KusamaBridge, PolkadotBridge, Portal, RococoBridge},
     xdns::Xdns,
     ChainId, GatewayVendor,
 };
@@ -19,12 +21,12 @@ pub mod weights;
 #[frame_support::pallet]
 pub mod pallet {
     use core::convert::TryInto;
-    use frame_support::pallet_prelude::*;
+    use frame_support::{pallet_prelude::*, storage::with_storage_layer};
     use frame_system::pallet_prelude::*;
     use sp_std::{vec, vec::Vec};
     use t3rn_primitives::{
         abi::GatewayABIConfig,
-        portal::RococoBridge,
+        portal::{KusamaBridge, PolkadotBridge, Portal, RococoBridge},
         xdns::{AllowedSideEffect, Xdns},
         ChainId, GatewayGenesisConfig, GatewaySysProps, GatewayType, GatewayVendor,
     };
@@ -32,7 +34,11 @@ pub mod pallet {
     /// Configure the pallet by specifying the parameters and types on which it depends.
     #[pallet::config]
     pub trait Config:
-        frame_system::Config + pallet_grandpa_finality_verifier::Config<RococoBridge>
+        frame_system::Config
+        + pallet_grandpa_finality_verifier::Config<RococoBridge>
+        + pallet_grandpa_finality_verifier::Config<PolkadotBridge>
+        + pallet_grandpa_finality_verifier::Config<KusamaBridge>
+        + pallet_ethereum_finality_verifier::Config
     {
         /// Because this pallet emits events, it depends on the runtime's definition of an event.
         type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
@@ -53,6 +59,8 @@ pub mod pallet {
         /// Event documentation should end with an array that provides descriptive names for event
         /// Gateway was registered successsfully. [ChainId]
         GatewayRegistered(ChainId),
+        /// Gateway was deregistered successsfully. [ChainId]
+        GatewayDeregistered(ChainId),
         /// Gateway owner was set successfully. [ChainId, Vec<u8>]
         SetOwner(ChainId, Vec<u8>),
         /// Gateway was set operational. [ChainId, bool]
@@ -70,6 +78,8 @@ pub mod pallet {
         UnimplementedGatewayVendor,
         /// Gateway registration failed
         RegistrationError,
+        /// Gateway deregistration failed
+        DeregistrationError,
         /// The gateways vendor is not available, which is a result of a missing XDNS record.
         GatewayVendorNotFound,
         /// Finality Verifier owner can't be set.
@@ -102,41 +112,73 @@ pub mod pallet {
             allowed_side_effects: Vec<AllowedSideEffect>,
             encoded_registration_data: Vec<u8>,
         ) -> DispatchResultWithPostInfo {
-            // ToDo xdns record is written also when the calls after this fail!!!
-            <T as Config>::Xdns::add_new_xdns_record(
-                origin.clone(),
-                url,
-                gateway_id,
-                None,
-                gateway_abi,
-                gateway_vendor.clone(),
-                gateway_type,
-                gateway_genesis,
-                gateway_sys_props,
-                vec![],
-                allowed_side_effects,
-            )?;
-
-            let res = match gateway_vendor {
-                GatewayVendor::Rococo =>
-                    pallet_grandpa_finality_verifier::Pallet::<T, RococoBridge>::initialize(
-                        origin,
-                        gateway_id,
-                        encoded_registration_data,
+            // the XDNS record and the verifier state are written together or not at all
+            with_storage_layer(|| {
+                <T as Config>::Xdns::add_new_xdns_record(
+                    origin.clone(),
+                    url,
+                    gateway_id,
+                    None,
+                    gateway_abi,
+                    gateway_vendor.clone(),
+                    gateway_type,
+                    gateway_genesis,
+                    gateway_sys_props,
+                    vec![],
+                    allowed_side_effects,
+                )?;
+
+                let res = match gateway_vendor {
+                    GatewayVendor::Rococo => pallet_grandpa_finality_verifier::Pallet::<
+                        T,
+                        RococoBridge,
+                    >::initialize(
+                        origin, gateway_id, encoded_registration_data
                     ),
-                _ => return Err(Error::<T>::UnimplementedGatewayVendor.into()),
-            };
+                    GatewayVendor::Polkadot => pallet_grandpa_finality_verifier::Pallet::<
+                        T,
+                        PolkadotBridge,
+                    >::initialize(
+                        origin, gateway_id, encoded_registration_data
+                    ),
+                    GatewayVendor::Kusama => pallet_grandpa_finality_verifier::Pallet::<
+                        T,
+                        KusamaBridge,
+                    >::initialize(
+                        origin, gateway_id, encoded_registration_data
+                    ),
+                    GatewayVendor::Ethereum =>
+                        pallet_ethereum_finality_verifier::Pallet::<T>::initialize(
+                            origin,
+                            gateway_id,
+                            encoded_registration_data,
+                        ),
+                };
 
-            match res {
-                Ok(_) => {
-                    Self::deposit_event(Event::GatewayRegistered(gateway_id));
-                    Ok(().into())
-                },
-                Err(msg) => {
-                    log::info!("{:?}", msg);
-                    Err(Error::<T>::RegistrationError.into())
-                },
-            }
+                match res {
+                    Ok(_) => {
+                        Self::deposit_event(Event::GatewayRegistered(gateway_id));
+                        Ok(().into())
+                    },
+                    Err(msg) => {
+                        log::info!("{:?}", msg);
+                        Err(Error::<T>::RegistrationError.into())
+                    },
+                }
+            })
+        }
+
+        /// Removes the verifier state and the XDNS record of the gateway.
+        ///
+        /// Ethereum gateways are cleared in bounded chunks, the XDNS record being removed once
+        /// the verifier state is gone, so the call may need to be repeated in later blocks.
+        #[pallet::weight(<Pallet<T> as Portal<T>>::deregister_gateway_weight())]
+        pub fn deregister_gateway(
+            origin: OriginFor<T>,
+            gateway_id: ChainId,
+        ) -> DispatchResultWithPostInfo {
+            <Self as Portal<T>>::deregister_gateway(origin, gateway_id)?;
+            Ok(().into())
         }
 
         #[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
@@ -154,7 +196,24 @@ pub mod pallet {
                         gateway_id,
                         encoded_new_owner.clone(),
                     ),
-                _ => unimplemented!(),
+                GatewayVendor::Polkadot => pallet_grandpa_finality_verifier::Pallet::<
+                    T,
+                    PolkadotBridge,
+                >::set_owner(
+                    origin, gateway_id, encoded_new_owner.clone()
+                ),
+                GatewayVendor::Kusama =>
+                    pallet_grandpa_finality_verifier::Pallet::<T, KusamaBridge>::set_owner(
+                        origin,
+                        gateway_id,
+                        encoded_new_owner.clone(),
+                    ),
+                GatewayVendor::Ethereum =>
+                    pallet_ethereum_finality_verifier::Pallet::<T>::set_owner(
+                        origin,
+                        gateway_id,
+                        encoded_new_owner.clone(),
+                    ),
             };
 
             match res {
@@ -182,7 +241,24 @@ pub mod pallet {
                         operational,
                         gateway_id,
                     ),
-                _ => unimplemented!(),
+                GatewayVendor::Polkadot => pallet_grandpa_finality_verifier::Pallet::<
+                    T,
+                    PolkadotBridge,
+                >::set_operational(
+                    origin, operational, gateway_id
+                ),
+                GatewayVendor::Kusama =>
+                    pallet_grandpa_finality_verifier::Pallet::<T, KusamaBridge>::set_operational(
+                        origin,
+                        operational,
+                        gateway_id,
+                    ),
+                GatewayVendor::Ethereum =>
+                    pallet_ethereum_finality_verifier::Pallet::<T>::set_operational(
+                        origin,
+                        operational,
+                        gateway_id,
+                    ),
             };
 
             match res {
@@ -212,7 +288,24 @@ pub mod pallet {
                         gateway_id,
                         encoded_header_data,
                     ),
-                _ => unimplemented!(), // ToDo remove once we remove the old vendors
+                GatewayVendor::Polkadot => pallet_grandpa_finality_verifier::Pallet::<
+                    T,
+                    PolkadotBridge,
+                >::submit_headers(
+                    origin, gateway_id, encoded_header_data
+                ),
+                GatewayVendor::Kusama =>
+                    pallet_grandpa_finality_verifier::Pallet::<T, KusamaBridge>::submit_headers(
+                        origin,
+                        gateway_id,
+                        encoded_header_data,
+                    ),
+                GatewayVendor::Ethereum =>
+                    pallet_ethereum_finality_verifier::Pallet::<T>::submit_headers(
+                        origin,
+                        gateway_id,
+                        encoded_header_data,
+                    ),
             };
 
             match res {
@@ -229,6 +322,28 @@ pub mod pallet {
     }
 }
 
+impl<T: Config> Pallet<T> {
+    /// Whether the gateway's finality verifier holds any state for it.
+    fn has_verifier_state(vendor: &GatewayVendor, gateway_id: ChainId) -> bool {
+        match vendor {
+            GatewayVendor::Rococo => pallet_grandpa_finality_verifier::BestFinalizedMap::<
+                T,
+                RococoBridge,
+            >::contains_key(gateway_id),
+            GatewayVendor::Polkadot => pallet_grandpa_finality_verifier::BestFinalizedMap::<
+                T,
+                PolkadotBridge,
+            >::contains_key(gateway_id),
+            GatewayVendor::Kusama => pallet_grandpa_finality_verifier::BestFinalizedMap::<
+                T,
+                KusamaBridge,
+            >::contains_key(gateway_id),
+            GatewayVendor::Ethereum =>
+                pallet_ethereum_finality_verifier::Pallet::<T>::has_state(gateway_id),
+        }
+    }
+}
+
 impl<T: Config> Portal<T> for Pallet<T> {
     fn get_latest_finalized_header(gateway_id: ChainId) -> Result<Option<Vec<u8>>, DispatchError> {
         let vendor = <T as Config>::Xdns::get_gateway_vendor(&gateway_id)
@@ -241,7 +356,22 @@ impl<T: Config> Portal<T> for Pallet<T> {
             >::get_latest_finalized_header(
                 gateway_id
             )),
-            _ => unimplemented!(),
+            GatewayVendor::Polkadot => Ok(pallet_grandpa_finality_verifier::Pallet::<
+                T,
+                PolkadotBridge,
+            >::get_latest_finalized_header(
+                gateway_id
+            )),
+            GatewayVendor::Kusama => Ok(pallet_grandpa_finality_verifier::Pallet::<
+                T,
+                KusamaBridge,
+            >::get_latest_finalized_header(
+                gateway_id
+            )),
+            GatewayVendor::Ethereum =>
+                Ok(pallet_ethereum_finality_verifier::Pallet::<T>::get_latest_finalized_header(
+                    gateway_id,
+                )),
         }
     }
 
@@ -251,7 +381,12 @@ impl<T: Config> Portal<T> for Pallet<T> {
 
         match vendor {
             GatewayVendor::Rococo => Ok(pallet_grandpa_finality_verifier::Pallet::<T, RococoBridge>::get_latest_finalized_height(gateway_id)),
-            _ => unimplemented!()
+            GatewayVendor::Polkadot => Ok(pallet_grandpa_finality_verifier::Pallet::<T, PolkadotBridge>::get_latest_finalized_height(gateway_id)),
+            GatewayVendor::Kusama => Ok(pallet_grandpa_finality_verifier::Pallet::<T, KusamaBridge>::get_latest_finalized_height(gateway_id)),
+            GatewayVendor::Ethereum =>
+                Ok(pallet_ethereum_finality_verifier::Pallet::<T>::get_latest_finalized_height(
+                    gateway_id,
+                )),
         }
     }
 
@@ -263,16 +398,171 @@ impl<T: Config> Portal<T> for Pallet<T> {
     ) -> Result<(Vec<Vec<u8>>, Vec<u8>), DispatchError> {
         let vendor = <T as Config>::Xdns::get_gateway_vendor(&gateway_id)
             .map_err(|_| Error::<T>::GatewayVendorNotFound)?;
+        let value_abi_unsigned_type =
+            <T as Config>::Xdns::get_gateway_value_unsigned_type(&gateway_id)?;
 
         match vendor {
             GatewayVendor::Rococo => pallet_grandpa_finality_verifier::Pallet::<T, RococoBridge>::confirm_and_decode_payload_params(
                 gateway_id,
                 encoded_inclusion_data,
                 submission_target_height,
-                <T as Config>::Xdns::get_gateway_value_unsigned_type_unsafe(&gateway_id).to_string_bytes(),
+                value_abi_unsigned_type.to_string_bytes(),
                 side_effect_id,
             ),
-            _ => unimplemented!()
+            GatewayVendor::Polkadot => pallet_grandpa_finality_verifier::Pallet::<T, PolkadotBridge>::confirm_and_decode_payload_params(
+                gateway_id,
+                encoded_inclusion_data,
+                submission_target_height,
+                value_abi_unsigned_type.to_string_bytes(),
+                side_effect_id,
+            ),
+            GatewayVendor::Kusama => pallet_grandpa_finality_verifier::Pallet::<T, KusamaBridge>::confirm_and_decode_payload_params(
+                gateway_id,
+                encoded_inclusion_data,
+                submission_target_height,
+                value_abi_unsigned_type.to_string_bytes(),
+                side_effect_id,
+            ),
+            GatewayVendor::Ethereum =>
+                pallet_ethereum_finality_verifier::Pallet::<T>::confirm_and_decode_payload_params(
+                    gateway_id,
+                    encoded_inclusion_data,
+                    submission_target_height,
+                    value_abi_unsigned_type.to_string_bytes(),
+                    side_effect_id,
+                ),
+        }
+    }
+
+    fn confirm_event_inclusion(
+        gateway_id: [u8; 4],
+        encoded_event: Vec<u8>,
+        submission_target_height: Vec<u8>,
+        inclusion_proof: Vec<Vec<u8>>,
+        encoded_block_hash: Vec<u8>,
+        side_effect_id: [u8; 4],
+    ) -> Result<(Vec<Vec<u8>>, Vec<u8>), DispatchError> {
+        let vendor = <T as Config>::Xdns::get_gateway_vendor(&gateway_id)
+            .map_err(|_| Error::<T>::GatewayVendorNotFound)?;
+        let value_abi_unsigned_type =
+            <T as Config>::Xdns::get_gateway_value_unsigned_type(&gateway_id)?;
+
+        match vendor {
+            GatewayVendor::Rococo =>
+                pallet_grandpa_finality_verifier::Pallet::<T, RococoBridge>::confirm_event_inclusion(
+                    gateway_id,
+                    encoded_event,
+                    submission_target_height,
+                    inclusion_proof,
+                    encoded_block_hash,
+                    value_abi_unsigned_type.to_string_bytes(),
+                    side_effect_id,
+                ),
+            GatewayVendor::Polkadot =>
+                pallet_grandpa_finality_verifier::Pallet::<T, PolkadotBridge>::confirm_event_inclusion(
+                    gateway_id,
+                    encoded_event,
+                    submission_target_height,
+                    inclusion_proof,
+                    encoded_block_hash,
+                    value_abi_unsigned_type.to_string_bytes(),
+                    side_effect_id,
+                ),
+            GatewayVendor::Kusama =>
+                pallet_grandpa_finality_verifier::Pallet::<T, KusamaBridge>::confirm_event_inclusion(
+                    gateway_id,
+                    encoded_event,
+                    submission_target_height,
+                    inclusion_proof,
+                    encoded_block_hash,
+                    value_abi_unsigned_type.to_string_bytes(),
+                    side_effect_id,
+                ),
+            GatewayVendor::Ethereum =>
+                pallet_ethereum_finality_verifier::Pallet::<T>::confirm_event_inclusion(
+                    gateway_id,
+                    encoded_event,
+                    submission_target_height,
+                    inclusion_proof,
+                    encoded_block_hash,
+                    value_abi_unsigned_type.to_string_bytes(),
+                    side_effect_id,
+                ),
         }
     }
+
+    fn deregister_gateway(origin: T::Origin, gateway_id: ChainId) -> Result<bool, DispatchError> {
+        let vendor = <T as Config>::Xdns::get_gateway_vendor(&gateway_id)
+            .map_err(|_| Error::<T>::GatewayVendorNotFound)?;
+
+        // the verifier state and the XDNS record are removed together or not at all
+        with_storage_layer(|| {
+            // records registered through XDNS alone have no verifier state to remove
+            let res = if !Self::has_verifier_state(&vendor, gateway_id) {
+                Ok(true)
+            } else {
+                match vendor {
+                    GatewayVendor::Rococo =>
+                        pallet_grandpa_finality_verifier::Pallet::<T, RococoBridge>::deinitialize(
+                            origin.clone(),
+                            gateway_id,
+                        )
+                        .map(|_| true),
+                    GatewayVendor::Polkadot =>
+                        pallet_grandpa_finality_verifier::Pallet::<T, PolkadotBridge>::deinitialize(
+                            origin.clone(),
+                            gateway_id,
+                        )
+                        .map(|_| true),
+                    GatewayVendor::Kusama =>
+                        pallet_grandpa_finality_verifier::Pallet::<T, KusamaBridge>::deinitialize(
+                            origin.clone(),
+                            gateway_id,
+                        )
+                        .map(|_| true),
+                    GatewayVendor::Ethereum =>
+                        pallet_ethereum_finality_verifier::Pallet::<T>::deinitialize(
+                            origin.clone(),
+                            gateway_id,
+                        ),
+                }
+            };
+
+            match res {
+                Ok(true) => {},
+                Ok(false) => return Ok(false),
+                Err(msg) => {
+                    log::info!("{:?}", msg);
+                    return Err(Error::<T>::DeregistrationError.into())
+                },
+            }
+
+            <T as Config>::Xdns::remove_xdns_record(origin, gateway_id)?;
+            Self::deposit_event(Event::GatewayDeregistered(gateway_id));
+            Ok(true)
+        })
+    }
+
+    /// The biggest ring buffer of the grandpa verifiers, with the initial header, or a full chunk
+    /// of the ethereum headers removal.
+    fn deregister_gateway_weight() -> Weight {
+        let grandpa_headers = [
+            <T as pallet_grandpa_finality_verifier::Config<RococoBridge>>::HeadersToStore::get(),
+            <T as pallet_grandpa_finality_verifier::Config<PolkadotBridge>>::HeadersToStore::get(),
+            <T as pallet_grandpa_finality_verifier::Config<KusamaBridge>>::HeadersToStore::get(),
+        ]
+        .iter()
+        .copied()
+        .max()
+        .unwrap_or_default()
+        .saturating_add(1);
+        // each grandpa header takes its hash, header and state root
+        let removed_keys = (grandpa_headers as u64)
+            .saturating_mul(3)
+            .max(pallet_ethereum_finality_verifier::MAX_KEYS_REMOVED_PER_DEINITIALIZATION as u64);
+
+        (10_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(removed_keys.saturating_add(12)))
+    }
 }
@@ -1,14 +1,22 @@
 //! Test utilities
 use circuit_mock_runtime::{ExtBuilder, *};
 use codec::{Decode, Encode};
-use frame_support::{assert_noop, assert_ok, dispatch::PostDispatchInfo};
+use frame_support::{
+    assert_noop, assert_ok,
+    dispatch::PostDispatchInfo,
+    traits::{Currency, ReservableCurrency},
+};
 use frame_system::pallet_prelude::OriginFor;
+use pallet_ethereum_finality_verifier::types::EthereumRegistrationData;
 use serde_json::Value;
+use snowbridge_ethereum::{difficulty::DifficultyConfig, Header as EthereumHeader};
 use sp_runtime::{DispatchError, DispatchErrorWithPostInfo};
 use std::fs;
 use t3rn_primitives::{
-    abi::GatewayABIConfig, xdns::AllowedSideEffect, ChainId, GatewayGenesisConfig, GatewaySysProps,
-    GatewayType, GatewayVendor,
+    abi::{CryptoAlgo, GatewayABIConfig, HasherAlgo},
+    portal::{KusamaBridge, PolkadotBridge, Portal as PortalT, RococoBridge},
+    xdns::AllowedSideEffect,
+    ChainId, GatewayGenesisConfig, GatewaySysProps, GatewayType, GatewayVendor,
 };
 
 fn register_file(
@@ -184,6 +192,91 @@ fn gateway_can_only_be_registered_once() {
     });
 }
 
+#[test]
+fn failed_verifier_initialization_reverts_xdns_record() {
+    ExtBuilder::default().build().execute_with(|| {
+        let raw_data = fs::read_to_string("./src/mock-data/1-register-roco.json").unwrap();
+        let json: Value = serde_json::from_str(raw_data.as_str()).unwrap();
+        let mut invalid = json[0].clone();
+        invalid["encoded_registration_data"] = Value::from("00");
+
+        assert_noop!(
+            register(Origin::root(), invalid, false),
+            pallet_portal::Error::<Runtime>::RegistrationError
+        );
+        assert!(pallet_xdns::XDNSRegistry::<Runtime>::get(*b"roco").is_none());
+        assert_ok!(register_file(
+            Origin::root(),
+            "1-register-roco.json",
+            true,
+            0
+        ));
+    });
+}
+
+#[test]
+fn deregisters_gateway_from_xdns_and_verifier() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(register_file(
+            Origin::root(),
+            "1-register-roco.json",
+            true,
+            0
+        ));
+        assert_noop!(
+            Portal::deregister_gateway(Origin::signed([1u8; 32].into()), *b"roco"),
+            pallet_portal::Error::<Runtime>::DeregistrationError
+        );
+
+        assert_ok!(Portal::deregister_gateway(Origin::root(), *b"roco"));
+
+        assert!(pallet_xdns::XDNSRegistry::<Runtime>::get(*b"roco").is_none());
+        assert_eq!(
+            pallet_grandpa_finality_verifier::Pallet::<Runtime, RococoBridge>::get_latest_finalized_header(*b"roco"),
+            None
+        );
+        assert_noop!(
+            Portal::deregister_gateway(Origin::root(), *b"roco"),
+            pallet_portal::Error::<Runtime>::GatewayVendorNotFound
+        );
+        assert_ok!(register_file(
+            Origin::root(),
+            "1-register-roco.json",
+            true,
+            0
+        ));
+    });
+}
+
+#[test]
+fn slashing_xdns_record_deregisters_gateway_from_verifier() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(register_file(
+            Origin::root(),
+            "1-register-roco.json",
+            true,
+            0
+        ));
+        // bond the gateway's record as if it was registered by ALICE
+        let _ = Balances::deposit_creating(&ALICE, 1_000);
+        assert_ok!(Balances::reserve(&ALICE, 100));
+        pallet_xdns::XDNSRegistry::<Runtime>::mutate(*b"roco", |record| {
+            record.as_mut().unwrap().assign_registrant(ALICE)
+        });
+        pallet_xdns::RegistrationDeposits::<Runtime>::insert(*b"roco", 100);
+
+        assert_ok!(XDNS::slash_xdns_record(Origin::root(), *b"roco"));
+
+        assert!(pallet_xdns::XDNSRegistry::<Runtime>::get(*b"roco").is_none());
+        assert_eq!(
+            pallet_grandpa_finality_verifier::Pallet::<Runtime, RococoBridge>::get_latest_finalized_header(*b"roco"),
+            None
+        );
+        assert_eq!(Balances::reserved_balance(&ALICE), 0);
+        assert_eq!(Balances::free_balance(&ALICE), 900);
+    });
+}
+
 #[test]
 fn cant_submit_without_registering() {
     let origin = Origin::root();
@@ -324,3 +417,78 @@ fn can_be_set_operational() {
         assert_ok!(submit_header_file(origin, "2-headers-roco.json", 0));
     });
 }
+
+#[test]
+fn register_ethereum_successfully() {
+    ExtBuilder::default().build().execute_with(|| {
+        let genesis = EthereumHeader {
+            number: 10,
+            difficulty: 1.into(),
+            ..Default::default()
+        };
+        let registration_data = EthereumRegistrationData::<AccountId> {
+            initial_headers: vec![genesis.clone()],
+            initial_difficulty: 1.into(),
+            descendants_until_final: 12,
+            difficulty_config: DifficultyConfig::mainnet(),
+            terminal_total_difficulty: None,
+            owner: AccountId::new([1u8; 32]),
+        };
+
+        assert_ok!(Portal::register_gateway(
+            Origin::root(),
+            b"eth".to_vec(),
+            *b"eth2",
+            GatewayABIConfig {
+                hasher: HasherAlgo::Keccak256,
+                crypto: CryptoAlgo::Ecdsa,
+                address_length: 20,
+                ..Default::default()
+            },
+            GatewayVendor::Ethereum,
+            GatewayType::TxOnly(0),
+            Default::default(),
+            GatewaySysProps {
+                ss58_format: 0,
+                token_symbol: b"ETH".to_vec(),
+                token_decimals: 18,
+            },
+            vec![*b"mult", *b"cevm"],
+            registration_data.encode(),
+        ));
+        assert_eq!(
+            <Portal as PortalT<Runtime>>::get_latest_finalized_header(*b"eth2"),
+            Ok(Some(genesis.compute_hash().encode()))
+        );
+        assert_eq!(
+            <Portal as PortalT<Runtime>>::get_latest_finalized_height(*b"eth2"),
+            Ok(Some(10u64.encode()))
+        );
+        assert_ok!(Portal::set_operational(Origin::root(), *b"eth2", false));
+    });
+}
+
+#[test]
+fn registers_polkadot_and_kusama_in_their_own_verifier_instances() {
+    ExtBuilder::default().build().execute_with(|| {
+        let raw_data = fs::read_to_string("./src/mock-data/1-register-roco.json").unwrap();
+        let json: Value = serde_json::from_str(raw_data.as_str()).unwrap();
+
+        let mut polkadot = json[0].clone();
+        polkadot["encoded_gateway_vendor"] = Value::from(hex::encode(GatewayVendor::Polkadot.encode()));
+        assert_ok!(register(Origin::root(), polkadot, true));
+
+        assert!(pallet_grandpa_finality_verifier::Pallet::<Runtime, PolkadotBridge>::get_latest_finalized_header(*b"roco").is_some());
+        assert!(pallet_grandpa_finality_verifier::Pallet::<Runtime, KusamaBridge>::get_latest_finalized_header(*b"roco").is_none());
+        assert!(pallet_grandpa_finality_verifier::Pallet::<Runtime, RococoBridge>::get_latest_finalized_header(*b"roco").is_none());
+        assert_eq!(
+            <Portal as PortalT<Runtime>>::get_latest_finalized_header(*b"roco"),
+            Ok(pallet_grandpa_finality_verifier::Pallet::<Runtime, PolkadotBridge>::get_latest_finalized_header(*b"roco"))
+        );
+        assert_ok!(Portal::set_operational(Origin::root(), *b"roco", false));
+        assert_noop!(
+            submit_header_file(Origin::signed([0u8; 32].into()), "2-headers-roco.json", 0),
+            pallet_portal::Error::<Runtime>::SubmitHeaderError
+        );
+    });
+}
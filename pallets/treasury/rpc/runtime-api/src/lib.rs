@@ -0,0 +1,20 @@
+//! Runtime API definition for querying the issuance of pallet Treasury.
+//!
+//! This API should be imported and implemented by the runtime,
+//! of a node that wants to show the tokens issued per round.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+pub use t3rn_primitives::common::RoundIndex;
+
+sp_api::decl_runtime_apis! {
+    /// The API to query the issuance history of pallet Treasury
+    pub trait TreasuryRuntimeApi<Balance> where
+        Balance: Codec,
+    {
+        /// Returns the tokens issued per past round within the given range of rounds
+        fn get_issuance_history(from: RoundIndex, to: RoundIndex) -> Vec<(RoundIndex, Balance)>;
+    }
+}
@@ -33,7 +33,7 @@ pub mod pallet {
     use t3rn_primitives::{
         common::{Range, RoundIndex, RoundInfo, BLOCKS_PER_YEAR},
         monetary::{BeneficiaryRole, InflationAllocation},
-        treasury::Treasury as TTreasury,
+        treasury::{StakeSource as TStakeSource, Treasury as TTreasury},
     };
 
     pub type BalanceOf<T> =
@@ -82,6 +82,9 @@ pub mod pallet {
         #[pallet::constant]
         type InflationRegressionMonths: Get<u32>;
 
+        /// Total stake of executors and collators the round issuance is computed from.
+        type StakeSource: TStakeSource<BalanceOf<Self>>;
+
         type WeightInfo: WeightInfo;
     }
 
@@ -106,6 +109,12 @@ pub mod pallet {
     /// Active means member of the respective active set.
     pub type TotalStakeExpectation<T: Config> = StorageValue<_, Range<BalanceOf<T>>, ValueQuery>;
 
+    #[pallet::storage]
+    #[pallet::getter(fn round_issuance)]
+    /// Tokens issued per past round.
+    pub type RoundIssuance<T: Config> =
+        StorageMap<_, Twox64Concat, RoundIndex, BalanceOf<T>, OptionQuery>;
+
     #[pallet::storage]
     #[pallet::getter(fn beneficiaries)]
     pub type Beneficiaries<T: Config> = StorageDoubleMap<
@@ -173,6 +182,7 @@ pub mod pallet {
         RoundTermTooShort,
         NotBeneficiary,
         NoRewardsAvailable,
+        RoundAlreadyIssued,
     }
 
     #[pallet::hooks]
@@ -191,21 +201,29 @@ pub mod pallet {
                 });
 
                 // issue tokens for the past round
-                //TODO: pull sum of active executors + collators stake
-                let total_stake: BalanceOf<T> = Self::u32_to_balance(50_u32);
+                let total_stake = T::StakeSource::total_stake();
                 let round_issuance = Self::compute_round_issuance(total_stake);
 
-                Self::mint_for_round(T::Origin::root(), round.index - 1, round_issuance)
-                    //TODO: panic possibility
-                    .expect("mint for round; will not fail unless called without root");
+                if let Err(err) =
+                    Self::mint_for_round(T::Origin::root(), round.index - 1, round_issuance)
+                {
+                    log::error!(
+                        "failed to mint for round {:?} -- {:?}",
+                        round.index - 1,
+                        err
+                    );
+                }
 
                 // adjust annual inflation gradually
                 let mut inflation_info = <InflationConfig<T>>::get();
                 let regressed_annual_inflation = Self::compute_regressed_annual_inflation();
-                inflation_info.update_from_annual::<T>(regressed_annual_inflation)
-                  //TODO: panic possibility
-                  .expect("update round from annual inflation; will not fail if annual inflation at genesis was valid");
-                <InflationConfig<T>>::put(inflation_info);
+                match inflation_info.update_from_annual::<T>(regressed_annual_inflation) {
+                    Ok(()) => <InflationConfig<T>>::put(inflation_info),
+                    Err(err) => log::error!(
+                        "failed to regress annual inflation, keeping the current one -- {:?}",
+                        err
+                    ),
+                }
             }
 
             T::WeightInfo::on_initialize()
@@ -289,8 +307,7 @@ pub mod pallet {
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
-        /// Mints tokens for given round.
-        ///TODO: maybe ensure can only be called once per round
+        /// Mints tokens for given round, at most once per round.
         ///TODO: exec, infl
         #[pallet::weight(10_000)] //TODO
         pub fn mint_for_round(
@@ -299,6 +316,10 @@ pub mod pallet {
             #[pallet::compact] amount: BalanceOf<T>, //TODO: revisit
         ) -> DispatchResult {
             ensure_root(origin)?;
+            ensure!(
+                !<RoundIssuance<T>>::contains_key(round_index),
+                Error::<T>::RoundAlreadyIssued
+            );
 
             let inflation_alloc = <InflationAlloc<T>>::get();
 
@@ -346,6 +367,8 @@ pub mod pallet {
                 Self::deposit_event(Event::BeneficiaryTokensIssued(candidate, issued));
             }
 
+            <RoundIssuance<T>>::insert(round_index, amount);
+
             Self::deposit_event(Event::RoundTokensIssued(round_index, amount));
 
             Ok(())
@@ -538,6 +561,20 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Returns the tokens issued per round within the given range of past rounds.
+        pub fn get_issuance_history(
+            from: RoundIndex,
+            to: RoundIndex,
+        ) -> Vec<(RoundIndex, BalanceOf<T>)> {
+            let to = to.min(<CurrentRound<T>>::get().index);
+
+            (from..=to)
+                .filter_map(|round_index| {
+                    <RoundIssuance<T>>::get(round_index).map(|issued| (round_index, issued))
+                })
+                .collect()
+        }
+
         /// Computes round issuance based on total staked for the given round
         /// Total stake consists of executors' and collators' total stake.
         pub(crate) fn compute_round_issuance(total_stake: BalanceOf<T>) -> BalanceOf<T> {
@@ -12,6 +12,7 @@ use sp_runtime::{
 use t3rn_primitives::{
     common::{Range, DEFAULT_ROUND_TERM},
     monetary::InflationAllocation,
+    treasury::StakeSource,
 };
 
 pub(crate) fn last_event() -> Event {
@@ -149,10 +150,21 @@ impl pallet_treasury::Config for Test {
     type InflationRegressionMonths = InflationRegressionMonths;
     type MinRoundTerm = MinRoundTerm;
     type ReserveAccount = ReserveAccount;
+    type StakeSource = StakeSourceMock;
     type TreasuryAccount = TreasuryAccount;
     type WeightInfo = ();
 }
 
+pub(crate) const TOTAL_STAKE: Balance = 50;
+
+pub struct StakeSourceMock;
+
+impl StakeSource<Balance> for StakeSourceMock {
+    fn total_stake() -> Balance {
+        TOTAL_STAKE
+    }
+}
+
 pub(crate) fn fast_forward_to(n: u64) {
     while System::block_number() < n {
         Treasury::on_finalize(System::block_number());
@@ -130,6 +130,38 @@ fn mint_for_round_splits_total_rewards_correctly_amongst_actors() {
     })
 }
 
+#[test]
+fn mint_for_round_fails_for_already_issued_round() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Treasury::mint_for_round(Origin::root(), 1, 10));
+
+        assert_noop!(
+            Treasury::mint_for_round(Origin::root(), 1, 10),
+            Error::<Test>::RoundAlreadyIssued
+        );
+    })
+}
+
+#[test]
+fn hook_records_issuance_computed_from_stake_source() {
+    new_test_ext().execute_with(|| {
+        <CurrentRound<Test>>::put(RoundInfo {
+            index: 1_u32,
+            term: <DefaultRoundTerm>::get(),
+            head: 1,
+        });
+
+        let first_round_issuance = Treasury::compute_round_issuance(TOTAL_STAKE);
+        fast_forward_to((<DefaultRoundTerm>::get() + 1) as u64);
+
+        assert_eq!(Treasury::round_issuance(1), Some(first_round_issuance));
+        assert_eq!(
+            Treasury::get_issuance_history(0, 10),
+            vec![(1, first_round_issuance)]
+        );
+    })
+}
+
 #[test]
 fn claim_rewards_fails_if_not_beneficiary() {
     new_test_ext().execute_with(|| {
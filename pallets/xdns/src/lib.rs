@@ -7,7 +7,7 @@
 #![allow(clippy::type_complexity)]
 #![allow(clippy::too_many_arguments)]
 use codec::Encode;
-use sp_runtime::traits::Hash;
+use sp_runtime::traits::{Hash, Saturating, Zero};
 use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 pub use t3rn_primitives::{
     abi::{GatewayABIConfig, Type},
@@ -39,13 +39,15 @@ pub mod pallet {
         pallet_prelude::*,
         traits::{
             fungible::{Inspect, Mutate},
-            Time,
+            ReservableCurrency, Time,
         },
     };
     use frame_system::pallet_prelude::*;
     use sp_std::convert::TryInto;
     use t3rn_primitives::{
+        portal::Portal,
         side_effect::interface::SideEffectInterface,
+        transfers::EscrowedBalanceOf,
         xdns::{AllowedSideEffect, Parachain, Xdns, XdnsRecord},
         Bytes, ChainId, EscrowTrait, GatewaySysProps, GatewayType, GatewayVendor,
     };
@@ -63,6 +65,23 @@ pub mod pallet {
 
         /// A type that manages escrow, and therefore balances
         type Escrowed: EscrowTrait<Self>;
+
+        /// Deposit reserved from a registrant for each xdns_record it owns
+        #[pallet::constant]
+        type RegistrationDeposit: Get<EscrowedBalanceOf<Self, Self::Escrowed>>;
+
+        /// Blocks it takes for transactions on Substrate gateways to be finalized and relayed
+        /// to Circuit. Parachains wait for it twice, as their blocks are finalized by the relay.
+        #[pallet::constant]
+        type SubstrateFinalityDelay: Get<Self::BlockNumber>;
+
+        /// Blocks it takes for transactions on Ethereum gateways to be finalized and relayed
+        /// to Circuit.
+        #[pallet::constant]
+        type EthereumFinalityDelay: Get<Self::BlockNumber>;
+
+        /// A type that deregisters gateways, removing their finality verifier state
+        type Portal: Portal<Self>;
     }
 
     // Simple declaration of the `Pallet` type. It is placeholder we use to implement traits and
@@ -104,6 +123,109 @@ pub mod pallet {
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
+        /// Registers a new xdns_record, owned by the signer in exchange for a deposit.
+        #[pallet::weight(< T as Config >::WeightInfo::add_new_xdns_record())]
+        pub fn register_xdns_record(
+            origin: OriginFor<T>,
+            url: Vec<u8>,
+            gateway_id: ChainId,
+            parachain: Option<Parachain>,
+            gateway_abi: GatewayABIConfig,
+            gateway_vendor: GatewayVendor,
+            gateway_type: GatewayType,
+            gateway_genesis: GatewayGenesisConfig,
+            gateway_sys_props: GatewaySysProps,
+            security_coordinates: Vec<u8>,
+            allowed_side_effects: Vec<AllowedSideEffect>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin.clone())?;
+
+            <Self as Xdns<T>>::add_new_xdns_record(
+                origin,
+                url,
+                gateway_id,
+                parachain,
+                gateway_abi,
+                gateway_vendor,
+                gateway_type,
+                gateway_genesis,
+                gateway_sys_props,
+                security_coordinates,
+                allowed_side_effects,
+            )?;
+
+            Ok(().into())
+        }
+
+        /// Updates the url, ABI or allowed side effects of an xdns_record. Registrant only access.
+        #[pallet::weight(< T as Config >::WeightInfo::update_xdns_record())]
+        pub fn update_xdns_record(
+            origin: OriginFor<T>,
+            gateway_id: ChainId,
+            url: Option<Vec<u8>>,
+            gateway_abi: Option<GatewayABIConfig>,
+            allowed_side_effects: Option<Vec<AllowedSideEffect>>,
+        ) -> DispatchResultWithPostInfo {
+            let registrant = ensure_signed(origin)?;
+
+            <XDNSRegistry<T>>::try_mutate(gateway_id, |maybe_record| -> DispatchResult {
+                let record = maybe_record.as_mut().ok_or(Error::<T>::UnknownXdnsRecord)?;
+                ensure!(
+                    record.registrant.as_ref() == Some(&registrant),
+                    Error::<T>::NotXdnsRecordRegistrant
+                );
+
+                if let Some(url) = url {
+                    record.url = url;
+                }
+                if let Some(gateway_abi) = gateway_abi {
+                    record.gateway_abi = gateway_abi;
+                }
+                if let Some(allowed_side_effects) = allowed_side_effects {
+                    record.allowed_side_effects = allowed_side_effects;
+                }
+                Self::ensure_valid_record(&record.gateway_abi, &record.allowed_side_effects)
+            })?;
+
+            Self::deposit_event(Event::<T>::XdnsRecordUpdated(gateway_id));
+            Ok(().into())
+        }
+
+        /// Removes a bad xdns_record from the onchain registry together with the gateway's verifier state, slashing the registrant's deposit. Root only access.
+        ///
+        /// Ethereum gateways are deregistered in bounded chunks, so once the deposit is slashed the
+        /// rest of the gateway is removed with the portal's `deregister_gateway`.
+        #[pallet::weight(
+            < T as Config >::WeightInfo::slash_xdns_record()
+                .saturating_add(T::Portal::deregister_gateway_weight())
+        )]
+        pub fn slash_xdns_record(
+            origin: OriginFor<T>,
+            gateway_id: ChainId,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin.clone())?;
+
+            let registrant = <XDNSRegistry<T>>::get(gateway_id)
+                .ok_or(Error::<T>::UnknownXdnsRecord)?
+                .registrant
+                .ok_or(Error::<T>::XdnsRecordNotBonded)?;
+            let deposit = <RegistrationDeposits<T>>::take(gateway_id)
+                .filter(|deposit| !deposit.is_zero())
+                .ok_or(Error::<T>::NoDepositToSlash)?;
+
+            let (_, not_slashed) =
+                <T::Escrowed as EscrowTrait<T>>::Currency::slash_reserved(&registrant, deposit);
+            T::Portal::deregister_gateway(origin, gateway_id)?;
+
+            let slashed = deposit.saturating_sub(not_slashed);
+            if !slashed.is_zero() {
+                Self::deposit_event(Event::<T>::XdnsRecordSlashed(
+                    registrant, gateway_id, slashed,
+                ));
+            }
+            Ok(().into())
+        }
+
         #[pallet::weight(< T as Config >::WeightInfo::add_new_xdns_record())]
         pub fn add_side_effect(
             origin: OriginFor<T>,
@@ -153,18 +275,45 @@ pub mod pallet {
             Self::update_gateway_ttl(gateway_id, last_finalized)
         }
 
-        /// Removes a xdns_record from the onchain registry. Root only access.
+        /// Records how many blocks it takes for the gateway's transactions to become final, as seen by Circuit, overriding the delay of its vendor. Root only access.
+        #[pallet::weight(< T as Config >::WeightInfo::update_ttl())]
+        pub fn set_finality_delay(
+            origin: OriginFor<T>,
+            gateway_id: ChainId,
+            finality_delay: T::BlockNumber,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            if !<XDNSRegistry<T>>::contains_key(gateway_id) {
+                return Err(Error::<T>::UnknownXdnsRecord.into())
+            }
+
+            <FinalityDelays<T>>::insert(gateway_id, finality_delay);
+            Self::deposit_event(Event::<T>::FinalityDelayUpdated(gateway_id, finality_delay));
+            Ok(().into())
+        }
+
+        /// Removes a xdns_record from the onchain registry, refunding the registrant's deposit. Root or registrant only access.
         #[pallet::weight(< T as Config >::WeightInfo::purge_xdns_record())]
         pub fn purge_xdns_record(
             origin: OriginFor<T>,
             requester: T::AccountId,
             xdns_record_id: [u8; 4],
         ) -> DispatchResultWithPostInfo {
-            ensure_root(origin)?;
+            match ensure_signed(origin.clone()) {
+                Ok(signer) => {
+                    let record = <XDNSRegistry<T>>::get(xdns_record_id)
+                        .ok_or(Error::<T>::UnknownXdnsRecord)?;
+                    ensure!(
+                        record.registrant.as_ref() == Some(&signer),
+                        Error::<T>::NotXdnsRecordRegistrant
+                    );
+                },
+                Err(_) => ensure_root(origin)?,
+            }
             if !<XDNSRegistry<T>>::contains_key(xdns_record_id) {
                 Err(Error::<T>::UnknownXdnsRecord.into())
             } else {
-                <XDNSRegistry<T>>::remove(xdns_record_id);
+                Self::purge_record(xdns_record_id);
                 Self::deposit_event(Event::<T>::XdnsRecordPurged(requester, xdns_record_id));
                 Ok(().into())
             }
@@ -180,6 +329,10 @@ pub mod pallet {
         XdnsRecordPurged(T::AccountId, [u8; 4]),
         /// \[xdns_record_id\]
         XdnsRecordUpdated([u8; 4]),
+        /// \[xdns_record_id, finality_delay\]
+        FinalityDelayUpdated([u8; 4], T::BlockNumber),
+        /// \[registrant, xdns_record_id, slashed_deposit\]
+        XdnsRecordSlashed(T::AccountId, [u8; 4], EscrowedBalanceOf<T, T::Escrowed>),
     }
 
     // Errors inform users that something went wrong.
@@ -197,6 +350,16 @@ pub mod pallet {
         SideEffectInterfaceNotFound,
         /// the xdns entry does not contain parachain information
         NoParachainInfoFound,
+        /// Only the registrant of the xdns_record can update it
+        NotXdnsRecordRegistrant,
+        /// The xdns_record has no registrant with a deposit to slash
+        XdnsRecordNotBonded,
+        /// The sizes of values, hashes or addresses of the gateway ABI can't be decoded
+        UnsupportedGatewayABI,
+        /// The allowed side effect has no interface stored
+        UnknownAllowedSideEffect,
+        /// No deposit is held for the xdns_record, as it was already slashed
+        NoDepositToSlash,
     }
 
     #[pallet::storage]
@@ -212,6 +375,18 @@ pub mod pallet {
     pub type XDNSRegistry<T: Config> =
         StorageMap<_, Identity, [u8; 4], XdnsRecord<T::AccountId>, OptionQuery>;
 
+    /// Deposits reserved from the registrants of xdns_records, returned on purge.
+    #[pallet::storage]
+    #[pallet::getter(fn registration_deposit)]
+    pub type RegistrationDeposits<T: Config> =
+        StorageMap<_, Identity, [u8; 4], EscrowedBalanceOf<T, T::Escrowed>, OptionQuery>;
+
+    /// Finality delays set by root, overriding the ones derived from the gateway vendor.
+    #[pallet::storage]
+    #[pallet::getter(fn finality_delay)]
+    pub type FinalityDelays<T: Config> =
+        StorageMap<_, Identity, [u8; 4], T::BlockNumber, OptionQuery>;
+
     // The genesis config type.
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
@@ -269,7 +444,14 @@ pub mod pallet {
             security_coordinates: Vec<u8>,
             allowed_side_effects: Vec<AllowedSideEffect>,
         ) -> DispatchResult {
-            ensure_root(origin)?;
+            // Root registers records on its own, anyone else owns the record for a deposit
+            let maybe_registrant = match ensure_signed(origin.clone()) {
+                Ok(registrant) => Some(registrant),
+                Err(_) => {
+                    ensure_root(origin)?;
+                    None
+                },
+            };
 
             // early exit if record already exists in storage
             if <XDNSRegistry<T>>::contains_key(gateway_id) {
@@ -290,18 +472,37 @@ pub mod pallet {
                 allowed_side_effects,
             );
 
-            // ToDo: Uncomment when switching into a model with open registration. Sudo access for now.
-            // xdns_record.assign_registrant(registrant.clone());
             let now =
                 TryInto::<u64>::try_into(<<T as Config>::Escrowed as EscrowTrait<T>>::Time::now())
                     .map_err(|_| "Unable to compute current timestamp")?;
 
+            if let Some(registrant) = maybe_registrant {
+                Self::ensure_valid_record(
+                    &xdns_record.gateway_abi,
+                    &xdns_record.allowed_side_effects,
+                )?;
+                let deposit = T::RegistrationDeposit::get();
+                <T::Escrowed as EscrowTrait<T>>::Currency::reserve(&registrant, deposit)?;
+                <RegistrationDeposits<T>>::insert(gateway_id, deposit);
+                xdns_record.assign_registrant(registrant);
+            }
+
             xdns_record.set_last_finalized(now);
             <XDNSRegistry<T>>::insert(gateway_id, xdns_record);
             Self::deposit_event(Event::<T>::XdnsRecordStored(gateway_id));
             Ok(())
         }
 
+        fn remove_xdns_record(origin: OriginFor<T>, gateway_id: ChainId) -> DispatchResult {
+            ensure_root(origin)?;
+            if !<XDNSRegistry<T>>::contains_key(gateway_id) {
+                return Err(Error::<T>::UnknownXdnsRecord.into())
+            }
+
+            Self::purge_record(gateway_id);
+            Ok(())
+        }
+
         /// returns a mapping of all allowed side_effects of a gateway.
         fn allowed_side_effects(
             gateway_id: &ChainId,
@@ -371,10 +572,21 @@ pub mod pallet {
                     .unwrap()
                     .gateway_abi
                     .value_type_size
-                    * 8,
+                    .saturating_mul(8),
             )
         }
 
+        fn get_gateway_value_unsigned_type(chain_id: &ChainId) -> Result<Type, DispatchError> {
+            let gateway_abi = <XDNSRegistry<T>>::get(chain_id)
+                .ok_or(Error::<T>::XdnsRecordNotFound)?
+                .gateway_abi;
+            ensure!(
+                gateway_abi.is_supported(),
+                Error::<T>::UnsupportedGatewayABI
+            );
+            Ok(Type::Uint(gateway_abi.value_type_size * 8))
+        }
+
         /// returns the gateway vendor of a gateway if its available
         fn get_gateway_vendor(chain_id: &ChainId) -> Result<GatewayVendor, DispatchError> {
             match <XDNSRegistry<T>>::get(chain_id) {
@@ -403,5 +615,61 @@ pub mod pallet {
         fn get_gateway_type_unsafe(chain_id: &ChainId) -> GatewayType {
             <XDNSRegistry<T>>::get(chain_id).unwrap().gateway_type
         }
+
+        fn get_gateway_finality_delay(chain_id: &ChainId) -> T::BlockNumber {
+            if let Some(finality_delay) = <FinalityDelays<T>>::get(chain_id) {
+                return finality_delay
+            }
+
+            match <XDNSRegistry<T>>::get(chain_id) {
+                Some(record) => match record.gateway_vendor {
+                    GatewayVendor::Ethereum => T::EthereumFinalityDelay::get(),
+                    GatewayVendor::Polkadot | GatewayVendor::Kusama | GatewayVendor::Rococo
+                        if record.parachain.is_some() =>
+                        T::SubstrateFinalityDelay::get()
+                            .saturating_add(T::SubstrateFinalityDelay::get()),
+                    GatewayVendor::Polkadot | GatewayVendor::Kusama | GatewayVendor::Rococo =>
+                        T::SubstrateFinalityDelay::get(),
+                },
+                None => Zero::zero(),
+            }
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Ensures the ABI of a registrant's record can be decoded and its allowed side effects
+        /// are known.
+        fn ensure_valid_record(
+            gateway_abi: &GatewayABIConfig,
+            allowed_side_effects: &[AllowedSideEffect],
+        ) -> DispatchResult {
+            ensure!(
+                gateway_abi.is_supported(),
+                Error::<T>::UnsupportedGatewayABI
+            );
+            for side_effect in allowed_side_effects {
+                ensure!(
+                    <StandardSideEffects<T>>::contains_key(side_effect)
+                        || <CustomSideEffects<T>>::contains_key(T::Hashing::hash(
+                            &side_effect.encode()
+                        )),
+                    Error::<T>::UnknownAllowedSideEffect
+                );
+            }
+            Ok(())
+        }
+
+        /// Removes the record of the gateway, returning the deposit to its registrant
+        fn purge_record(gateway_id: ChainId) {
+            let maybe_registrant = <XDNSRegistry<T>>::take(gateway_id).and_then(|r| r.registrant);
+            <FinalityDelays<T>>::remove(gateway_id);
+
+            if let (Some(registrant), Some(deposit)) = (
+                maybe_registrant,
+                <RegistrationDeposits<T>>::take(gateway_id),
+            ) {
+                <T::Escrowed as EscrowTrait<T>>::Currency::unreserve(&registrant, deposit);
+            }
+        }
     }
 }
@@ -20,7 +20,11 @@
 use super::*;
 use circuit_mock_runtime::{ExtBuilder, *};
 use codec::Decode;
-use frame_support::{assert_err, assert_noop, assert_ok};
+use frame_support::{
+    assert_err, assert_noop, assert_ok,
+    dispatch::DispatchResultWithPostInfo,
+    traits::{Currency, Get},
+};
 use frame_system::Origin;
 use sp_runtime::DispatchError;
 use t3rn_primitives::{abi::Type, xdns::Xdns, GatewayType, GatewayVendor};
@@ -277,7 +281,7 @@ fn should_error_trying_to_purge_a_missing_xdns_record() {
 }
 
 #[test]
-fn should_error_trying_to_purge_an_xdns_record_if_not_root() {
+fn should_error_trying_to_purge_an_xdns_record_if_not_root_or_registrant() {
     ExtBuilder::default()
         .with_standard_side_effects()
         .with_default_xdns_records()
@@ -285,7 +289,7 @@ fn should_error_trying_to_purge_an_xdns_record_if_not_root() {
         .execute_with(|| {
             assert_noop!(
                 XDNS::purge_xdns_record(Origin::<Runtime>::Signed(ALICE).into(), ALICE, *b"gate"),
-                DispatchError::BadOrigin
+                pallet_xdns::Error::<Runtime>::NotXdnsRecordRegistrant
             );
             assert_eq!(
                 pallet_xdns::XDNSRegistry::<Runtime>::iter().count(),
@@ -340,6 +344,84 @@ fn should_error_when_trying_to_update_ttl_as_non_root() {
     });
 }
 
+#[test]
+fn should_set_finality_delay_for_a_known_xdns_record() {
+    ExtBuilder::default()
+        .with_standard_side_effects()
+        .with_default_xdns_records()
+        .build()
+        .execute_with(|| {
+            assert_eq!(XDNS::get_gateway_finality_delay(b"gate"), 0);
+
+            assert_ok!(XDNS::set_finality_delay(
+                Origin::<Runtime>::Root.into(),
+                *b"gate",
+                64
+            ));
+            assert_eq!(XDNS::get_gateway_finality_delay(b"gate"), 64);
+
+            assert_ok!(XDNS::purge_xdns_record(
+                Origin::<Runtime>::Root.into(),
+                ALICE,
+                *b"gate"
+            ));
+            assert_eq!(XDNS::get_gateway_finality_delay(b"gate"), 0);
+        });
+}
+
+#[test]
+fn should_derive_finality_delay_from_the_gateway_vendor() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(XDNS::add_new_xdns_record(
+            Origin::<Runtime>::Root.into(),
+            b"some_url".to_vec(),
+            *b"eth2",
+            None,
+            Default::default(),
+            GatewayVendor::Ethereum,
+            GatewayType::TxOnly(0),
+            Default::default(),
+            Default::default(),
+            vec![],
+            vec![],
+        ));
+        assert_eq!(
+            XDNS::get_gateway_finality_delay(b"eth2"),
+            <Runtime as pallet_xdns::Config>::EthereumFinalityDelay::get()
+        );
+
+        assert_ok!(XDNS::set_finality_delay(
+            Origin::<Runtime>::Root.into(),
+            *b"eth2",
+            64
+        ));
+        assert_eq!(XDNS::get_gateway_finality_delay(b"eth2"), 64);
+    });
+}
+
+#[test]
+fn should_error_when_trying_to_set_finality_delay_for_a_missing_xdns_record() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            XDNS::set_finality_delay(Origin::<Runtime>::Root.into(), *b"miss", 64),
+            pallet_xdns::pallet::Error::<Runtime>::UnknownXdnsRecord
+        );
+    });
+}
+
+#[test]
+fn should_error_when_trying_to_set_finality_delay_as_non_root() {
+    ExtBuilder::default()
+        .with_default_xdns_records()
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                XDNS::set_finality_delay(Origin::<Runtime>::Signed(ALICE).into(), *b"gate", 64),
+                DispatchError::BadOrigin
+            );
+        });
+}
+
 #[test]
 fn should_contain_gateway_system_properties() {
     ExtBuilder::default()
@@ -410,3 +492,206 @@ fn gate_gateway_vendor_returns_vendor_for_known_record() {
             assert_ok!(actual, GatewayVendor::Rococo);
         });
 }
+
+fn register_test_record_as(registrant: AccountId) -> DispatchResultWithPostInfo {
+    XDNS::register_xdns_record(
+        Origin::<Runtime>::Signed(registrant).into(),
+        b"some_url".to_vec(),
+        *b"test",
+        None,
+        Default::default(),
+        GatewayVendor::Rococo,
+        GatewayType::TxOnly(0),
+        Default::default(),
+        Default::default(),
+        vec![],
+        vec![],
+    )
+}
+
+#[test]
+fn should_register_a_xdns_record_for_a_deposit_returned_on_purge() {
+    ExtBuilder::default().build().execute_with(|| {
+        let _ = Balances::deposit_creating(&ALICE, 1_000);
+
+        assert_ok!(register_test_record_as(ALICE));
+
+        assert_eq!(
+            pallet_xdns::XDNSRegistry::<Runtime>::get(b"test")
+                .unwrap()
+                .registrant,
+            Some(ALICE)
+        );
+        assert_eq!(Balances::reserved_balance(&ALICE), 100);
+        assert_eq!(XDNS::registration_deposit(b"test"), Some(100));
+
+        assert_ok!(XDNS::purge_xdns_record(
+            Origin::<Runtime>::Root.into(),
+            ALICE,
+            *b"test"
+        ));
+
+        assert_eq!(Balances::reserved_balance(&ALICE), 0);
+        assert_eq!(Balances::free_balance(&ALICE), 1_000);
+        assert_eq!(XDNS::registration_deposit(b"test"), None);
+    });
+}
+
+#[test]
+fn should_let_the_registrant_purge_its_xdns_record_for_the_deposit() {
+    ExtBuilder::default().build().execute_with(|| {
+        let _ = Balances::deposit_creating(&ALICE, 1_000);
+        assert_ok!(register_test_record_as(ALICE));
+
+        assert_noop!(
+            XDNS::purge_xdns_record(Origin::<Runtime>::Signed(BOB).into(), BOB, *b"test"),
+            pallet_xdns::Error::<Runtime>::NotXdnsRecordRegistrant
+        );
+
+        assert_ok!(XDNS::purge_xdns_record(
+            Origin::<Runtime>::Signed(ALICE).into(),
+            ALICE,
+            *b"test"
+        ));
+
+        assert!(pallet_xdns::XDNSRegistry::<Runtime>::get(b"test").is_none());
+        assert_eq!(Balances::reserved_balance(&ALICE), 0);
+        assert_eq!(Balances::free_balance(&ALICE), 1_000);
+    });
+}
+
+#[test]
+fn should_only_let_the_registrant_update_its_xdns_record() {
+    ExtBuilder::default()
+        .with_standard_side_effects()
+        .build()
+        .execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, 1_000);
+            assert_ok!(register_test_record_as(ALICE));
+
+            assert_noop!(
+                XDNS::update_xdns_record(
+                    Origin::<Runtime>::Signed(BOB).into(),
+                    *b"test",
+                    Some(b"other_url".to_vec()),
+                    None,
+                    None,
+                ),
+                pallet_xdns::Error::<Runtime>::NotXdnsRecordRegistrant
+            );
+
+            assert_ok!(XDNS::update_xdns_record(
+                Origin::<Runtime>::Signed(ALICE).into(),
+                *b"test",
+                Some(b"other_url".to_vec()),
+                None,
+                Some(vec![*b"tran"]),
+            ));
+
+            let record = pallet_xdns::XDNSRegistry::<Runtime>::get(b"test").unwrap();
+            assert_eq!(record.url, b"other_url".to_vec());
+            assert_eq!(record.allowed_side_effects, vec![*b"tran"]);
+        });
+}
+
+#[test]
+fn should_reject_registrant_records_with_unsupported_abi_or_unknown_side_effects() {
+    ExtBuilder::default()
+        .with_standard_side_effects()
+        .build()
+        .execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, 1_000);
+            let unsupported_abi = GatewayABIConfig {
+                value_type_size: 32,
+                ..Default::default()
+            };
+
+            assert_noop!(
+                XDNS::register_xdns_record(
+                    Origin::<Runtime>::Signed(ALICE).into(),
+                    b"some_url".to_vec(),
+                    *b"test",
+                    None,
+                    unsupported_abi.clone(),
+                    GatewayVendor::Rococo,
+                    GatewayType::TxOnly(0),
+                    Default::default(),
+                    Default::default(),
+                    vec![],
+                    vec![],
+                ),
+                pallet_xdns::Error::<Runtime>::UnsupportedGatewayABI
+            );
+
+            assert_ok!(register_test_record_as(ALICE));
+
+            assert_noop!(
+                XDNS::update_xdns_record(
+                    Origin::<Runtime>::Signed(ALICE).into(),
+                    *b"test",
+                    None,
+                    Some(unsupported_abi),
+                    None,
+                ),
+                pallet_xdns::Error::<Runtime>::UnsupportedGatewayABI
+            );
+            assert_noop!(
+                XDNS::update_xdns_record(
+                    Origin::<Runtime>::Signed(ALICE).into(),
+                    *b"test",
+                    None,
+                    None,
+                    Some(vec![*b"none"]),
+                ),
+                pallet_xdns::Error::<Runtime>::UnknownAllowedSideEffect
+            );
+        });
+}
+
+#[test]
+fn should_slash_the_deposit_of_a_challenged_xdns_record() {
+    ExtBuilder::default()
+        .with_default_xdns_records()
+        .build()
+        .execute_with(|| {
+            let _ = Balances::deposit_creating(&ALICE, 1_000);
+            assert_ok!(register_test_record_as(ALICE));
+
+            assert_noop!(
+                XDNS::slash_xdns_record(Origin::<Runtime>::Signed(ALICE).into(), *b"test"),
+                DispatchError::BadOrigin
+            );
+            assert_noop!(
+                XDNS::slash_xdns_record(Origin::<Runtime>::Root.into(), *b"gate"),
+                pallet_xdns::Error::<Runtime>::XdnsRecordNotBonded
+            );
+
+            assert_ok!(XDNS::slash_xdns_record(
+                Origin::<Runtime>::Root.into(),
+                *b"test"
+            ));
+
+            assert!(pallet_xdns::XDNSRegistry::<Runtime>::get(b"test").is_none());
+            assert_eq!(Balances::reserved_balance(&ALICE), 0);
+            assert_eq!(Balances::free_balance(&ALICE), 900);
+            assert!(System::events().iter().any(|record| record.event
+                == circuit_mock_runtime::Event::XDNS(pallet_xdns::Event::XdnsRecordSlashed(
+                    ALICE, *b"test", 100
+                ))));
+        });
+}
+
+#[test]
+fn should_not_slash_a_xdns_record_twice() {
+    ExtBuilder::default().build().execute_with(|| {
+        let _ = Balances::deposit_creating(&ALICE, 1_000);
+        assert_ok!(register_test_record_as(ALICE));
+        // the deposit was slashed, while the gateway is still being deregistered
+        pallet_xdns::RegistrationDeposits::<Runtime>::remove(*b"test");
+
+        assert_noop!(
+            XDNS::slash_xdns_record(Origin::<Runtime>::Root.into(), *b"test"),
+            pallet_xdns::Error::<Runtime>::NoDepositToSlash
+        );
+    });
+}
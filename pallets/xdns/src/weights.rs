@@ -40,6 +40,8 @@ pub trait WeightInfo {
     fn add_new_xdns_record() -> Weight;
     fn update_ttl() -> Weight;
     fn purge_xdns_record() -> Weight;
+    fn update_xdns_record() -> Weight;
+    fn slash_xdns_record() -> Weight;
     fn best_available() -> Weight;
 }
 
@@ -64,6 +66,20 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().writes(1_u64))
     }
 
+    // Not benchmarked, estimated by its storage accesses
+    fn update_xdns_record() -> Weight {
+        10_000_u64
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Not benchmarked, estimated by its storage accesses without the gateway deregistration
+    fn slash_xdns_record() -> Weight {
+        10_000_u64
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+
     fn best_available() -> Weight {
         25_265_000_u64.saturating_add(T::DbWeight::get().reads(1_u64))
     }
@@ -89,6 +105,20 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().writes(1_u64))
     }
 
+    // Not benchmarked, estimated by its storage accesses
+    fn update_xdns_record() -> Weight {
+        10_000_u64
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    // Not benchmarked, estimated by its storage accesses without the gateway deregistration
+    fn slash_xdns_record() -> Weight {
+        10_000_u64
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+
     fn best_available() -> Weight {
         25_265_000_u64.saturating_add(RocksDbWeight::get().reads(1_u64))
     }
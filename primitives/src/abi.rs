@@ -98,6 +98,15 @@ impl Default for GatewayABIConfig {
     }
 }
 
+impl GatewayABIConfig {
+    /// Whether the sizes of values, hashes and addresses are ones the ABI can decode.
+    pub fn is_supported(&self) -> bool {
+        matches!(self.value_type_size, 4 | 8 | 16)
+            && matches!(self.hash_size, 16 | 32)
+            && matches!(self.address_length, 20 | 32)
+    }
+}
+
 #[derive(PartialEq, Clone, Encode, Decode, Eq, Hash, Debug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct ContractActionDesc<Hash, TargetId, AccountId> {
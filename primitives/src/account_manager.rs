@@ -20,6 +20,8 @@ pub struct Settlement<Account, Balance> {
     pub outcome: Outcome,
     pub source: BenefitSource,
     pub role: CircuitRole,
+    /// Version of the split policy the settlement was calculated with, 0 for the default policy
+    pub policy_version: u32,
 }
 
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
@@ -39,6 +41,65 @@ pub enum Outcome {
     Commit,
 }
 
+/// Percentages of the charge fee returned to the payee, settled for the recipient
+/// and kept by the escrow account to keep the account manager alive.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct OutcomeSplit {
+    pub payee: u8,
+    pub recipient: u8,
+    pub escrow: u8,
+}
+
+impl OutcomeSplit {
+    pub fn is_valid(&self) -> bool {
+        self.payee as u16 + self.recipient as u16 + self.escrow as u16 == 100
+    }
+}
+
+/// Splits of the charge fee per outcome of the charge
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct SplitPolicy {
+    pub commit: OutcomeSplit,
+    pub revert: OutcomeSplit,
+    pub unexpected_failure: OutcomeSplit,
+}
+
+impl SplitPolicy {
+    pub fn split(&self, outcome: &Outcome) -> &OutcomeSplit {
+        match outcome {
+            Outcome::Commit => &self.commit,
+            Outcome::Revert => &self.revert,
+            Outcome::UnexpectedFailure => &self.unexpected_failure,
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.commit.is_valid() && self.revert.is_valid() && self.unexpected_failure.is_valid()
+    }
+}
+
+impl Default for SplitPolicy {
+    fn default() -> Self {
+        SplitPolicy {
+            commit: OutcomeSplit {
+                payee: 0,
+                recipient: 99,
+                escrow: 1,
+            },
+            revert: OutcomeSplit {
+                payee: 89,
+                recipient: 10,
+                escrow: 1,
+            },
+            unexpected_failure: OutcomeSplit {
+                payee: 49,
+                recipient: 50,
+                escrow: 1,
+            },
+        }
+    }
+}
+
 pub trait AccountManager<Account, Balance, Hash, BlockNumber> {
     /// Lookup charge by Id and fail if not found
     fn get_charge_or_fail(
@@ -58,6 +119,8 @@ pub trait AccountManager<Account, Balance, Hash, BlockNumber> {
         role: CircuitRole,
         recipient: Option<Account>,
     ) -> DispatchResult;
+    /// Lower the reward offered by a pending charge, refunding the difference to the payee
+    fn reduce_offered_reward(charge_id: Hash, offered_reward: Balance) -> DispatchResult;
     /// Finalize a transaction, with an optional reason for failures
     fn finalize(
         charge_id: Hash,
@@ -29,7 +29,7 @@ pub struct HeaderId {
     pub hash: H256,
 }
 
-const EMPTY_OMMERS_HASH: [u8; 32] =
+pub const EMPTY_OMMERS_HASH: [u8; 32] =
     hex!("1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347");
 
 /// An Ethereum block header.
@@ -70,6 +70,15 @@ pub struct Header {
 
     // Base fee per gas (EIP-1559), only in headers from the London hardfork onwards.
     pub base_fee: Option<U256>,
+
+    // Withdrawals root (EIP-4895), only in headers from the Shanghai hardfork onwards.
+    pub withdrawals_root: Option<H256>,
+
+    // Blob gas used and excess blob gas (EIP-4844), and the parent beacon block root (EIP-4788),
+    // only in headers from the Cancun hardfork onwards.
+    pub blob_gas_used: Option<u64>,
+    pub excess_blob_gas: Option<u64>,
+    pub parent_beacon_block_root: Option<H256>,
 }
 
 impl Header {
@@ -153,7 +162,17 @@ impl Header {
     fn rlp(&self, with_seal: bool) -> Bytes {
         let mut s = RlpStream::new();
 
-        let stream_length_without_seal = if self.base_fee.is_some() { 14 } else { 13 };
+        let stream_length_without_seal = 13
+            + [
+                self.base_fee.is_some(),
+                self.withdrawals_root.is_some(),
+                self.blob_gas_used.is_some(),
+                self.excess_blob_gas.is_some(),
+                self.parent_beacon_block_root.is_some(),
+            ]
+            .iter()
+            .filter(|present| **present)
+            .count();
 
         if with_seal {
             s.begin_list(stream_length_without_seal + self.seal.len());
@@ -184,6 +203,18 @@ impl Header {
         if let Some(base_fee) = self.base_fee {
             s.append(&base_fee);
         }
+        if let Some(withdrawals_root) = self.withdrawals_root {
+            s.append(&withdrawals_root);
+        }
+        if let Some(blob_gas_used) = self.blob_gas_used {
+            s.append(&blob_gas_used);
+        }
+        if let Some(excess_blob_gas) = self.excess_blob_gas {
+            s.append(&excess_blob_gas);
+        }
+        if let Some(parent_beacon_block_root) = self.parent_beacon_block_root {
+            s.append(&parent_beacon_block_root);
+        }
 
         s.out().to_vec()
     }
@@ -278,6 +309,10 @@ mod tests {
                 vec
             }],
             base_fee: None,
+            withdrawals_root: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            parent_beacon_block_root: None,
         };
         assert_eq!(
             header.compute_hash().as_bytes(),
@@ -317,6 +352,10 @@ mod tests {
                 rlp::encode(&nonce).to_vec(),
             ],
             base_fee: None,
+            withdrawals_root: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            parent_beacon_block_root: None,
         };
         assert_eq!(
             header.compute_hash().as_bytes(),
@@ -1,3 +1,4 @@
+use crate::common::RoundIndex;
 use codec::{Decode, Encode};
 
 use scale_info::TypeInfo;
@@ -32,3 +33,11 @@ pub struct ClaimableArtifacts<Account, Balance> {
     pub total_round_claim: Balance,
     pub benefit_source: BenefitSource,
 }
+
+/// Amount accrued by a beneficiary from the benefit source in the round, yet to be claimed
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct PendingClaim<Balance> {
+    pub round: RoundIndex,
+    pub benefit_source: BenefitSource,
+    pub amount: Balance,
+}
@@ -14,7 +14,7 @@ pub const STAKER_LOCK_ID: LockIdentifier = *b"stkrstkl";
 
 #[derive(Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct NominatedStake<Account, Balance> {
-    executor: Account,
+    pub executor: Account,
     pub staker: Account,
     pub nominated_stake: Balance,
 }
@@ -23,9 +23,10 @@ pub trait Executors<T: frame_system::Config, Balance> {
     fn active_set() -> Vec<T::AccountId>;
     fn reserve_bond(executor: &T::AccountId, amount: Balance) -> Result<Balance, DispatchError>;
     fn unreserve_bond(executor: &T::AccountId, amount: Balance) -> Balance;
-    fn slash_bond(executor: &T::AccountId, amount: Balance) -> Balance;
+    fn slash_bond(executor: &T::AccountId, amount: Balance, beneficiary: &T::AccountId) -> Balance;
     fn increase_bond(executor: &T::AccountId, amount: Balance) -> Balance;
     fn collateral_bond(executor: &T::AccountId) -> Balance;
+    fn min_candidate_bond() -> Balance;
     fn total_nominated_stake(executor: &T::AccountId) -> Balance;
     fn stakes(staker: &T::AccountId) -> Vec<NominatedStake<T::AccountId, Balance>>;
     fn stakes_per_executor(executor: &T::AccountId) -> Vec<NominatedStake<T::AccountId, Balance>>;
@@ -45,6 +46,10 @@ impl<T: frame_system::Config, Balance: Zero> Executors<T, Balance> for Executors
         Zero::zero()
     }
 
+    fn min_candidate_bond() -> Balance {
+        Zero::zero()
+    }
+
     fn reserve_bond(_executor: &T::AccountId, _amount: Balance) -> Result<Balance, DispatchError> {
         Ok(Zero::zero())
     }
@@ -53,7 +58,11 @@ impl<T: frame_system::Config, Balance: Zero> Executors<T, Balance> for Executors
         Zero::zero()
     }
 
-    fn slash_bond(_executor: &T::AccountId, _amount: Balance) -> Balance {
+    fn slash_bond(
+        _executor: &T::AccountId,
+        _amount: Balance,
+        _beneficiary: &T::AccountId,
+    ) -> Balance {
         Zero::zero()
     }
 
@@ -142,6 +151,17 @@ impl<A, B: Default> Default for ExecutorSnapshot<A, B> {
     }
 }
 
+/// Staking rewards of a round pending payout.
+#[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct DelayedPayout<Balance> {
+    /// Total round reward to be paid out to executors and their stakers.
+    pub round_issuance: Balance,
+    /// Total stake of the round's active set the reward is split by.
+    pub total_staked: Balance,
+    /// Part of the round reward transferred to executors and stakers so far.
+    pub paid: Balance,
+}
+
 /// Generic type describing either an executor's self-bond or a staker's bond.
 // #[derive(Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[derive(Clone, Encode, Decode, RuntimeDebug, TypeInfo, PartialEq)]
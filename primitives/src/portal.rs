@@ -5,6 +5,7 @@ use crate::{
     abi::GatewayABIConfig, xdns::AllowedSideEffect, ChainId, GatewayGenesisConfig, GatewaySysProps,
     GatewayType, GatewayVendor,
 };
+use frame_support::weights::Weight;
 use scale_info::TypeInfo;
 use sp_runtime::DispatchError;
 use sp_std::vec::Vec;
@@ -32,15 +33,39 @@ pub struct RegistrationData {
 }
 
 pub type RococoBridge = ();
+pub type PolkadotBridge = frame_support::instances::Instance1;
+pub type KusamaBridge = frame_support::instances::Instance2;
 
 pub trait Portal<T: frame_system::Config> {
     fn get_latest_finalized_header(chain_id: ChainId) -> Result<Option<Vec<u8>>, DispatchError>;
     fn get_latest_finalized_height(chain_id: ChainId) -> Result<Option<Vec<u8>>, DispatchError>;
 
+    /// Verifies the inclusion of the event carried by the encoded inclusion data and decodes the
+    /// SideEffect params from it. Every verifier encodes the inclusion data as the SCALE encoded
+    /// tuple `(encoded_event, inclusion_proof, block_hash)`, i.e. the arguments of
+    /// `confirm_event_inclusion` in the same order.
     fn confirm_and_decode_payload_params(
         gateway_id: [u8; 4],
         submission_target_height: Vec<u8>,
         encoded_inclusion_data: Vec<u8>,
         side_effect_id: [u8; 4],
     ) -> Result<(Vec<Vec<u8>>, Vec<u8>), DispatchError>;
+
+    /// Verifies that the encoded event is included in the block with the given hash, using the
+    /// header stored by the gateway's finality verifier, and decodes the SideEffect params from it.
+    fn confirm_event_inclusion(
+        gateway_id: [u8; 4],
+        encoded_event: Vec<u8>,
+        submission_target_height: Vec<u8>,
+        inclusion_proof: Vec<Vec<u8>>,
+        encoded_block_hash: Vec<u8>,
+        side_effect_id: [u8; 4],
+    ) -> Result<(Vec<Vec<u8>>, Vec<u8>), DispatchError>;
+
+    /// Removes the verifier state and the XDNS record of the gateway. Returns false while the
+    /// verifier state is still being cleared, in which case the XDNS record is kept.
+    fn deregister_gateway(origin: T::Origin, gateway_id: ChainId) -> Result<bool, DispatchError>;
+
+    /// Worst case weight of `deregister_gateway`
+    fn deregister_gateway_weight() -> Weight;
 }
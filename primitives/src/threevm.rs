@@ -5,6 +5,7 @@ use crate::{
     contracts_registry::{AuthorInfo, RegistryContract},
 };
 use codec::{Decode, Encode};
+use frame_support::weights::Weight;
 use sp_runtime::{DispatchError, DispatchResult};
 use sp_std::{result::Result, vec::Vec};
 use t3rn_sdk_primitives::{
@@ -171,3 +172,15 @@ pub trait ModuleOperations<T: frame_system::Config, Balance> {
     fn get_type(&self) -> &ContractType;
     fn set_type(&mut self, kind: ContractType);
 }
+
+/// Calls into contracts deployed on the 3VM contracts pallet on behalf of an account.
+pub trait ContractsCall<AccountId, Balance> {
+    fn call(
+        origin: AccountId,
+        dest: AccountId,
+        value: Balance,
+        gas_limit: Weight,
+        storage_deposit_limit: Option<Balance>,
+        data: Vec<u8>,
+    ) -> DispatchResult;
+}
@@ -1,5 +1,25 @@
 use crate::common::RoundInfo;
+use sp_runtime::traits::{Saturating, Zero};
 
 pub trait Treasury<T: frame_system::Config> {
     fn current_round() -> RoundInfo<T::BlockNumber>;
 }
+
+/// Source of the stake the round issuance is computed from
+pub trait StakeSource<Balance> {
+    fn total_stake() -> Balance;
+}
+
+impl<Balance: Zero> StakeSource<Balance> for () {
+    fn total_stake() -> Balance {
+        Zero::zero()
+    }
+}
+
+impl<Balance: Saturating, A: StakeSource<Balance>, B: StakeSource<Balance>> StakeSource<Balance>
+    for (A, B)
+{
+    fn total_stake() -> Balance {
+        A::total_stake().saturating_add(B::total_stake())
+    }
+}
@@ -169,6 +169,9 @@ pub trait Xdns<T: frame_system::Config> {
         allowed_side_effects: Vec<AllowedSideEffect>,
     ) -> DispatchResult;
 
+    /// Removes the record of the gateway. Root only access.
+    fn remove_xdns_record(origin: OriginFor<T>, gateway_id: ChainId) -> DispatchResult;
+
     fn allowed_side_effects(gateway_id: &ChainId)
         -> BTreeMap<[u8; 4], Box<dyn SideEffectProtocol>>;
 
@@ -182,6 +185,9 @@ pub trait Xdns<T: frame_system::Config> {
 
     fn get_gateway_value_unsigned_type_unsafe(chain_id: &ChainId) -> Type;
 
+    /// Unsigned type of the gateway's values, failing for value sizes the ABI can't decode.
+    fn get_gateway_value_unsigned_type(chain_id: &ChainId) -> Result<Type, DispatchError>;
+
     fn get_gateway_type_unsafe(chain_id: &ChainId) -> GatewayType;
 
     fn get_gateway_vendor(chain_id: &ChainId) -> Result<GatewayVendor, DispatchError>;
@@ -189,4 +195,7 @@ pub trait Xdns<T: frame_system::Config> {
     fn get_gateway_security_coordinates(chain_id: &ChainId) -> Result<Vec<u8>, DispatchError>;
 
     fn get_gateway_para_id(chain_id: &ChainId) -> Result<u32, DispatchError>;
+
+    /// Blocks it takes for transactions on the gateway to become final, zero if not recorded
+    fn get_gateway_finality_delay(chain_id: &ChainId) -> T::BlockNumber;
 }
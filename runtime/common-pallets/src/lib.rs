@@ -19,6 +19,8 @@ pub use pallet_account_manager;
 pub use pallet_circuit;
 pub use pallet_clock;
 pub use pallet_contracts_registry;
+pub use pallet_ethereum_finality_verifier;
+pub use pallet_executors;
 pub use pallet_grandpa_finality_verifier;
 pub use pallet_portal;
 pub use pallet_treasury;
@@ -1,6 +1,10 @@
 use crate::*;
 
-use frame_support::{parameter_types, traits::ConstU32, PalletId};
+use frame_support::{
+    parameter_types,
+    traits::{ConstU32, ConstU64},
+    PalletId,
+};
 use pallet_grandpa_finality_verifier::bridges::runtime as bp_runtime;
 use sp_core::H256;
 use t3rn_primitives::common::DEFAULT_ROUND_TERM;
@@ -15,15 +19,39 @@ impl t3rn_primitives::EscrowTrait<Runtime> for Runtime {
 impl pallet_clock::Config for Runtime {
     type AccountManager = AccountManager;
     type Event = Event;
-    type Executors = t3rn_primitives::executors::ExecutorsMock<Self>;
+    type Executors = Executors;
     type RoundDuration = ConstU32<500u32>;
     type Treasury = Treasury;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const StakingRewardRate: Perbill = Perbill::from_parts(100_000); // 0.01% per round
+}
+
+impl pallet_executors::Config for Runtime {
+    type Currency = Balances;
+    type Event = Event;
+    type RewardPaymentDelay = ConstU32<2u32>;
+    type RewardsAccount = TreasuryAccount;
+    type StakingRewardRate = StakingRewardRate;
+    type Treasury = Treasury;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const XdnsRegistrationDeposit: Balance = 100;
 }
 
 impl pallet_xdns::Config for Runtime {
     type Balances = Balances;
     type Escrowed = Self;
+    // ~10 minutes, for PoW confirmations and header relaying
+    type EthereumFinalityDelay = ConstU32<100u32>;
     type Event = Event;
+    type Portal = Portal;
+    type RegistrationDeposit = XdnsRegistrationDeposit;
+    type SubstrateFinalityDelay = ConstU32<0u32>;
     type WeightInfo = pallet_xdns::weights::SubstrateWeight<Runtime>;
 }
 
@@ -55,18 +83,55 @@ parameter_types! {
     pub const SelfGatewayId: [u8; 4] = [3, 3, 3, 3];
 }
 
+/// Escrowed `call` side effects invoke the 3VM contracts from the Circuit's escrow account.
+pub struct CircuitContractsCall;
+impl t3rn_primitives::threevm::ContractsCall<AccountId, Balance> for CircuitContractsCall {
+    fn call(
+        origin: AccountId,
+        dest: AccountId,
+        value: Balance,
+        gas_limit: frame_support::weights::Weight,
+        storage_deposit_limit: Option<Balance>,
+        data: Vec<u8>,
+    ) -> sp_runtime::DispatchResult {
+        let outcome = Contracts::bare_call(
+            origin,
+            dest,
+            value,
+            gas_limit,
+            storage_deposit_limit,
+            data,
+            false,
+        );
+        match outcome.result {
+            Ok(exec_return) if exec_return.did_revert() =>
+                Err("Escrowed contract call reverted".into()),
+            Ok(_) => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+}
+
 impl pallet_circuit::Config for Runtime {
     type AccountManager = AccountManager;
+    type Assets = Assets;
     type Balances = Balances;
+    type BootstrapRelayers = ();
     type Call = Call;
+    type Contracts = CircuitContractsCall;
     type DeletionQueueLimit = ConstU32<100u32>;
     type Escrowed = Self;
+    type EscrowedCallGasLimit = ConstU64<10_000_000_000u64>;
     type Event = Event;
-    type Executors = t3rn_primitives::executors::ExecutorsMock<Self>;
+    type Executors = Executors;
+    type MaxSideEffectsPerXtx = ConstU32<32u32>;
+    type MisbehaviourChallengeWindow = ConstU32<10u32>;
     type Portal = Portal;
     type SelfAccountId = CircuitAccountId;
     type SelfGatewayId = SelfGatewayId;
     type SelfParaId = ConstU32<3333u32>;
+    type SideEffectBiddingPeriod = ConstU32<3u32>;
+    type SideEffectBondingGracePeriod = ConstU32<3u32>;
     type SignalQueueDepth = ConstU32<5u32>;
     type WeightInfo = ();
     type XBIPortal = XBIPortalRuntimeEntry;
@@ -74,6 +139,8 @@ impl pallet_circuit::Config for Runtime {
     type Xdns = XDNS;
     type XtxTimeoutCheckInterval = ConstU32<10u32>;
     type XtxTimeoutDefault = ConstU32<400u32>;
+    type XtxTimeoutMax = ConstU32<14400u32>;
+    type XtxTimeoutMin = ConstU32<10u32>;
 }
 
 parameter_types! {
@@ -97,6 +164,50 @@ impl pallet_grandpa_finality_verifier::Config<RococoBridgeInstance> for Runtime
     type WeightInfo = ();
 }
 
+type PolkadotBridgeInstance = t3rn_primitives::portal::PolkadotBridge;
+
+#[derive(Debug)]
+pub struct PolkadotLikeChain;
+impl bp_runtime::Chain for PolkadotLikeChain {
+    type BlockNumber = u32;
+    type Hash = H256;
+    type Hasher = BlakeTwo256;
+    type Header = sp_runtime::generic::Header<u32, BlakeTwo256>;
+}
+
+impl pallet_grandpa_finality_verifier::Config<PolkadotBridgeInstance> for Runtime {
+    type BridgedChain = PolkadotLikeChain;
+    type HeadersToStore = HeadersToStore;
+    type WeightInfo = ();
+}
+
+type KusamaBridgeInstance = t3rn_primitives::portal::KusamaBridge;
+
+#[derive(Debug)]
+pub struct KusamaLikeChain;
+impl bp_runtime::Chain for KusamaLikeChain {
+    type BlockNumber = u32;
+    type Hash = H256;
+    type Hasher = BlakeTwo256;
+    type Header = sp_runtime::generic::Header<u32, BlakeTwo256>;
+}
+
+impl pallet_grandpa_finality_verifier::Config<KusamaBridgeInstance> for Runtime {
+    type BridgedChain = KusamaLikeChain;
+    type HeadersToStore = HeadersToStore;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const EthereumHeadersToKeep: u32 = 500;
+    pub const VerifyEthereumPoW: bool = true;
+}
+
+impl pallet_ethereum_finality_verifier::Config for Runtime {
+    type HeadersToKeep = EthereumHeadersToKeep;
+    type VerifyPoW = VerifyEthereumPoW;
+}
+
 // MinRoundTerm plays a crucial role:
 //  + must at least be the size of the active collator set
 //  + is applied as default round term during genesis
@@ -124,6 +235,7 @@ impl pallet_treasury::Config for Runtime {
     type InflationRegressionMonths = InflationRegressionMonths;
     type MinRoundTerm = MinRoundTerm;
     type ReserveAccount = ReserveAccount;
+    type StakeSource = Executors;
     type TreasuryAccount = TreasuryAccount;
     type WeightInfo = pallet_treasury::weights::TreasuryWeight<Runtime>;
 }
@@ -53,9 +53,10 @@ frame_support::construct_runtime!(
         // t3rn pallets
         XDNS: pallet_xdns::{Pallet, Call, Config<T>, Storage, Event<T>} = 100,
         ContractsRegistry: pallet_contracts_registry::{Pallet, Call, Config<T>, Storage, Event<T>} = 106,
-        Circuit: pallet_circuit::{Pallet, Call, Storage, Event<T>} = 108,
+        Circuit: pallet_circuit::{Pallet, Call, Config<T>, Storage, Event<T>} = 108,
         Treasury: pallet_treasury = 109,
-        Clock: pallet_clock::{Pallet, Storage, Event<T>} = 110,
+        Clock: pallet_clock::{Pallet, Call, Storage, Event<T>} = 110,
+        Executors: pallet_executors = 113,
 
         XBIPortal: pallet_xbi_portal::{Pallet, Call, Storage, Event<T>} = 111,
         XBIPortalEnter: pallet_xbi_portal_enter::{Pallet, Call, Event<T>} = 112,
@@ -70,6 +71,15 @@ frame_support::construct_runtime!(
         RococoBridge: pallet_grandpa_finality_verifier::{
             Pallet, Storage
         } = 129,
+        EthereumBridge: pallet_ethereum_finality_verifier::{
+            Pallet, Storage
+        } = 130,
+        PolkadotBridge: pallet_grandpa_finality_verifier::<Instance1>::{
+            Pallet, Storage
+        } = 131,
+        KusamaBridge: pallet_grandpa_finality_verifier::<Instance2>::{
+            Pallet, Storage
+        } = 132,
     }
 );
 
@@ -86,6 +96,8 @@ pub struct ExtBuilder {
     known_xdns_records: Vec<XdnsRecord<AccountId>>,
     standard_side_effects: Vec<SideEffectInterface>,
     known_contracts: Vec<RegistryContract<H256, AccountId, Balance, BlockNumber>>,
+    active_executors: Vec<AccountId>,
+    relayers: Vec<AccountId>,
 }
 
 impl ExtBuilder {
@@ -250,6 +262,23 @@ impl ExtBuilder {
         self
     }
 
+    pub fn with_active_executors(mut self, executors: Vec<AccountId>) -> ExtBuilder {
+        self.active_executors = executors;
+        self
+    }
+
+    pub fn with_relayers(mut self, relayers: Vec<AccountId>) -> ExtBuilder {
+        self.relayers = relayers;
+        self
+    }
+
+    /// Lets the default test executors bond and confirm side effects.
+    pub fn with_default_executors_and_relayers(self) -> ExtBuilder {
+        let accounts = vec![EXECUTOR_DEFAULT, EXECUTOR_SECOND, BOB_RELAYER];
+        self.with_active_executors(accounts.clone())
+            .with_relayers(accounts)
+    }
+
     pub fn build(self) -> sp_io::TestExternalities {
         let mut t = frame_system::GenesisConfig::default()
             .build_storage::<Runtime>()
@@ -266,12 +295,24 @@ impl ExtBuilder {
         .assimilate_storage(&mut t)
         .expect("Pallet xdns can be assimilated");
 
+        pallet_circuit::GenesisConfig::<Runtime> {
+            relayers: self.relayers,
+        }
+        .assimilate_storage(&mut t)
+        .expect("Pallet circuit can be assimilated");
+
         // pallet_executors::GenesisConfig::<Runtime>::default()
         //     .assimilate_storage(&mut t)
         //     .expect("mock pallet-staking genesis storage assimilation");
 
+        let mut active_executors = self.active_executors;
+        active_executors.sort();
+
         let mut ext = sp_io::TestExternalities::new(t);
-        ext.execute_with(|| System::set_block_number(1));
+        ext.execute_with(|| {
+            pallet_executors::ActiveSet::<Runtime>::put(active_executors);
+            System::set_block_number(1)
+        });
         ext
     }
 }
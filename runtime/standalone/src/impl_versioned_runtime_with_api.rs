@@ -1,9 +1,14 @@
 use super::*;
 
 use pallet_3vm_evm::AddressMapping;
+use pallet_circuit_rpc_runtime_api::{
+    FullSideEffect, InsuranceDeposit, OpenSideEffect, XExecSignal,
+};
+use pallet_clock_rpc_runtime_api::{BenefitSource, CircuitRole};
 use pallet_grandpa::{
     fg_primitives, AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList,
 };
+use pallet_treasury_rpc_runtime_api::RoundIndex;
 use pallet_xdns_rpc_runtime_api::{ChainId, FetchXdnsRecordsResponse, GatewayABIConfig};
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -52,10 +57,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     //   `spec_version`, and `authoring_version` are the same between Wasm and native.
     // This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
     //   the compatible custom types.
-    spec_version: 2,
+    spec_version: 3,
     impl_version: 2,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 1,
+    transaction_version: 2,
     state_version: 1,
 };
 
@@ -307,6 +312,50 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_circuit_rpc_runtime_api::CircuitRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash> for Runtime {
+        fn get_xtx(xtx_id: Hash) -> Option<XExecSignal<AccountId, BlockNumber, Balance>> {
+            Circuit::get_x_exec_signals(xtx_id)
+        }
+
+        fn get_full_side_effects(
+            xtx_id: Hash,
+        ) -> Option<Vec<Vec<FullSideEffect<AccountId, BlockNumber, Balance>>>> {
+            Circuit::get_full_side_effects(xtx_id)
+        }
+
+        fn get_open_insurance_deposits(
+        ) -> Vec<(Hash, Hash, InsuranceDeposit<AccountId, BlockNumber, Balance>)> {
+            Circuit::get_open_insurance_deposits()
+        }
+
+        fn get_open_side_effects(
+            target: ChainId,
+            kind: [u8; 4],
+        ) -> Vec<(Hash, OpenSideEffect<AccountId, Hash, BlockNumber, Balance>)> {
+            Circuit::get_open_side_effects_by_kind(target, kind)
+        }
+
+        fn get_xtx_by_requester(
+            requester: AccountId,
+            offset: u32,
+            limit: u32,
+        ) -> Vec<(Hash, XExecSignal<AccountId, BlockNumber, Balance>)> {
+            Circuit::get_xtx_by_requester(&requester, offset, limit)
+        }
+    }
+
+    impl pallet_clock_rpc_runtime_api::ClockRuntimeApi<Block, AccountId, Balance> for Runtime {
+        fn get_pending_claims(beneficiary: AccountId) -> Vec<(CircuitRole, BenefitSource, Balance)> {
+            Clock::get_pending_claims_by_beneficiary(&beneficiary)
+        }
+    }
+
+    impl pallet_treasury_rpc_runtime_api::TreasuryRuntimeApi<Block, Balance> for Runtime {
+        fn get_issuance_history(from: RoundIndex, to: RoundIndex) -> Vec<(RoundIndex, Balance)> {
+            Treasury::get_issuance_history(from, to)
+        }
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     impl frame_benchmarking::Benchmark<Block> for Runtime {
         fn benchmark_metadata(extra: bool) -> (
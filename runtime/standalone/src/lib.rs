@@ -78,9 +78,10 @@ construct_runtime!(
         // t3rn pallets
         XDNS: pallet_xdns::{Pallet, Call, Config<T>, Storage, Event<T>} = 100,
         ContractsRegistry: pallet_contracts_registry::{Pallet, Call, Config<T>, Storage, Event<T>} = 106,
-        Circuit: pallet_circuit::{Pallet, Call, Storage, Event<T>} = 108,
+        Circuit: pallet_circuit::{Pallet, Call, Config<T>, Storage, Event<T>} = 108,
         Treasury: pallet_treasury = 109,
-        Clock: pallet_clock::{Pallet, Storage, Event<T>} = 110,
+        Clock: pallet_clock::{Pallet, Call, Storage, Event<T>} = 110,
+        Executors: pallet_executors = 113,
 
         XBIPortal: pallet_xbi_portal::{Pallet, Call, Storage, Event<T>} = 111,
 
@@ -94,6 +95,15 @@ construct_runtime!(
         RococoBridge: pallet_grandpa_finality_verifier::{
             Pallet, Storage
         } = 129,
+        EthereumBridge: pallet_ethereum_finality_verifier::{
+            Pallet, Storage
+        } = 130,
+        PolkadotBridge: pallet_grandpa_finality_verifier::<Instance1>::{
+            Pallet, Storage
+        } = 131,
+        KusamaBridge: pallet_grandpa_finality_verifier::<Instance2>::{
+            Pallet, Storage
+        } = 132,
 
     }
 );
@@ -6,16 +6,21 @@ use sp_core::crypto::AccountId32;
 parameter_types! {
     // TODO: update me to be better
     pub EscrowAccount: AccountId32 = AccountId32::new([55_u8; 32]);
+    pub const ChargeTimeoutRounds: u32 = 10;
+    pub const StaleChargesSweepLimit: u32 = 100;
 }
 
 impl pallet_account_manager::Config for Runtime {
+    type ChargeTimeoutRounds = ChargeTimeoutRounds;
     type Clock = Clock;
     type Currency = Balances;
     type EscrowAccount = EscrowAccount;
     type Event = Event;
-    type Executors = t3rn_primitives::executors::ExecutorsMock<Self>;
+    type Executors = Executors;
+    type StaleChargesSweepLimit = StaleChargesSweepLimit;
     type Time = Timestamp;
     type WeightInfo = ();
+    type XtxTimeoutMax = <Runtime as pallet_circuit::Config>::XtxTimeoutMax;
 }
 
 pallet_account_manager::setup_currency_adapter!();
@@ -20,6 +20,11 @@ pub use circuit_runtime_types::*;
 
 use codec::Decode;
 use pallet_3vm_evm::AddressMapping;
+use pallet_circuit_rpc_runtime_api::{
+    FullSideEffect, InsuranceDeposit, OpenSideEffect, XExecSignal,
+};
+use pallet_clock_rpc_runtime_api::{BenefitSource, CircuitRole};
+use pallet_treasury_rpc_runtime_api::RoundIndex;
 use pallet_xdns_rpc_runtime_api::{ChainId, FetchXdnsRecordsResponse, GatewayABIConfig};
 
 use sp_api::impl_runtime_apis;
@@ -76,10 +81,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("t0rn"),
     impl_name: create_runtime_str!("Circuit Collator"),
     authoring_version: 2,
-    spec_version: 3,
+    spec_version: 4,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 2,
+    transaction_version: 3,
     // https://github.com/paritytech/cumulus/issues/998
     // https://github.com/paritytech/substrate/pull/9732
     // https://github.com/paritytech/substrate/pull/10073
@@ -144,9 +149,10 @@ construct_runtime!(
         // t3rn pallets
         XDNS: pallet_xdns::{Pallet, Call, Config<T>, Storage, Event<T>} = 100,
         ContractsRegistry: pallet_contracts_registry::{Pallet, Call, Config<T>, Storage, Event<T>} = 106,
-        Circuit: pallet_circuit::{Pallet, Call, Storage, Event<T>} = 108,
+        Circuit: pallet_circuit::{Pallet, Call, Config<T>, Storage, Event<T>} = 108,
         Treasury: pallet_treasury = 109,
-        Clock: pallet_clock::{Pallet, Storage, Event<T>} = 110,
+        Clock: pallet_clock::{Pallet, Call, Storage, Event<T>} = 110,
+        Executors: pallet_executors = 113,
 
         // 3VM
         ThreeVm: pallet_3vm = 119,
@@ -162,6 +168,15 @@ construct_runtime!(
         RococoBridge: pallet_grandpa_finality_verifier::{
             Pallet, Storage
         } = 129,
+        EthereumBridge: pallet_ethereum_finality_verifier::{
+            Pallet, Storage
+        } = 130,
+        PolkadotBridge: pallet_grandpa_finality_verifier::<Instance1>::{
+            Pallet, Storage
+        } = 131,
+        KusamaBridge: pallet_grandpa_finality_verifier::<Instance2>::{
+            Pallet, Storage
+        } = 132,
 
         // admin
         Sudo: pallet_sudo::{Pallet, Call, Config<T>, Storage, Event<T>} = 255,
@@ -376,6 +391,50 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_circuit_rpc_runtime_api::CircuitRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash> for Runtime {
+        fn get_xtx(xtx_id: Hash) -> Option<XExecSignal<AccountId, BlockNumber, Balance>> {
+            Circuit::get_x_exec_signals(xtx_id)
+        }
+
+        fn get_full_side_effects(
+            xtx_id: Hash,
+        ) -> Option<Vec<Vec<FullSideEffect<AccountId, BlockNumber, Balance>>>> {
+            Circuit::get_full_side_effects(xtx_id)
+        }
+
+        fn get_open_insurance_deposits(
+        ) -> Vec<(Hash, Hash, InsuranceDeposit<AccountId, BlockNumber, Balance>)> {
+            Circuit::get_open_insurance_deposits()
+        }
+
+        fn get_open_side_effects(
+            target: ChainId,
+            kind: [u8; 4],
+        ) -> Vec<(Hash, OpenSideEffect<AccountId, Hash, BlockNumber, Balance>)> {
+            Circuit::get_open_side_effects_by_kind(target, kind)
+        }
+
+        fn get_xtx_by_requester(
+            requester: AccountId,
+            offset: u32,
+            limit: u32,
+        ) -> Vec<(Hash, XExecSignal<AccountId, BlockNumber, Balance>)> {
+            Circuit::get_xtx_by_requester(&requester, offset, limit)
+        }
+    }
+
+    impl pallet_clock_rpc_runtime_api::ClockRuntimeApi<Block, AccountId, Balance> for Runtime {
+        fn get_pending_claims(beneficiary: AccountId) -> Vec<(CircuitRole, BenefitSource, Balance)> {
+            Clock::get_pending_claims_by_beneficiary(&beneficiary)
+        }
+    }
+
+    impl pallet_treasury_rpc_runtime_api::TreasuryRuntimeApi<Block, Balance> for Runtime {
+        fn get_issuance_history(from: RoundIndex, to: RoundIndex) -> Vec<(RoundIndex, Balance)> {
+            Treasury::get_issuance_history(from, to)
+        }
+    }
+
     impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
         fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
             ParachainSystem::collect_collation_info(header)
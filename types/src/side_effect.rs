@@ -6,6 +6,9 @@ use scale_info::{
     TypeInfo,
 };
 
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
 #[cfg(feature = "runtime")]
 use num::Zero;
 #[cfg(feature = "runtime")]
@@ -29,6 +32,7 @@ pub const SWAP_SIDE_EFFECT_ID: &[u8; 4] = b"swap";
 pub const DATA_SIDE_EFFECT_ID: &[u8; 4] = b"data";
 
 #[derive(Clone, Eq, PartialEq, Encode, Default, Decode, Debug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct SideEffect<AccountId, BlockNumber, BalanceOf> {
     pub target: TargetId,
     pub prize: BalanceOf,
@@ -333,6 +337,7 @@ fn take_insurance<Balance: MaxEncodedLen>(bytes: &mut bytes::Bytes, args: &mut V
 }
 
 #[derive(Clone, Eq, PartialEq, Encode, Decode, Debug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum ConfirmationOutcome {
     Success,
     MisbehaviourMalformedValues {
@@ -350,6 +355,7 @@ impl Default for ConfirmationOutcome {
 }
 
 #[derive(Clone, Eq, PartialEq, Encode, Decode, Debug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct ConfirmedSideEffect<AccountId, BlockNumber, BalanceOf> {
     pub err: Option<ConfirmationOutcome>,
     pub output: Option<Bytes>,
@@ -360,6 +366,7 @@ pub struct ConfirmedSideEffect<AccountId, BlockNumber, BalanceOf> {
 }
 
 #[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Encode, Decode, Debug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum SecurityLvl {
     Dirty,
     Optimistic,